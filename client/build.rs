@@ -8,6 +8,23 @@ fn get_float(value: &toml::Value, key: &str) -> f32 {
         .unwrap_or_else(|| panic!("Missing or invalid {}", key))
 }
 
+fn get_bool(value: &toml::Value, key: &str) -> bool {
+    value.as_bool()
+        .unwrap_or_else(|| panic!("Missing or invalid {}", key))
+}
+
+/// FNV-1a hash of the parsed constants (must match server/build.rs)
+///
+/// Hashes the values as re-serialized by `toml`, so comments and formatting
+/// don't change it. Exchanged in the handshake to catch client/server builds
+/// made from different `game-constants.toml` files.
+fn constants_hash(config: &toml::Value) -> u64 {
+    let canonical = toml::to_string(config).expect("Failed to serialize game-constants.toml");
+    canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
 fn main() {
     // Read the shared constants file
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
@@ -25,6 +42,9 @@ fn main() {
     // Extract values
     let physics = config.get("physics").expect("Missing [physics] section");
     let gameplay = config.get("gameplay").expect("Missing [gameplay] section");
+    let collision = config.get("collision").expect("Missing [collision] section");
+    let damage = config.get("damage").expect("Missing [damage] section");
+    let pickups = config.get("pickups").expect("Missing [pickups] section");
     
     let thrust_accel = get_float(physics.get("thrust_accel").unwrap(), "thrust_accel");
     let rotation_speed = get_float(physics.get("rotation_speed").unwrap(), "rotation_speed");
@@ -32,6 +52,15 @@ fn main() {
     let drag = get_float(physics.get("drag").unwrap(), "drag");
     let world_limit = get_float(physics.get("world_limit").unwrap(), "world_limit");
     let ship_radius = get_float(gameplay.get("ship_radius").unwrap(), "ship_radius");
+    let collision_response = get_bool(collision.get("response").unwrap(), "response");
+    let restitution = get_float(collision.get("restitution").unwrap(), "restitution");
+    let polygon_hitboxes = get_bool(collision.get("polygon_hitboxes").unwrap(), "polygon_hitboxes");
+    let max_health = get_float(damage.get("max_health").unwrap(), "max_health");
+    let max_shield = get_float(damage.get("max_shield").unwrap(), "max_shield");
+    let pickup_radius = get_float(pickups.get("radius").unwrap(), "radius");
+    let pickup_duration = get_float(pickups.get("duration").unwrap(), "duration");
+    let speed_boost = get_float(pickups.get("speed_boost").unwrap(), "speed_boost");
+    let rapid_fire = get_float(pickups.get("rapid_fire").unwrap(), "rapid_fire");
     
     // Generate the constants.rs file
     let out_dir = env::var("OUT_DIR").unwrap();
@@ -41,6 +70,8 @@ fn main() {
         r#"// This file is auto-generated by build.rs from game-constants.toml
 // DO NOT EDIT MANUALLY - your changes will be overwritten
 
+pub const CONSTANTS_HASH: u64 = {};  // compared with the server in the handshake
+
 // Client-specific constants
 pub const CAMERA_DECAY_RATE: f32 = 14.0;
 
@@ -51,13 +82,32 @@ pub const MAX_SPEED: f32 = {:.1};     // pixels/sec
 pub const DRAG: f32 = {};            // velocity multiplier per frame
 pub const WORLD_LIMIT: f32 = {:.1};    // world boundary for wrapping
 pub const SHIP_RADIUS: f32 = {:.1};    // ship collision radius
+pub const COLLISION_RESPONSE: bool = {};  // bounce instead of destroy on contact
+pub const RESTITUTION: f32 = {:?};  // fraction of closing speed kept after a bounce
+pub const POLYGON_HITBOXES: bool = {};  // collide with asteroid outlines, not bounding circles
+pub const MAX_HEALTH: f32 = {:?};  // hull points (health bar scale)
+pub const MAX_SHIELD: f32 = {:?};  // shield points (shield bar scale)
+pub const PICKUP_RADIUS: f32 = {:?};  // collection radius (pixels)
+pub const PICKUP_DURATION: f32 = {:?};  // seconds timed effects last
+pub const SPEED_BOOST: f32 = {:?};  // thrust and top speed multiplier while boosted
+pub const RAPID_FIRE: f32 = {:?};  // fire rate multiplier with rapid fire
 "#,
+        constants_hash(&config),
         thrust_accel,
         rotation_speed,
         max_speed,
         drag,
         world_limit,
         ship_radius,
+        collision_response,
+        restitution,
+        polygon_hitboxes,
+        max_health,
+        max_shield,
+        pickup_radius,
+        pickup_duration,
+        speed_boost,
+        rapid_fire,
     );
     
     fs::write(&dest_path, generated_code)
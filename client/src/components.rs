@@ -13,6 +13,10 @@ pub struct MapBoundary;
 #[derive(Component, Default)]
 pub struct Velocity(pub Vec2);
 
+// Spin in radians/sec (asteroids)
+#[derive(Component, Default)]
+pub struct AngularVelocity(pub f32);
+
 #[derive(Component)]
 pub struct Thruster {
     pub base_length: f32,
@@ -58,6 +62,18 @@ pub struct NetworkedPlayer {
 #[derive(Component)]
 pub struct NetworkedAsteroid {
     pub id: u32,
+    pub radius: f32,
+}
+
+#[derive(Component)]
+pub struct NetworkedPickup {
+    pub id: u32,
+}
+
+// Player name drawn above a remote ship (top-level so it doesn't rotate with it)
+#[derive(Component)]
+pub struct NameLabel {
+    pub owner: bevy::prelude::Entity,
 }
 
 // Invincibility tracking for ships after respawn
@@ -1,7 +1,7 @@
 use battlestar_shared::Color as NetColor;
 use bevy::prelude::*;
-use crate::components::{NetworkedPlayer, NetworkedAsteroid, Player, Ship, Velocity, Thruster, ThrusterOwner, Invincible};
-use crate::entities::{build_triangle_mesh, build_thruster_mesh, build_circle_mesh};
+use crate::components::{AngularVelocity, NameLabel, NetworkedPickup, NetworkedPlayer, NetworkedAsteroid, Player, Ship, Velocity, Thruster, ThrusterOwner, Invincible};
+use crate::entities::{build_triangle_mesh, build_thruster_mesh, build_polygon_mesh};
 
 /// Spawn a player's local ship
 pub fn spawn_local_ship(
@@ -64,6 +64,7 @@ pub fn spawn_networked_ship(
     id: u32,
     position: Vec3,
     color: NetColor,
+    name: &str,
 ) -> Entity {
     let bevy_color = Color::srgb(color.r, color.g, color.b);
 
@@ -80,6 +81,20 @@ pub fn spawn_networked_ship(
 
     spawn_thruster_for_ship(commands, meshes, materials, ship_entity);
 
+    // Name label, kept above the ship by `update_name_labels`
+    if !name.is_empty() {
+        commands.spawn((
+            Text2d::new(name),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(bevy_color),
+            Transform::from_translation(position),
+            NameLabel { owner: ship_entity },
+        ));
+    }
+
     ship_entity
 }
 
@@ -107,22 +122,61 @@ fn spawn_thruster_for_ship(
     commands.entity(ship_entity).add_child(thruster_entity);
 }
 
-/// Spawn a networked asteroid
+/// Spawn a networked asteroid (its outline is rebuilt from the id)
 pub fn spawn_networked_asteroid(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<ColorMaterial>,
-    id: u32,
-    position: Vec3,
-    radius: f32,
+    asteroid: &battlestar_shared::Asteroid,
 ) -> Entity {
+    let outline = battlestar_shared::entities::asteroid::asteroid_outline(asteroid.id, asteroid.radius);
+
     commands
         .spawn((
-            Mesh2d(meshes.add(build_circle_mesh(radius, 32))),
+            Mesh2d(meshes.add(build_polygon_mesh(&outline))),
             MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
-            Transform::from_translation(position),
-            NetworkedAsteroid { id },
-            Velocity::default(),
+            Transform::from_xyz(asteroid.position.x, asteroid.position.y, 0.0)
+                .with_rotation(Quat::from_rotation_z(asteroid.rotation)),
+            NetworkedAsteroid {
+                id: asteroid.id,
+                radius: asteroid.radius,
+            },
+            Velocity(Vec2::new(asteroid.velocity.x, asteroid.velocity.y)),
+            AngularVelocity(asteroid.angular_velocity),
+        ))
+        .id()
+}
+
+/// Spawn a networked pickup (a diamond colored by what it does)
+pub fn spawn_networked_pickup(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    pickup: &battlestar_shared::entities::Pickup,
+) -> Entity {
+    use battlestar_shared::entities::PickupKind;
+    use battlestar_shared::Vec2 as NetVec2;
+
+    let r = crate::constants::PICKUP_RADIUS;
+    let diamond = [
+        NetVec2::new(r, 0.0),
+        NetVec2::new(0.0, r),
+        NetVec2::new(-r, 0.0),
+        NetVec2::new(0.0, -r),
+    ];
+    let color = match pickup.kind {
+        PickupKind::ShieldRecharge => Color::srgb(0.3, 0.8, 1.0),
+        PickupKind::RapidFire => Color::srgb(1.0, 0.5, 0.1),
+        PickupKind::SpeedBoost => Color::srgb(0.3, 1.0, 0.3),
+        PickupKind::Invincibility => Color::srgb(1.0, 0.9, 0.2),
+    };
+
+    commands
+        .spawn((
+            Mesh2d(meshes.add(build_polygon_mesh(&diamond))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(color))),
+            Transform::from_xyz(pickup.position.x, pickup.position.y, 0.5),
+            NetworkedPickup { id: pickup.id },
         ))
         .id()
 }
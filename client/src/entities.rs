@@ -129,25 +129,18 @@ pub fn build_triangle_mesh(radius: f32) -> Mesh {
     mesh
 }
 
-pub fn build_circle_mesh(radius: f32, segments: usize) -> Mesh {
-    use std::f32::consts::PI;
-    
+/// Filled polygon from an outline that is star-shaped around the origin
+/// (triangle fan from the center, e.g. `asteroid_outline`)
+pub fn build_polygon_mesh(outline: &[battlestar_shared::Vec2]) -> Mesh {
     let mut positions = vec![[0.0, 0.0, 0.0]]; // Center
-    
-    for i in 0..=segments {
-        let angle = (i as f32) * 2.0 * PI / (segments as f32);
-        positions.push([
-            radius * angle.cos(),
-            radius * angle.sin(),
-            0.0,
-        ]);
-    }
-    
+    positions.extend(outline.iter().map(|vertex| [vertex.x, vertex.y, 0.0]));
+
+    let count = outline.len() as u32;
     let mut indices = Vec::new();
-    for i in 1..=segments {
+    for i in 1..=count {
         indices.push(0u32);
-        indices.push(i as u32);
-        indices.push((i % segments) as u32 + 1);
+        indices.push(i);
+        indices.push(i % count + 1);
     }
 
     let mut mesh = Mesh::new(
@@ -32,6 +32,9 @@ fn build_app() -> App {
                 systems::setup::setup_instructions,
                 systems::camera::setup_camera,
                 systems::joystick::setup_joystick,
+                systems::chat::setup_chat,
+                systems::ping::setup_ping_text,
+                systems::diagnostics::setup_diagnostics,
                 net::setup_network,
             ),
         )
@@ -41,25 +44,80 @@ fn build_app() -> App {
                 net::poll_connection_state,
                 systems::joystick::update_joystick,
                 net::gather_player_input
-                    .after(systems::joystick::update_joystick),
+                    .after(systems::joystick::update_joystick)
+                    .after(systems::chat::handle_chat_keys),  // No ship control while typing
                 net::send_player_input
-                    .after(net::gather_player_input),
-                net::receive_game_state,
+                    .after(net::gather_player_input)
+                    .run_if(in_state(systems::screens::MatchState::Playing)),
+                systems::pickups::tick_local_modifiers
+                    .after(net::ReceiveSet),
                 systems::movement::apply_local_physics
                     .after(net::gather_player_input)
-                    .after(net::receive_game_state),  // CRUCIAL: Apply local physics AFTER server updates
+                    .after(systems::pickups::tick_local_modifiers)
+                    .after(net::ReceiveSet)  // CRUCIAL: Apply local physics AFTER server updates
+                    .run_if(in_state(systems::screens::MatchState::Playing)),  // Server ignores input outside a round
+                systems::movement::predict_local_collisions
+                    .after(systems::movement::apply_local_physics)
+                    .run_if(in_state(systems::screens::MatchState::Playing)),
                 systems::movement::update_asteroids,  // Update asteroid positions locally
                 systems::movement::update_thruster_length
-                    .after(systems::movement::apply_local_physics),
+                    .after(systems::movement::predict_local_collisions),
                 systems::camera::update_camera
-                    .after(systems::movement::apply_local_physics),
+                    .after(systems::movement::predict_local_collisions),
                 net::update_local_ship_color,
                 systems::invincibility::blink_invincible_ships,  // Blink effect for invincible ships
+                systems::pickups::spin_pickups,
+                systems::health_bar::attach_health_bars,
+                systems::health_bar::update_health_bars
+                    .after(systems::movement::predict_local_collisions),
+                systems::name_labels::update_name_labels
+                    .after(systems::movement::predict_local_collisions),
+                systems::screens::sync_match_state
+                    .after(net::ReceiveSet),
+                systems::screens::update_screen_text
+                    .after(net::ReceiveSet),
+            ),
+        )
+        .add_systems(
+            Update,
+            (net::read_server_messages, net::handle_session_messages, net::apply_snapshots)
+                .chain()
+                .in_set(net::ReceiveSet),
+        )
+        .add_systems(
+            Update,
+            (
+                systems::chat::handle_chat_keys,
+                systems::chat::update_chat_box
+                    .after(systems::chat::handle_chat_keys)
+                    .after(net::ReceiveSet),
+                net::exchange_pings
+                    .after(net::ReceiveSet),
+                systems::ping::update_ping_text
+                    .after(net::exchange_pings),
+                net::update_traffic_rates,
+                systems::diagnostics::toggle_diagnostics,
+                systems::diagnostics::update_diagnostics
+                    .after(systems::diagnostics::toggle_diagnostics)
+                    .after(net::update_traffic_rates)
+                    .after(net::exchange_pings),
             ),
         )
+        .add_message::<net::ServerMessageReceived>()
+        .init_state::<systems::screens::MatchState>()
+        .add_systems(OnEnter(systems::screens::MatchState::Lobby), systems::screens::spawn_screen)
+        .add_systems(OnEnter(systems::screens::MatchState::Countdown), systems::screens::spawn_screen)
+        .add_systems(OnEnter(systems::screens::MatchState::Results), systems::screens::spawn_screen)
+        .add_systems(OnEnter(systems::screens::MatchState::Incompatible), systems::screens::spawn_screen)
         .insert_resource(net::PlayerInput::default())
         .insert_resource(net::PlayerColor::default())
         .insert_resource(net::LocalShipEntity::default())
+        .insert_resource(net::ShipVitals::default())
+        .insert_resource(net::LocalModifiers::default())
+        .insert_resource(systems::screens::MatchStatus::default())
+        .insert_resource(systems::chat::ChatState::default())
+        .insert_resource(net::NetworkClock::default())
+        .insert_resource(net::NetDiagnostics::default())
         .insert_resource(net::InputThrottle::default());
     app
 }
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::transport::NetworkClient;
+
+/// Corrections kept for the overlay graph (3 seconds of updates at 20 Hz)
+pub const CORRECTION_HISTORY: usize = 60;
+
+/// Messages and bytes through the WebSocket, bumped by the socket callbacks
+/// and `WebSocketHandle::send`
+#[derive(Default)]
+pub struct TrafficCounters {
+    packets_in: AtomicU64,
+    bytes_in: AtomicU64,
+    packets_out: AtomicU64,
+    bytes_out: AtomicU64,
+}
+
+impl TrafficCounters {
+    pub fn record_in(&self, bytes: usize) {
+        self.packets_in.fetch_add(1, Ordering::Relaxed);
+        self.bytes_in.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_out(&self, bytes: usize) {
+        self.packets_out.fetch_add(1, Ordering::Relaxed);
+        self.bytes_out.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    fn totals(&self) -> TrafficRates {
+        TrafficRates {
+            packets_in: self.packets_in.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            packets_out: self.packets_out.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Traffic over one second (or running totals)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrafficRates {
+    pub packets_in: u64,
+    pub bytes_in: u64,
+    pub packets_out: u64,
+    pub bytes_out: u64,
+}
+
+/// What the F3 overlay shows, collected while receiving updates
+#[derive(Resource)]
+pub struct NetDiagnostics {
+    /// Traffic during the last full second
+    pub rates: TrafficRates,
+    /// Tick of the latest state update
+    pub last_server_tick: Option<u64>,
+    /// Distance between the predicted local ship and each server update
+    pub corrections: VecDeque<f32>,
+    totals: TrafficRates,
+    timer: Timer,
+}
+
+impl Default for NetDiagnostics {
+    fn default() -> Self {
+        Self {
+            rates: TrafficRates::default(),
+            last_server_tick: None,
+            corrections: VecDeque::with_capacity(CORRECTION_HISTORY),
+            totals: TrafficRates::default(),
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+        }
+    }
+}
+
+impl NetDiagnostics {
+    pub fn record_tick(&mut self, tick: u64) {
+        self.last_server_tick = Some(tick);
+    }
+
+    pub fn record_correction(&mut self, distance: f32) {
+        if self.corrections.len() == CORRECTION_HISTORY {
+            self.corrections.pop_front();
+        }
+        self.corrections.push_back(distance);
+    }
+}
+
+/// Turn the traffic counters into per-second rates, once a second
+pub fn update_traffic_rates(
+    client: Res<NetworkClient>,
+    mut diagnostics: ResMut<NetDiagnostics>,
+    time: Res<Time<Real>>,
+) {
+    diagnostics.timer.tick(time.delta());
+    if !diagnostics.timer.just_finished() {
+        return;
+    }
+
+    let totals = client.traffic.totals();
+    let previous = diagnostics.totals;
+    diagnostics.rates = TrafficRates {
+        packets_in: totals.packets_in - previous.packets_in,
+        bytes_in: totals.bytes_in - previous.bytes_in,
+        packets_out: totals.packets_out - previous.packets_out,
+        bytes_out: totals.bytes_out - previous.bytes_out,
+    };
+    diagnostics.totals = totals;
+}
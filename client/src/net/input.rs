@@ -1,7 +1,8 @@
-use battlestar_shared::ClientInput;
+use battlestar_shared::{ClientInput, ClientMessage};
 use bevy::prelude::*;
 
 use crate::components::Joystick;
+use crate::systems::chat::ChatState;
 use super::transport::{NetworkClient, WebSocketHandle};
 
 /// Shared input state gathered each frame from keyboard + joystick.
@@ -29,8 +30,11 @@ impl Default for InputThrottle {
 }
 
 /// Gather input from keyboard and mobile joystick into shared PlayerInput resource
+///
+/// The keyboard is ignored while the chat box is open.
 pub fn gather_player_input(
     kb_input: Res<ButtonInput<KeyCode>>,
+    chat: Res<ChatState>,
     joystick_query: Query<&Joystick>,
     mut player_input: ResMut<PlayerInput>,
 ) {
@@ -38,17 +42,19 @@ pub fn gather_player_input(
     let mut rotate = 0.0;
 
     // Keyboard
-    if kb_input.pressed(KeyCode::KeyW) {
-        thrust += 1.0;
-    }
-    if kb_input.pressed(KeyCode::KeyS) {
-        thrust -= 1.0;
-    }
-    if kb_input.pressed(KeyCode::KeyA) {
-        rotate -= 1.0;
-    }
-    if kb_input.pressed(KeyCode::KeyD) {
-        rotate += 1.0;
+    if !chat.typing {
+        if kb_input.pressed(KeyCode::KeyW) {
+            thrust += 1.0;
+        }
+        if kb_input.pressed(KeyCode::KeyS) {
+            thrust -= 1.0;
+        }
+        if kb_input.pressed(KeyCode::KeyA) {
+            rotate -= 1.0;
+        }
+        if kb_input.pressed(KeyCode::KeyD) {
+            rotate += 1.0;
+        }
     }
 
     // Mobile joystick — takes priority when active
@@ -89,13 +95,11 @@ pub fn send_player_input(
         return;
     }
 
-    let input = ClientInput {
+    let input = ClientMessage::Input(ClientInput {
         player_id: client.player_id,
         thrust: player_input.thrust,
         rotate: player_input.rotate,
-    };
+    });
 
-    if let Ok(json) = serde_json::to_string(&input) {
-        let _ = ws_handle.ws.send_with_str(&json);
-    }
+    ws_handle.send(&input);
 }
@@ -0,0 +1,87 @@
+use battlestar_shared::{
+    network::{ClockSync, RttEstimator},
+    ClientMessage,
+};
+use bevy::prelude::*;
+
+use super::transport::{NetworkClient, WebSocketHandle};
+
+/// Round trip and server clock estimates, fed by ping/pong exchanges
+///
+/// `handle_session_messages` queues pings and pongs from the server here;
+/// `exchange_pings` timestamps them with the real clock and answers.
+#[derive(Resource)]
+pub struct NetworkClock {
+    pub rtt: RttEstimator,
+    pub clock: ClockSync,
+    /// Estimated server tick, refreshed every frame
+    pub server_tick: Option<f64>,
+    timer: Timer,
+    /// Pongs to record: (client_time, server_time, tick)
+    pongs: Vec<(f64, f64, u64)>,
+    /// Server pings to answer (their `server_time`)
+    pings: Vec<f64>,
+}
+
+impl Default for NetworkClock {
+    fn default() -> Self {
+        Self {
+            rtt: RttEstimator::new(),
+            clock: ClockSync::new(),
+            server_tick: None,
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            pongs: Vec::new(),
+            pings: Vec::new(),
+        }
+    }
+}
+
+impl NetworkClock {
+    pub fn queue_pong(&mut self, client_time: f64, server_time: f64, tick: u64) {
+        self.pongs.push((client_time, server_time, tick));
+    }
+
+    pub fn queue_ping(&mut self, server_time: f64) {
+        self.pings.push(server_time);
+    }
+}
+
+/// Ping the server every second, answer its pings, update the estimates
+pub fn exchange_pings(
+    client: Res<NetworkClient>,
+    ws_handle: Option<Res<WebSocketHandle>>,
+    mut network_clock: ResMut<NetworkClock>,
+    time: Res<Time<Real>>,
+) {
+    // Real time: keeps running when the game clock is paused or scaled
+    let now = time.elapsed_secs_f64() * 1000.0;
+    let network_clock = &mut *network_clock;
+
+    for (client_time, server_time, tick) in network_clock.pongs.drain(..) {
+        network_clock.rtt.add_sample(now - client_time);
+        network_clock.clock.add_sample(client_time, server_time, tick, now);
+    }
+    network_clock.server_tick = network_clock.clock.server_tick(now);
+
+    let Some(ws_handle) = ws_handle else {
+        return;
+    };
+    if !client.connected {
+        return;
+    }
+
+    let mut outgoing: Vec<ClientMessage> = network_clock
+        .pings
+        .drain(..)
+        .map(|server_time| ClientMessage::Pong { server_time })
+        .collect();
+
+    network_clock.timer.tick(time.delta());
+    if network_clock.timer.just_finished() {
+        outgoing.push(ClientMessage::Ping { client_time: now });
+    }
+
+    for message in &outgoing {
+        ws_handle.send(message);
+    }
+}
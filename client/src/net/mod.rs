@@ -1,7 +1,14 @@
+pub mod diagnostics;
 pub mod input;
+pub mod latency;
 pub mod sync;
 pub mod transport;
 
+pub use diagnostics::{update_traffic_rates, NetDiagnostics};
 pub use input::{gather_player_input, send_player_input, InputThrottle, PlayerInput};
-pub use sync::{receive_game_state, update_local_ship_color, LocalShipEntity, PlayerColor};
-pub use transport::{poll_connection_state, setup_network};
+pub use latency::{exchange_pings, NetworkClock};
+pub use sync::{
+    apply_snapshots, handle_session_messages, read_server_messages, update_local_ship_color, LocalModifiers,
+    LocalShipEntity, PlayerColor, ReceiveSet, ServerMessageReceived, ShipVitals,
+};
+pub use transport::{poll_connection_state, setup_network, NetworkClient};
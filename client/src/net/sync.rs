@@ -1,9 +1,23 @@
-use battlestar_shared::{Color as NetColor, ServerMessage};
+use battlestar_shared::{
+    entities::{Modifier, PickupKind},
+    network::PROTOCOL_VERSION,
+    physics::wrap_position,
+    Color as NetColor, DeltaState, GameState, ServerMessage, Vec2 as NetVec2,
+};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use std::collections::HashSet;
 
-use crate::components::{NetworkedAsteroid, NetworkedPlayer, Invincible};
+use crate::components::{
+    AngularVelocity, Invincible, NetworkedAsteroid, NetworkedPickup, NetworkedPlayer, Player, Velocity,
+};
+use crate::constants::{CONSTANTS_HASH, WORLD_LIMIT};
 use crate::domain;
+use crate::systems::chat::ChatState;
+use crate::systems::screens::{MatchState, MatchStatus};
 
+use super::diagnostics::NetDiagnostics;
+use super::latency::NetworkClock;
 use super::transport::NetworkClient;
 
 #[derive(Resource, Default)]
@@ -15,25 +29,74 @@ pub struct PlayerColor {
 #[derive(Resource, Default)]
 pub struct LocalShipEntity(pub Option<Entity>);
 
-pub fn receive_game_state(
-    mut commands: Commands,
-    mut client: ResMut<NetworkClient>,
-    mut player_color: ResMut<PlayerColor>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut existing_ships: Query<
-        (Entity, &NetworkedPlayer, &mut Transform, &mut crate::components::Velocity, &mut Invincible),
-        Without<crate::components::Player>,
-    >,
-    mut local_player: Query<
-        (&mut Transform, &mut crate::components::Velocity, &mut Invincible),
-        With<crate::components::Player>,
-    >,
-    mut existing_asteroids: Query<
-        (Entity, &NetworkedAsteroid, &mut Transform, &mut crate::components::Velocity),
-        (Without<NetworkedPlayer>, Without<crate::components::Player>),
-    >,
-) {
+/// Hull and shield of one ship, as last reported by the server
+#[derive(Clone, Copy, Debug)]
+pub struct Vitals {
+    pub health: f32,
+    pub shield: f32,
+}
+
+/// Latest hull and shield per ship ID (drawn as bars over ships)
+#[derive(Resource, Default)]
+pub struct ShipVitals(pub std::collections::HashMap<u32, Vitals>);
+
+/// Timed pickup effects on the local ship (counted down locally between updates)
+#[derive(Resource, Default)]
+pub struct LocalModifiers(pub Vec<Modifier>);
+
+/// A message from the server, parsed off the socket this frame
+#[derive(Message)]
+pub struct ServerMessageReceived(pub ServerMessage);
+
+/// Systems turning server messages into game state, in order:
+/// `read_server_messages`, `handle_session_messages`, `apply_snapshots`
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReceiveSet;
+
+type RemoteShips<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static NetworkedPlayer, &'static mut Transform, &'static mut Velocity, &'static mut Invincible),
+    Without<Player>,
+>;
+
+type LocalShip<'w, 's> =
+    Query<'w, 's, (&'static mut Transform, &'static mut Velocity, &'static mut Invincible), With<Player>>;
+
+type Asteroids<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static NetworkedAsteroid,
+        &'static mut Transform,
+        &'static mut Velocity,
+        &'static mut AngularVelocity,
+    ),
+    (Without<NetworkedPlayer>, Without<Player>),
+>;
+
+/// Entities mirroring the server's ships, asteroids and pickups
+#[derive(SystemParam)]
+pub struct NetworkedWorld<'w, 's> {
+    commands: Commands<'w, 's>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+    ships: RemoteShips<'w, 's>,
+    asteroids: Asteroids<'w, 's>,
+    pickups: Query<'w, 's, (Entity, &'static NetworkedPickup)>,
+}
+
+/// What the server says about the local player's own ship
+#[derive(SystemParam)]
+pub struct LocalShipState<'w, 's> {
+    ship: LocalShip<'w, 's>,
+    color: ResMut<'w, PlayerColor>,
+    modifiers: ResMut<'w, LocalModifiers>,
+}
+
+/// Parse what arrived on the socket since the last frame
+pub fn read_server_messages(client: Res<NetworkClient>, mut received: MessageWriter<ServerMessageReceived>) {
     if !client.connected {
         return;
     }
@@ -48,254 +111,445 @@ pub fn receive_game_state(
 
     for msg in messages {
         if let Ok(server_msg) = serde_json::from_str::<ServerMessage>(&msg) {
-            match server_msg {
-                ServerMessage::Welcome { assigned_id } => {
-                    info!("Received player ID: {}", assigned_id);
-                    client.player_id = assigned_id;
-                }
-                ServerMessage::DeltaState(delta_state) => {
-                    // Delta update: only changed ships (90% bandwidth reduction)
-                    // Track removed ships if this is a full state delta
-                    let mut seen_ids = if delta_state.is_full_state {
-                        Some(std::collections::HashSet::new())
-                    } else {
-                        None
-                    };
-
-                    for ship_update in delta_state.changed_ships {
-                        if let Some(ref mut ids) = seen_ids {
-                            ids.insert(ship_update.id);
-                        }
-
-                        // Update local player from server state (server-authoritative)
-                        if ship_update.id == client.player_id {
-                            // Update color if provided (spawn/respawn)
-                            if let Some(color) = ship_update.color {
-                                player_color.color = Some(color);
-                            }
-
-                            if let Some((mut transform, mut velocity, mut invincible)) = local_player.iter_mut().next() {
-                                let server_pos = Vec3::new(
-                                    ship_update.position.x,
-                                    ship_update.position.y,
-                                    0.0,
-                                );
-
-                                // Update invincibility state
-                                invincible.enabled = ship_update.is_invincible.unwrap_or(false);
-
-                                // If far from server (collision/respawn), snap immediately
-                                // Otherwise smooth correction to avoid jitter with prediction
-                                let distance = transform.translation.distance(server_pos);
-                                let blend = if distance > 100.0 { 1.0 } else { 0.3 };
-
-                                transform.translation = transform.translation.lerp(server_pos, blend);
-
-                                // For rotation: only reconcile if there's a big discrepancy (collision/respawn)
-                                // Otherwise trust client-side prediction to avoid fighting with local input
-                                if distance > 100.0 {
-                                    let server_rot = Quat::from_rotation_z(ship_update.rotation);
-                                    transform.rotation = server_rot;
-                                }
-
-                                // Snap velocity so client prediction stays accurate
-                                velocity.0.x = ship_update.velocity.x;
-                                velocity.0.y = ship_update.velocity.y;
-                            }
-                            continue;
-                        }
-
-                        // Find or create the ship entity for other players
-                        let mut found = false;
-                        for (_entity, networked, mut transform, mut velocity, mut invincible) in existing_ships.iter_mut() {
-                            if networked.id == ship_update.id {
-                                // Update invincibility state
-                                invincible.enabled = ship_update.is_invincible.unwrap_or(false);
-
-                                // Interpolate other players for smooth network updates
-                                let blend = 0.3;
-                                transform.translation.x += (ship_update.position.x - transform.translation.x) * blend;
-                                transform.translation.y += (ship_update.position.y - transform.translation.y) * blend;
-
-                                let target_quat = Quat::from_rotation_z(ship_update.rotation);
-                                transform.rotation = transform.rotation.slerp(target_quat, blend);
-
-                                // Update velocity for thruster visuals
-                                velocity.0.x = ship_update.velocity.x;
-                                velocity.0.y = ship_update.velocity.y;
-
-                                found = true;
-                                break;
-                            }
-                        }
-
-                        if !found {
-                            // Need color to spawn - skip if not provided
-                            if let Some(color) = ship_update.color {
-                                domain::spawn_networked_ship(
-                                    &mut commands,
-                                    &mut meshes,
-                                    &mut materials,
-                                    ship_update.id,
-                                    Vec3::new(ship_update.position.x, ship_update.position.y, 0.0),
-                                    color,
-                                );
-                            }
-                        }
-                    }
-
-                    // Remove ships that were explicitly removed
-                    for removed_id in delta_state.removed_ship_ids {
-                        for (entity, networked, _, _, _) in existing_ships.iter() {
-                            if networked.id == removed_id {
-                                commands.entity(entity).despawn();
-                                break;
-                            }
-                        }
-                    }
-
-                    // If full state delta, remove ships not in update
-                    if let Some(seen_ids) = seen_ids {
-                        for (entity, networked, _, _, _) in existing_ships.iter() {
-                            if !seen_ids.contains(&networked.id) {
-                                commands.entity(entity).despawn();
-                            }
-                        }
-                    }
+            received.write(ServerMessageReceived(server_msg));
+        }
+    }
+}
+
+/// Handshake, clock, chat and match messages (everything but game state)
+pub fn handle_session_messages(
+    mut received: MessageReader<ServerMessageReceived>,
+    mut client: ResMut<NetworkClient>,
+    mut match_status: ResMut<MatchStatus>,
+    mut chat: ResMut<ChatState>,
+    mut network_clock: ResMut<NetworkClock>,
+    time: Res<Time>,
+) {
+    for ServerMessageReceived(server_msg) in received.read() {
+        match server_msg {
+            ServerMessage::Welcome { assigned_id, name } => {
+                info!("Received player ID: {} (playing as '{}')", assigned_id, name);
+                client.player_id = *assigned_id;
+                client.player_name = name.clone();
+            }
+            ServerMessage::Pong { client_time, server_time, tick } => {
+                network_clock.queue_pong(*client_time, *server_time, *tick);
+            }
+            ServerMessage::Ping { server_time } => {
+                network_clock.queue_ping(*server_time);
+            }
+            ServerMessage::Chat { from, name, text } => {
+                chat.receive(*from, name.clone(), text.clone(), time.elapsed_secs());
+            }
+            ServerMessage::IncompatibleClient { protocol_version, constants_hash } => {
+                // The server closes the connection; only a reload can fix this
+                error!(
+                    "Incompatible with server (protocol {} vs {}, constants {:016x} vs {:016x})",
+                    PROTOCOL_VERSION, protocol_version, CONSTANTS_HASH, constants_hash
+                );
+                match_status.state = MatchState::Incompatible;
+            }
+            ServerMessage::Error { kind, detail } => {
+                // The server dropped one of our messages
+                warn!("Server rejected a message ({:?}): {}", kind, detail);
+            }
+            ServerMessage::Idle { action, timeout_secs } => {
+                // Server parked or kicked us after no input for timeout_secs
+                warn!("Marked idle after {}s without input: {:?}", timeout_secs, action);
+            }
+            ServerMessage::ShipDestroyed { ship_id, source } => {
+                // The ship respawns right away; the next update carries its new position
+                info!("Ship {} destroyed by {:?}", ship_id, source);
+            }
+            ServerMessage::MatchWaiting { players, min_players } => {
+                match_status.state = MatchState::Lobby;
+                match_status.players = *players;
+                match_status.min_players = *min_players;
+            }
+            ServerMessage::MatchCountdown { secs } => {
+                match_status.state = MatchState::Countdown;
+                match_status.countdown_secs = *secs;
+            }
+            ServerMessage::MatchStarted { mode, remaining_secs } => {
+                // Every ship respawns; colors follow in the next update
+                info!("Round started ({:?}, {}s left)", mode, remaining_secs);
+                match_status.state = MatchState::Playing;
+            }
+            ServerMessage::RoundEnded(results) => {
+                info!("Round over ({:?}): {:?}", results.mode, results.winner);
+                match_status.state = MatchState::Results;
+                match_status.results = Some(results.clone());
+            }
+            ServerMessage::DeltaState(_) | ServerMessage::GameState(_) => {} // apply_snapshots
+        }
+    }
+}
+
+/// Apply delta and full state updates to the mirrored entities
+pub fn apply_snapshots(
+    mut received: MessageReader<ServerMessageReceived>,
+    client: Res<NetworkClient>,
+    mut world: NetworkedWorld,
+    mut local: LocalShipState,
+    mut vitals: ResMut<ShipVitals>,
+    mut diagnostics: ResMut<NetDiagnostics>,
+) {
+    for ServerMessageReceived(server_msg) in received.read() {
+        match server_msg {
+            ServerMessage::DeltaState(delta_state) => {
+                apply_delta(delta_state, client.player_id, &mut world, &mut local, &mut vitals, &mut diagnostics);
+            }
+            ServerMessage::GameState(game_state) => {
+                apply_full_state(game_state, client.player_id, &mut world, &mut local, &mut vitals, &mut diagnostics);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Changed ships, asteroids and pickups since the previous tick
+fn apply_delta(
+    delta_state: &DeltaState,
+    player_id: u32,
+    world: &mut NetworkedWorld,
+    local: &mut LocalShipState,
+    vitals: &mut ShipVitals,
+    diagnostics: &mut NetDiagnostics,
+) {
+    diagnostics.record_tick(delta_state.tick);
+    // Delta update: only changed ships (90% bandwidth reduction)
+    // Track removed ships if this is a full state delta
+    let mut seen_ids = if delta_state.is_full_state {
+        Some(HashSet::new())
+    } else {
+        None
+    };
+
+    for ship_update in &delta_state.changed_ships {
+        if let Some(ref mut ids) = seen_ids {
+            ids.insert(ship_update.id);
+        }
+        vitals.0.insert(ship_update.id, Vitals {
+            health: ship_update.health,
+            shield: ship_update.shield,
+        });
+
+        // Update local player from server state (server-authoritative)
+        if ship_update.id == player_id {
+            // Update color if provided (spawn/respawn)
+            if let Some(color) = ship_update.color {
+                local.color.color = Some(color);
+            }
+            local.modifiers.0 = ship_update.modifiers.clone();
+
+            if let Some((mut transform, mut velocity, mut invincible)) = local.ship.iter_mut().next() {
+                let server_pos = Vec3::new(
+                    ship_update.position.x,
+                    ship_update.position.y,
+                    0.0,
+                );
+
+                // Update invincibility state
+                invincible.enabled = ship_update.is_invincible.unwrap_or(false);
+
+                // If far from server (collision/respawn), snap immediately
+                // Otherwise smooth correction to avoid jitter with prediction
+                // Measured the short way so crossing the wrap seam isn't a 4000px jump
+                let distance = wrapped_distance(transform.translation, server_pos);
+                diagnostics.record_correction(distance);
+                let blend = if distance > 100.0 { 1.0 } else { 0.3 };
+
+                transform.translation = wrapped_lerp(transform.translation, server_pos, blend);
+
+                // For rotation: only reconcile if there's a big discrepancy (collision/respawn)
+                // Otherwise trust client-side prediction to avoid fighting with local input
+                if distance > 100.0 {
+                    let server_rot = Quat::from_rotation_z(ship_update.rotation);
+                    transform.rotation = server_rot;
                 }
-                ServerMessage::GameState(game_state) => {
-                    // Track which ships we've seen
-                    let mut seen_ids = std::collections::HashSet::new();
-
-                    for server_ship in game_state.ships {
-                        seen_ids.insert(server_ship.id);
-
-                        // Update local player from server state (server-authoritative)
-                        if server_ship.id == client.player_id {
-                            player_color.color = Some(server_ship.color);
-
-                            if let Some((mut transform, mut velocity, mut invincible)) = local_player.iter_mut().next() {
-                                let server_pos = Vec3::new(
-                                    server_ship.position.x,
-                                    server_ship.position.y,
-                                    0.0,
-                                );
-
-                                // Default to not invincible for GameState (no field in Ship)
-                                invincible.enabled = false;
-
-                                // If far from server (collision/respawn), snap immediately
-                                // Otherwise smooth correction to avoid jitter with prediction
-                                let distance = transform.translation.distance(server_pos);
-                                let blend = if distance > 100.0 { 1.0 } else { 0.3 };
-
-                                transform.translation = transform.translation.lerp(server_pos, blend);
-
-                                // For rotation: only reconcile if there's a big discrepancy (collision/respawn)
-                                // Otherwise trust client-side prediction to avoid fighting with local input
-                                if distance > 100.0 {
-                                    let server_rot = Quat::from_rotation_z(server_ship.rotation);
-                                    transform.rotation = server_rot;
-                                }
-
-                                // Snap velocity so client prediction stays accurate
-                                velocity.0.x = server_ship.velocity.x;
-                                velocity.0.y = server_ship.velocity.y;
-                            }
-                            continue;
-                        }
-
-                        // Find or create the ship entity for other players
-                        let mut found = false;
-                        for (_entity, networked, mut transform, mut velocity, mut invincible) in existing_ships.iter_mut() {
-                            if networked.id == server_ship.id {
-                                // Default to not invincible for GameState
-                                invincible.enabled = false;
-
-                                // Interpolate other players for smooth network updates
-                                let blend = 0.3; // Slightly lower for remote players to reduce jitter
-                                transform.translation.x += (server_ship.position.x - transform.translation.x) * blend;
-                                transform.translation.y += (server_ship.position.y - transform.translation.y) * blend;
-
-                                let target_quat = Quat::from_rotation_z(server_ship.rotation);
-                                transform.rotation = transform.rotation.slerp(target_quat, blend);
-
-                                // Update velocity for thruster visuals
-                                velocity.0.x = server_ship.velocity.x;
-                                velocity.0.y = server_ship.velocity.y;
-
-                                found = true;
-                                break;
-                            }
-                        }
-
-                        if !found {
-                            // Spawn new networked player ship
-                            domain::spawn_networked_ship(
-                                &mut commands,
-                                &mut meshes,
-                                &mut materials,
-                                server_ship.id,
-                                Vec3::new(server_ship.position.x, server_ship.position.y, 0.0),
-                                server_ship.color,
-                            );
-                        }
-                    }
-
-                    // Remove ships that no longer exist (despawn will handle children)
-                    for (entity, networked, _, _, _) in existing_ships.iter() {
-                        if !seen_ids.contains(&networked.id) {
-                            commands.entity(entity).despawn();
-                        }
-                    }
-
-                    // Update asteroids from server
-                    let mut seen_asteroid_ids = std::collections::HashSet::new();
-                    for server_asteroid in game_state.asteroids {
-                        seen_asteroid_ids.insert(server_asteroid.id);
-
-                        // Find or create asteroid entity
-                        let mut found = false;
-                        for (_entity, networked, mut transform, mut velocity) in existing_asteroids.iter_mut() {
-                            if networked.id == server_asteroid.id {
-                                // Update position with server authority (periodic corrections)
-                                transform.translation.x = server_asteroid.position.x;
-                                transform.translation.y = server_asteroid.position.y;
-
-                                // Update velocity for client-side prediction
-                                velocity.0.x = server_asteroid.velocity.x;
-                                velocity.0.y = server_asteroid.velocity.y;
-
-                                found = true;
-                                break;
-                            }
-                        }
-
-                        if !found {
-                            // Spawn new networked asteroid
-                            domain::spawn_networked_asteroid(
-                                &mut commands,
-                                &mut meshes,
-                                &mut materials,
-                                server_asteroid.id,
-                                Vec3::new(server_asteroid.position.x, server_asteroid.position.y, 0.0),
-                                server_asteroid.radius,
-                            );
-                        }
-                    }
-
-                    // Remove asteroids that no longer exist
-                    for (entity, networked, _, _) in existing_asteroids.iter() {
-                        if !seen_asteroid_ids.contains(&networked.id) {
-                            commands.entity(entity).despawn();
-                        }
-                    }
+
+                // Snap velocity so client prediction stays accurate
+                velocity.0.x = ship_update.velocity.x;
+                velocity.0.y = ship_update.velocity.y;
+            }
+            continue;
+        }
+
+        // Find or create the ship entity for other players
+        let mut found = false;
+        for (_entity, networked, mut transform, mut velocity, mut invincible) in world.ships.iter_mut() {
+            if networked.id == ship_update.id {
+                // Update invincibility state
+                invincible.enabled = ship_update.is_invincible.unwrap_or(false);
+
+                // Interpolate other players for smooth network updates
+                let blend = 0.3;
+                let target = Vec3::new(ship_update.position.x, ship_update.position.y, 0.0);
+                transform.translation = wrapped_lerp(transform.translation, target, blend);
+
+                let target_quat = Quat::from_rotation_z(ship_update.rotation);
+                transform.rotation = transform.rotation.slerp(target_quat, blend);
+
+                // Update velocity for thruster visuals
+                velocity.0.x = ship_update.velocity.x;
+                velocity.0.y = ship_update.velocity.y;
+
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            // Need color to spawn - skip if not provided
+            if let Some(color) = ship_update.color {
+                domain::spawn_networked_ship(
+                    &mut world.commands,
+                    &mut world.meshes,
+                    &mut world.materials,
+                    ship_update.id,
+                    Vec3::new(ship_update.position.x, ship_update.position.y, 0.0),
+                    color,
+                    ship_update.name.as_deref().unwrap_or_default(),
+                );
+            }
+        }
+    }
+
+    // Remove ships that were explicitly removed
+    for &removed_id in &delta_state.removed_ship_ids {
+        vitals.0.remove(&removed_id);
+        for (entity, networked, _, _, _) in world.ships.iter() {
+            if networked.id == removed_id {
+                world.commands.entity(entity).despawn();
+                break;
+            }
+        }
+    }
+
+    // Asteroids knocked off course by a bounce, or added to top up the field
+    for server_asteroid in &delta_state.changed_asteroids {
+        let mut found = false;
+        for (_entity, networked, mut transform, mut velocity, mut spin) in world.asteroids.iter_mut() {
+            if networked.id == server_asteroid.id {
+                transform.translation.x = server_asteroid.position.x;
+                transform.translation.y = server_asteroid.position.y;
+                velocity.0.x = server_asteroid.velocity.x;
+                velocity.0.y = server_asteroid.velocity.y;
+                transform.rotation = Quat::from_rotation_z(server_asteroid.rotation);
+                spin.0 = server_asteroid.angular_velocity;
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            domain::spawn_networked_asteroid(
+                &mut world.commands,
+                &mut world.meshes,
+                &mut world.materials,
+                server_asteroid,
+            );
+        }
+    }
+
+    // Pickups appearing and being collected
+    for pickup in &delta_state.added_pickups {
+        if !world.pickups.iter().any(|(_, networked)| networked.id == pickup.id) {
+            domain::spawn_networked_pickup(&mut world.commands, &mut world.meshes, &mut world.materials, pickup);
+        }
+    }
+    for (entity, networked) in world.pickups.iter() {
+        if delta_state.removed_pickup_ids.contains(&networked.id) {
+            world.commands.entity(entity).despawn();
+        }
+    }
+
+    // If full state delta, remove ships not in update
+    if let Some(seen_ids) = seen_ids {
+        for (entity, networked, _, _, _) in world.ships.iter() {
+            if !seen_ids.contains(&networked.id) {
+                world.commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+/// Whole world state: anything the server didn't list is despawned
+fn apply_full_state(
+    game_state: &GameState,
+    player_id: u32,
+    world: &mut NetworkedWorld,
+    local: &mut LocalShipState,
+    vitals: &mut ShipVitals,
+    diagnostics: &mut NetDiagnostics,
+) {
+    diagnostics.record_tick(game_state.tick);
+    // Track which ships we've seen
+    let mut seen_ids = HashSet::new();
+
+    for server_ship in &game_state.ships {
+        seen_ids.insert(server_ship.id);
+        vitals.0.insert(server_ship.id, Vitals {
+            health: server_ship.health,
+            shield: server_ship.shield,
+        });
+
+        // Update local player from server state (server-authoritative)
+        if server_ship.id == player_id {
+            local.color.color = Some(server_ship.color);
+            local.modifiers.0 = server_ship.modifiers.clone();
+
+            if let Some((mut transform, mut velocity, mut invincible)) = local.ship.iter_mut().next() {
+                let server_pos = Vec3::new(
+                    server_ship.position.x,
+                    server_ship.position.y,
+                    0.0,
+                );
+
+                // Respawn invincibility isn't in Ship, only the pickup effect
+                invincible.enabled = server_ship.has_modifier(PickupKind::Invincibility);
+
+                // If far from server (collision/respawn), snap immediately
+                // Otherwise smooth correction to avoid jitter with prediction
+                // Measured the short way so crossing the wrap seam isn't a 4000px jump
+                let distance = wrapped_distance(transform.translation, server_pos);
+                diagnostics.record_correction(distance);
+                let blend = if distance > 100.0 { 1.0 } else { 0.3 };
+
+                transform.translation = wrapped_lerp(transform.translation, server_pos, blend);
+
+                // For rotation: only reconcile if there's a big discrepancy (collision/respawn)
+                // Otherwise trust client-side prediction to avoid fighting with local input
+                if distance > 100.0 {
+                    let server_rot = Quat::from_rotation_z(server_ship.rotation);
+                    transform.rotation = server_rot;
                 }
+
+                // Snap velocity so client prediction stays accurate
+                velocity.0.x = server_ship.velocity.x;
+                velocity.0.y = server_ship.velocity.y;
+            }
+            continue;
+        }
+
+        // Find or create the ship entity for other players
+        let mut found = false;
+        for (_entity, networked, mut transform, mut velocity, mut invincible) in world.ships.iter_mut() {
+            if networked.id == server_ship.id {
+                // Default to not invincible for GameState
+                invincible.enabled = false;
+
+                // Interpolate other players for smooth network updates
+                let blend = 0.3; // Slightly lower for remote players to reduce jitter
+                let target = Vec3::new(server_ship.position.x, server_ship.position.y, 0.0);
+                transform.translation = wrapped_lerp(transform.translation, target, blend);
+
+                let target_quat = Quat::from_rotation_z(server_ship.rotation);
+                transform.rotation = transform.rotation.slerp(target_quat, blend);
+
+                // Update velocity for thruster visuals
+                velocity.0.x = server_ship.velocity.x;
+                velocity.0.y = server_ship.velocity.y;
+
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            // Spawn new networked player ship
+            domain::spawn_networked_ship(
+                &mut world.commands,
+                &mut world.meshes,
+                &mut world.materials,
+                server_ship.id,
+                Vec3::new(server_ship.position.x, server_ship.position.y, 0.0),
+                server_ship.color,
+                &server_ship.name,
+            );
+        }
+    }
+
+    // Remove ships that no longer exist (despawn will handle children)
+    for (entity, networked, _, _, _) in world.ships.iter() {
+        if !seen_ids.contains(&networked.id) {
+            world.commands.entity(entity).despawn();
+        }
+    }
+
+    // Update asteroids from server
+    let mut seen_asteroid_ids = HashSet::new();
+    for server_asteroid in &game_state.asteroids {
+        seen_asteroid_ids.insert(server_asteroid.id);
+
+        // Find or create asteroid entity
+        let mut found = false;
+        for (_entity, networked, mut transform, mut velocity, mut spin) in world.asteroids.iter_mut() {
+            if networked.id == server_asteroid.id {
+                // Update position with server authority (periodic corrections)
+                transform.translation.x = server_asteroid.position.x;
+                transform.translation.y = server_asteroid.position.y;
+
+                // Update velocity and spin for client-side prediction
+                velocity.0.x = server_asteroid.velocity.x;
+                velocity.0.y = server_asteroid.velocity.y;
+                transform.rotation = Quat::from_rotation_z(server_asteroid.rotation);
+                spin.0 = server_asteroid.angular_velocity;
+
+                found = true;
+                break;
             }
         }
+
+        if !found {
+            // Spawn new networked asteroid
+            domain::spawn_networked_asteroid(
+                &mut world.commands,
+                &mut world.meshes,
+                &mut world.materials,
+                server_asteroid,
+            );
+        }
+    }
+
+    // Remove asteroids that no longer exist
+    for (entity, networked, _, _, _) in world.asteroids.iter() {
+        if !seen_asteroid_ids.contains(&networked.id) {
+            world.commands.entity(entity).despawn();
+        }
+    }
+
+    // Pickups: spawn new ones, drop collected ones
+    for pickup in &game_state.pickups {
+        if !world.pickups.iter().any(|(_, networked)| networked.id == pickup.id) {
+            domain::spawn_networked_pickup(&mut world.commands, &mut world.meshes, &mut world.materials, pickup);
+        }
+    }
+    for (entity, networked) in world.pickups.iter() {
+        if !game_state.pickups.iter().any(|pickup| pickup.id == networked.id) {
+            world.commands.entity(entity).despawn();
+        }
     }
 }
 
+/// Shortest distance between two points in the toroidal world (XY only)
+fn wrapped_distance(from: Vec3, to: Vec3) -> f32 {
+    NetVec2::new(from.x, from.y).wrapped_distance_to(NetVec2::new(to.x, to.y), WORLD_LIMIT)
+}
+
+/// Move `from` toward `to` by `blend`, taking the short way across the wrap seam
+///
+/// Keeps `from.z`. The result is wrapped back into the world like the
+/// server does, so a ship crossing the edge slides over instead of
+/// sweeping across the whole map.
+fn wrapped_lerp(from: Vec3, to: Vec3, blend: f32) -> Vec3 {
+    let start = NetVec2::new(from.x, from.y);
+    let delta = start.wrapped_delta_to(NetVec2::new(to.x, to.y), WORLD_LIMIT);
+    let mut pos = start + delta * blend;
+    wrap_position(&mut pos, WORLD_LIMIT);
+    Vec3::new(pos.x, pos.y, from.z)
+}
+
 pub fn update_local_ship_color(
     mut commands: Commands,
     mut local_ship: ResMut<LocalShipEntity>,
@@ -412,21 +666,3 @@ fn spawn_networked_ship(
 
     commands.entity(ship_entity).add_child(thruster_entity);
 }
-
-fn spawn_networked_asteroid(
-    commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
-    id: u32,
-    position: Vec3,
-    radius: f32,
-) {
-    use crate::components::Velocity;
-    commands.spawn((
-        Mesh2d(meshes.add(crate::entities::build_circle_mesh(radius, 32))),
-        MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
-        Transform::from_translation(position),
-        NetworkedAsteroid { id },
-        Velocity::default(),
-    ));
-}
@@ -1,13 +1,22 @@
+use battlestar_shared::{network::PROTOCOL_VERSION, ClientMessage};
 use bevy::prelude::*;
 use std::sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{ErrorEvent, MessageEvent, WebSocket};
 
+use crate::constants::CONSTANTS_HASH;
+
+use super::diagnostics::TrafficCounters;
+
 #[derive(Resource)]
 pub struct NetworkClient {
     pub player_id: u32,
+    /// Name the server accepted (from `Welcome`)
+    pub player_name: String,
     pub messages: Arc<Mutex<Vec<String>>>,
+    /// Messages and bytes in both directions (diagnostics overlay)
+    pub traffic: Arc<TrafficCounters>,
     pub connected: bool,
     ws_url: String,
     connected_flag: Arc<AtomicBool>,
@@ -50,7 +59,9 @@ impl Default for NetworkClient {
 
         Self {
             player_id,
+            player_name: String::new(),
             messages: Arc::new(Mutex::new(Vec::new())),
+            traffic: Arc::new(TrafficCounters::default()),
             connected: false,
             ws_url,
             connected_flag: Arc::new(AtomicBool::new(false)),
@@ -60,7 +71,19 @@ impl Default for NetworkClient {
 
 #[derive(Resource)]
 pub struct WebSocketHandle {
-    pub ws: WebSocket,
+    ws: WebSocket,
+    traffic: Arc<TrafficCounters>,
+}
+
+impl WebSocketHandle {
+    /// Send a message to the server (dropped if the socket isn't open)
+    pub fn send(&self, message: &ClientMessage) {
+        if let Ok(json) = serde_json::to_string(message) {
+            if self.ws.send_with_str(&json).is_ok() {
+                self.traffic.record_out(json.len());
+            }
+        }
+    }
 }
 
 // Manual Send+Sync because we're in WASM (single-threaded)
@@ -83,11 +106,13 @@ pub fn setup_network(mut commands: Commands) {
     match WebSocket::new(&ws_url) {
         Ok(ws) => {
             let messages = client.messages.clone();
+            let traffic = client.traffic.clone();
 
             // Setup onmessage callback
             let onmessage_callback = Closure::<dyn FnMut(_)>::new(move |e: MessageEvent| {
                 if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
                     let msg = String::from(txt);
+                    traffic.record_in(msg.len());
                     if let Ok(mut msgs) = messages.lock() {
                         msgs.push(msg);
                     }
@@ -105,10 +130,23 @@ pub fn setup_network(mut commands: Commands) {
             ws.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
             onerror_callback.forget();
 
-            // Setup onopen callback
+            // Setup onopen callback: say hello before anything else
             let connected_flag = client.connected_flag.clone();
+            let ws_for_hello = ws.clone();
+            let traffic = client.traffic.clone();
             let onopen_callback = Closure::<dyn FnMut()>::new(move || {
                 info!("WebSocket connected!");
+                let hello = ClientMessage::Hello {
+                    name: requested_name(),
+                    client_version: env!("CARGO_PKG_VERSION").to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    constants_hash: CONSTANTS_HASH,
+                };
+                if let Ok(json) = serde_json::to_string(&hello) {
+                    if ws_for_hello.send_with_str(&json).is_ok() {
+                        traffic.record_out(json.len());
+                    }
+                }
                 connected_flag.store(true, Ordering::Relaxed);
             });
             ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
@@ -130,7 +168,10 @@ pub fn setup_network(mut commands: Commands) {
             ws.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
             onclose_callback.forget();
 
-            commands.insert_resource(WebSocketHandle { ws });
+            commands.insert_resource(WebSocketHandle {
+                ws,
+                traffic: client.traffic.clone(),
+            });
         }
         Err(e) => {
             error!("Failed to create WebSocket: {:?}", e);
@@ -140,6 +181,21 @@ pub fn setup_network(mut commands: Commands) {
     commands.insert_resource(client);
 }
 
+/// Name from the page URL (`?name=Starbuck`); empty lets the server pick one
+fn requested_name() -> String {
+    let search = web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .unwrap_or_default();
+
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("name="))
+        .and_then(|value| js_sys::decode_uri_component(&value.replace('+', " ")).ok())
+        .map(String::from)
+        .unwrap_or_default()
+}
+
 /// Polls the WebSocket connected flag each frame and updates the resource
 pub fn poll_connection_state(mut client: ResMut<NetworkClient>) {
     client.connected = client.connected_flag.load(Ordering::Relaxed);
@@ -1,5 +1,6 @@
 use crate::components::Player;
-use crate::constants::CAMERA_DECAY_RATE;
+use crate::constants::{CAMERA_DECAY_RATE, WORLD_LIMIT};
+use battlestar_shared::Vec2 as NetVec2;
 use bevy::{prelude::*, color::palettes::css::*};
 
 pub fn setup_camera(mut commands: Commands) {
@@ -17,9 +18,27 @@ pub fn update_camera(
     player: Single<&Transform, (With<Player>, Without<Camera2d>)>,
     time: Res<Time>,
 ) {
-    let Vec3 { x, y, .. } = player.translation;
-    let direction = Vec3::new(x, y, camera.translation.z);
+    // Follow the player the short way round: when the ship wraps, the
+    // camera keeps sliding past the edge instead of flying across the map
+    let camera_pos = NetVec2::new(camera.translation.x, camera.translation.y);
+    let player_pos = NetVec2::new(player.translation.x, player.translation.y);
+    let delta = camera_pos.wrapped_delta_to(player_pos, WORLD_LIMIT);
+    let direction = Vec3::new(
+        camera.translation.x + delta.x,
+        camera.translation.y + delta.y,
+        camera.translation.z,
+    );
     camera
         .translation
         .smooth_nudge(&direction, CAMERA_DECAY_RATE, time.delta_secs());
+
+    // Once past the edge, jump to the matching spot on the other side
+    // (the player is already there, so the view stays centered on it)
+    let size = WORLD_LIMIT * 2.0;
+    if camera.translation.x.abs() > WORLD_LIMIT {
+        camera.translation.x -= size * camera.translation.x.signum();
+    }
+    if camera.translation.y.abs() > WORLD_LIMIT {
+        camera.translation.y -= size * camera.translation.y.signum();
+    }
 }
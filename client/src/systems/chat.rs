@@ -0,0 +1,213 @@
+use battlestar_shared::ClientMessage;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::net::transport::{NetworkClient, WebSocketHandle};
+
+/// Lines shown in the chat box
+const CHAT_HISTORY: usize = 8;
+
+/// Seconds a line stays fully visible
+const LINE_VISIBLE_SECS: f32 = 8.0;
+
+/// Seconds a line takes to fade out afterwards
+const LINE_FADE_SECS: f32 = 2.0;
+
+/// Longest draft (the server cuts longer messages anyway)
+const MAX_DRAFT_LENGTH: usize = 200;
+
+/// One received chat line (or a local notice when `name` is empty)
+pub struct ChatLine {
+    pub name: String,
+    pub text: String,
+    /// `Time::elapsed_secs` when it arrived
+    pub received_at: f32,
+}
+
+/// Chat history, the message being typed and the local mute list
+#[derive(Resource, Default)]
+pub struct ChatState {
+    /// Chat box open: the keyboard types instead of flying the ship
+    pub typing: bool,
+    pub draft: String,
+    pub lines: Vec<ChatLine>,
+    /// Player IDs whose messages are hidden (this client only)
+    pub muted: HashSet<u32>,
+    /// Latest sender under each lowercase name, to resolve `/mute name`
+    senders: HashMap<String, u32>,
+}
+
+impl ChatState {
+    /// Add a received line unless its sender is muted
+    pub fn receive(&mut self, from: u32, name: String, text: String, now: f32) {
+        self.senders.insert(name.to_lowercase(), from);
+        if self.muted.contains(&from) {
+            return;
+        }
+        self.push(name, text, now);
+    }
+
+    fn push(&mut self, name: String, text: String, now: f32) {
+        self.lines.push(ChatLine { name, text, received_at: now });
+        if self.lines.len() > CHAT_HISTORY {
+            self.lines.remove(0);
+        }
+    }
+}
+
+/// One history row of the chat box (0 is the oldest)
+#[derive(Component)]
+pub struct ChatLineText(usize);
+
+/// Row showing the draft while typing
+#[derive(Component)]
+pub struct ChatDraftText;
+
+/// Spawn the chat box above the instructions
+pub fn setup_chat(mut commands: Commands) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: px(56.0),
+            left: px(12.0),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        })
+        .with_children(|parent| {
+            for index in 0..CHAT_HISTORY {
+                parent.spawn((
+                    Text::default(),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    ChatLineText(index),
+                ));
+            }
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.9, 0.4)),
+                ChatDraftText,
+            ));
+        });
+}
+
+/// Enter opens the chat box and sends, Escape cancels
+///
+/// `/mute name` and `/unmute name` are handled here and never sent.
+pub fn handle_chat_keys(
+    mut keys: MessageReader<KeyboardInput>,
+    mut chat: ResMut<ChatState>,
+    client: Res<NetworkClient>,
+    ws_handle: Option<Res<WebSocketHandle>>,
+    time: Res<Time>,
+) {
+    for key in keys.read() {
+        if !key.state.is_pressed() {
+            continue;
+        }
+        match (&key.logical_key, chat.typing) {
+            (Key::Enter, false) => chat.typing = true,
+            (Key::Enter, true) => {
+                chat.typing = false;
+                let draft = std::mem::take(&mut chat.draft);
+                let draft = draft.trim();
+                if draft.is_empty() {
+                    continue;
+                }
+                if let Some(notice) = mute_command(&mut chat, draft) {
+                    chat.push(String::new(), notice, time.elapsed_secs());
+                } else if client.connected {
+                    if let Some(ws_handle) = &ws_handle {
+                        ws_handle.send(&ClientMessage::Chat { text: draft.to_string() });
+                    }
+                }
+            }
+            (Key::Escape, true) => {
+                chat.typing = false;
+                chat.draft.clear();
+            }
+            (Key::Backspace, true) => {
+                chat.draft.pop();
+            }
+            (_, true) => {
+                if let Some(text) = &key.text {
+                    for c in text.chars().filter(|c| !c.is_control()) {
+                        if chat.draft.chars().count() < MAX_DRAFT_LENGTH {
+                            chat.draft.push(c);
+                        }
+                    }
+                }
+            }
+            (_, false) => {}
+        }
+    }
+}
+
+/// Apply `/mute name` or `/unmute name`, returning the notice to show
+///
+/// The name is resolved to the player who last chatted under it, so a
+/// player can't dodge a mute by renaming or be muted through a namesake.
+fn mute_command(chat: &mut ChatState, draft: &str) -> Option<String> {
+    let (command, name) = draft.split_once(' ')?;
+    let name = name.trim();
+    if command != "/mute" && command != "/unmute" {
+        return None;
+    }
+    let Some(&player_id) = chat.senders.get(&name.to_lowercase()) else {
+        return Some(format!("No one named {} has chatted", name));
+    };
+    if command == "/mute" {
+        chat.muted.insert(player_id);
+        Some(format!("{} muted", name))
+    } else {
+        chat.muted.remove(&player_id);
+        Some(format!("{} unmuted", name))
+    }
+}
+
+/// Show the latest lines, fading them out with age (all visible while typing)
+pub fn update_chat_box(
+    chat: Res<ChatState>,
+    time: Res<Time>,
+    mut lines: Query<(&ChatLineText, &mut Text, &mut TextColor), Without<ChatDraftText>>,
+    mut draft: Query<&mut Text, With<ChatDraftText>>,
+) {
+    let now = time.elapsed_secs();
+    // Rows are bottom-aligned: the newest line sits right above the draft
+    let first_row = CHAT_HISTORY - chat.lines.len();
+
+    for (row, mut text, mut color) in lines.iter_mut() {
+        let Some(line) = row.0.checked_sub(first_row).and_then(|i| chat.lines.get(i)) else {
+            text.0.clear();
+            continue;
+        };
+
+        text.0 = if line.name.is_empty() {
+            line.text.clone()
+        } else {
+            format!("{}: {}", line.name, line.text)
+        };
+        let age = now - line.received_at;
+        let alpha = if chat.typing {
+            1.0
+        } else {
+            (1.0 - (age - LINE_VISIBLE_SECS) / LINE_FADE_SECS).clamp(0.0, 1.0)
+        };
+        color.0.set_alpha(alpha);
+    }
+
+    for mut text in draft.iter_mut() {
+        text.0 = if chat.typing {
+            format!("> {}_", chat.draft)
+        } else {
+            String::new()
+        };
+    }
+}
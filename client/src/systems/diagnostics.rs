@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+
+use crate::net::diagnostics::CORRECTION_HISTORY;
+use crate::net::{NetDiagnostics, NetworkClock};
+
+/// Correction drawn as a full-height bar (the local ship snaps above this)
+const GRAPH_MAX_DISTANCE: f32 = 100.0;
+
+/// Height of the correction graph
+const GRAPH_HEIGHT: f32 = 60.0;
+
+/// Network diagnostics panel, toggled with F3
+#[derive(Component)]
+pub struct DiagnosticsOverlay;
+
+#[derive(Component)]
+pub struct DiagnosticsText;
+
+/// One bar of the correction graph (0 is the oldest update)
+#[derive(Component)]
+pub struct CorrectionBar(usize);
+
+/// Spawn the overlay, hidden until F3 is pressed
+pub fn setup_diagnostics(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: px(12.0),
+                left: px(12.0),
+                padding: UiRect::all(px(8.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            Visibility::Hidden,
+            DiagnosticsOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                DiagnosticsText,
+            ));
+            parent.spawn((
+                Text::new("Prediction corrections"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(Node {
+                    height: px(GRAPH_HEIGHT),
+                    align_items: AlignItems::FlexEnd,
+                    column_gap: px(1.0),
+                    ..default()
+                })
+                .with_children(|graph| {
+                    for index in 0..CORRECTION_HISTORY {
+                        graph.spawn((
+                            Node {
+                                width: px(3.0),
+                                height: px(0.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.9, 0.4)),
+                            CorrectionBar(index),
+                        ));
+                    }
+                });
+        });
+}
+
+pub fn toggle_diagnostics(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlay: Query<&mut Visibility, With<DiagnosticsOverlay>>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        for mut visibility in overlay.iter_mut() {
+            visibility.toggle_visible_hidden();
+        }
+    }
+}
+
+/// Refresh the numbers and the graph while the overlay is shown
+pub fn update_diagnostics(
+    diagnostics: Res<NetDiagnostics>,
+    network_clock: Res<NetworkClock>,
+    overlay: Query<&Visibility, With<DiagnosticsOverlay>>,
+    mut texts: Query<&mut Text, With<DiagnosticsText>>,
+    mut bars: Query<(&CorrectionBar, &mut Node, &mut BackgroundColor)>,
+) {
+    if overlay.iter().all(|visibility| *visibility == Visibility::Hidden) {
+        return;
+    }
+
+    let rtt = match network_clock.rtt.rtt_ms() {
+        Some(rtt) => format!("{:.0} ms (±{:.0})", rtt, network_clock.rtt.jitter_ms()),
+        None => "--".to_string(),
+    };
+    let last_tick = diagnostics
+        .last_server_tick
+        .map(|tick| tick.to_string())
+        .unwrap_or_else(|| "--".to_string());
+    let behind = match (network_clock.server_tick, diagnostics.last_server_tick) {
+        (Some(now), Some(last)) => format!("{:.1} ticks", now - last as f64),
+        _ => "--".to_string(),
+    };
+    let last_correction = diagnostics.corrections.back().copied().unwrap_or_default();
+    let max_correction = diagnostics.corrections.iter().copied().fold(0.0, f32::max);
+    let rates = diagnostics.rates;
+
+    for mut text in texts.iter_mut() {
+        text.0 = format!(
+            "RTT: {}\nDown: {} msg/s, {:.1} KB/s\nUp: {} msg/s, {:.1} KB/s\nServer tick: {}\nBehind: {}\nCorrection: {:.1} px (max {:.1})",
+            rtt,
+            rates.packets_in,
+            rates.bytes_in as f32 / 1024.0,
+            rates.packets_out,
+            rates.bytes_out as f32 / 1024.0,
+            last_tick,
+            behind,
+            last_correction,
+            max_correction,
+        );
+    }
+
+    // Newest correction on the right
+    let first_bar = CORRECTION_HISTORY - diagnostics.corrections.len();
+    for (bar, mut node, mut color) in bars.iter_mut() {
+        let distance = bar
+            .0
+            .checked_sub(first_bar)
+            .and_then(|i| diagnostics.corrections.get(i))
+            .copied()
+            .unwrap_or_default();
+        node.height = px(distance.min(GRAPH_MAX_DISTANCE) / GRAPH_MAX_DISTANCE * GRAPH_HEIGHT);
+        color.0 = if distance > GRAPH_MAX_DISTANCE {
+            Color::srgb(0.9, 0.3, 0.3)
+        } else {
+            Color::srgb(0.3, 0.9, 0.4)
+        };
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use crate::components::{NetworkedPlayer, Player};
+use crate::constants::{MAX_HEALTH, MAX_SHIELD};
+use crate::net::{NetworkClient, ShipVitals};
+
+const BAR_WIDTH: f32 = 40.0;
+const BAR_HEIGHT: f32 = 4.0;
+
+/// Height of the hull bar above the ship center (shield bar sits just above)
+const BAR_OFFSET: f32 = 38.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BarKind {
+    Hull,
+    Shield,
+}
+
+/// Hull or shield bar drawn over a ship
+///
+/// Bars are top-level entities rather than children of the ship, so they
+/// stay horizontal while the ship rotates.
+#[derive(Component)]
+pub struct HealthBar {
+    pub owner: Entity,
+    pub kind: BarKind,
+}
+
+/// Marker for ships that already have their bars
+#[derive(Component)]
+pub struct HasHealthBars;
+
+/// Local and remote ships that don't have their bars yet
+type ShipsWithoutBars<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    (Or<(With<Player>, With<NetworkedPlayer>)>, Without<HasHealthBars>),
+>;
+
+/// Spawn hull and shield bars for every ship that doesn't have them yet
+pub fn attach_health_bars(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    ships: ShipsWithoutBars,
+) {
+    for owner in &ships {
+        for (kind, color) in [
+            (BarKind::Hull, Color::srgb(0.2, 0.85, 0.3)),
+            (BarKind::Shield, Color::srgb(0.3, 0.6, 1.0)),
+        ] {
+            commands.spawn((
+                Mesh2d(meshes.add(Rectangle::new(BAR_WIDTH, BAR_HEIGHT))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(color))),
+                Transform::default(),
+                HealthBar { owner, kind },
+            ));
+        }
+        commands.entity(owner).insert(HasHealthBars);
+    }
+}
+
+/// Keep bars above their ship, sized by the latest health/shield from the server
+pub fn update_health_bars(
+    mut commands: Commands,
+    client: Res<NetworkClient>,
+    vitals: Res<ShipVitals>,
+    ships: Query<(&Transform, Option<&NetworkedPlayer>), Without<HealthBar>>,
+    mut bars: Query<(Entity, &HealthBar, &mut Transform)>,
+) {
+    for (entity, bar, mut transform) in &mut bars {
+        let Ok((ship_transform, networked)) = ships.get(bar.owner) else {
+            // Ship despawned (disconnect, local ship recolored)
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let id = networked.map_or(client.player_id, |n| n.id);
+        let (fraction, row) = match (bar.kind, vitals.0.get(&id)) {
+            (BarKind::Hull, Some(v)) => (v.health / MAX_HEALTH, 0.0),
+            (BarKind::Shield, Some(v)) => (v.shield / MAX_SHIELD, 1.0),
+            (BarKind::Hull, None) => (1.0, 0.0),
+            (BarKind::Shield, None) => (1.0, 1.0),
+        };
+        let fraction = fraction.clamp(0.0, 1.0);
+
+        // Shrink toward the left edge
+        transform.translation = ship_transform.translation
+            + Vec3::new(
+                -(1.0 - fraction) * BAR_WIDTH * 0.5,
+                BAR_OFFSET + row * (BAR_HEIGHT + 2.0),
+                1.0,
+            );
+        transform.scale.x = fraction;
+    }
+}
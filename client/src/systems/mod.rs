@@ -3,3 +3,10 @@ pub mod movement;
 pub mod setup;
 pub mod joystick;
 pub mod invincibility;
+pub mod health_bar;
+pub mod pickups;
+pub mod screens;
+pub mod name_labels;
+pub mod chat;
+pub mod ping;
+pub mod diagnostics;
@@ -1,5 +1,8 @@
-use battlestar_shared::{physics, Input, PhysicsConstants, Vec2};
-use crate::components::{Player, Thruster, ThrusterOwner, Velocity, NetworkedAsteroid};
+use battlestar_shared::{
+    entities::{asteroid::asteroid_outline, pickup::effective_constants, PickupConstants},
+    physics, Input, PhysicsConstants, Vec2,
+};
+use crate::components::{AngularVelocity, Invincible, NetworkedAsteroid, NetworkedPlayer, Player, Thruster, ThrusterOwner, Velocity};
 use crate::constants::*;
 use bevy::prelude::*;
 
@@ -9,6 +12,7 @@ use bevy::prelude::*;
 pub fn apply_local_physics(
     mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
     player_input: Res<crate::net::PlayerInput>,
+    modifiers: Res<crate::net::LocalModifiers>,
     time: Res<Time>,
 ) {
     let Some((mut transform, mut velocity)) = player_query.iter_mut().next() else {
@@ -17,8 +21,9 @@ pub fn apply_local_physics(
 
     let dt = time.delta().as_secs_f32();
 
-    // Create physics constants from game constants
-    let constants = PhysicsConstants::from_game_constants(
+    // Create physics constants from game constants, with the same pickup
+    // effects as the server so a speed boost doesn't rubber-band
+    let base = PhysicsConstants::from_game_constants(
         THRUST_ACCEL,
         ROTATION_SPEED,
         MAX_SPEED,
@@ -26,6 +31,8 @@ pub fn apply_local_physics(
         WORLD_LIMIT,
         SHIP_RADIUS,
     );
+    let pickups = PickupConstants::from_game_constants(PICKUP_RADIUS, PICKUP_DURATION, SPEED_BOOST, RAPID_FIRE);
+    let constants = effective_constants(&base, &modifiers.0, &pickups);
 
     // Create shared input structure
     let input = Input::new(player_input.thrust, player_input.rotate);
@@ -67,6 +74,77 @@ pub fn apply_local_physics(
     velocity.0.y = vel.y;
 }
 
+/// Other players' ships (everything the local ship can bounce off besides asteroids)
+type OtherShips<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static Velocity, &'static Invincible),
+    (With<NetworkedPlayer>, Without<Player>),
+>;
+
+// Client-side prediction of collision bounces for the local player
+// Mirrors the server's response (same shared code) so bouncing off an asteroid
+// or another ship feels immediate; only the local ship is moved, everything
+// else stays server-authoritative
+pub fn predict_local_collisions(
+    mut player_query: Query<(&mut Transform, &mut Velocity, &Invincible), With<Player>>,
+    asteroids: Query<(&Transform, &Velocity, &NetworkedAsteroid), Without<Player>>,
+    ships: OtherShips,
+) {
+    if !COLLISION_RESPONSE {
+        return;
+    }
+
+    let Some((mut transform, mut velocity, invincible)) = player_query.iter_mut().next() else {
+        return;
+    };
+    if invincible.enabled {
+        return; // Invincible ships pass through, like on the server
+    }
+
+    let mut ship = physics::Body::new(
+        Vec2::new(transform.translation.x, transform.translation.y),
+        Vec2::new(velocity.0.x, velocity.0.y),
+        SHIP_RADIUS,
+    );
+
+    let body = |transform: &Transform, velocity: &Velocity, radius| {
+        physics::Body::new(
+            Vec2::new(transform.translation.x, transform.translation.y),
+            Vec2::new(velocity.0.x, velocity.0.y),
+            radius,
+        )
+    };
+
+    for (asteroid_transform, asteroid_velocity, asteroid) in &asteroids {
+        let mut other = body(asteroid_transform, asteroid_velocity, asteroid.radius);
+        if POLYGON_HITBOXES {
+            // Same outline the server collides with, at the rendered rotation
+            let rotation = asteroid_transform.rotation.to_euler(EulerRot::XYZ).2;
+            let outline: Vec<Vec2> = asteroid_outline(asteroid.id, asteroid.radius)
+                .into_iter()
+                .map(|vertex| vertex.rotated(rotation))
+                .collect();
+            physics::resolve_polygon_collision(&mut ship, &mut other, &outline, RESTITUTION, WORLD_LIMIT);
+        } else {
+            physics::resolve_collision(&mut ship, &mut other, RESTITUTION, WORLD_LIMIT);
+        }
+    }
+
+    for (other_transform, other_velocity, other_invincible) in &ships {
+        if other_invincible.enabled {
+            continue; // The server leaves invincible ships out of ship collisions
+        }
+        let mut other = body(other_transform, other_velocity, SHIP_RADIUS);
+        physics::resolve_collision(&mut ship, &mut other, RESTITUTION, WORLD_LIMIT);
+    }
+
+    transform.translation.x = ship.position.x;
+    transform.translation.y = ship.position.y;
+    velocity.0.x = ship.velocity.x;
+    velocity.0.y = ship.velocity.y;
+}
+
 pub fn update_thruster_length(
     mut thrusters: Query<(&Thruster, &ThrusterOwner, &mut Transform)>,
     velocities: Query<&Velocity>,
@@ -83,15 +161,17 @@ pub fn update_thruster_length(
     }
 }
 
-/// Update asteroid positions locally based on their velocity
+/// Update asteroid positions and spin locally based on their velocity
 /// Server will periodically correct positions in GameState (every 5 seconds)
 pub fn update_asteroids(
-    mut asteroids: Query<(&mut Transform, &Velocity), With<NetworkedAsteroid>>,
+    mut asteroids: Query<(&mut Transform, &Velocity, &AngularVelocity), With<NetworkedAsteroid>>,
     time: Res<Time>,
 ) {
     let dt = time.delta().as_secs_f32();
 
-    for (mut transform, velocity) in &mut asteroids {
+    for (mut transform, velocity, spin) in &mut asteroids {
+        transform.rotate_z(spin.0 * dt);
+
         // Update position based on velocity
         transform.translation.x += velocity.0.x * dt;
         transform.translation.y += velocity.0.y * dt;
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use crate::components::NameLabel;
+
+/// Height of the name above the ship center (just over the health bars)
+const LABEL_OFFSET: f32 = 58.0;
+
+/// Keep name labels above their ship, despawning them with it
+pub fn update_name_labels(
+    mut commands: Commands,
+    ships: Query<&Transform, Without<NameLabel>>,
+    mut labels: Query<(Entity, &NameLabel, &mut Transform)>,
+) {
+    for (entity, label, mut transform) in &mut labels {
+        let Ok(ship_transform) = ships.get(label.owner) else {
+            // Ship despawned (disconnect, removed from full state)
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        transform.translation = ship_transform.translation + Vec3::new(0.0, LABEL_OFFSET, 1.0);
+    }
+}
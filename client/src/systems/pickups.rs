@@ -0,0 +1,23 @@
+use battlestar_shared::entities::pickup::tick_modifiers;
+use bevy::prelude::*;
+use crate::components::NetworkedPickup;
+use crate::net::LocalModifiers;
+
+/// Pickup spin speed (radians/sec), purely visual
+const PICKUP_SPIN: f32 = 2.0;
+
+/// Count the local ship's timed effects down between server updates
+///
+/// Keeps prediction in step with the server: a speed boost ends on the
+/// client at the same moment instead of waiting for the next update.
+pub fn tick_local_modifiers(time: Res<Time>, mut modifiers: ResMut<LocalModifiers>) {
+    tick_modifiers(&mut modifiers.0, time.delta().as_secs_f32());
+}
+
+/// Spin pickups so they stand out from asteroids
+pub fn spin_pickups(time: Res<Time>, mut pickups: Query<&mut Transform, With<NetworkedPickup>>) {
+    let angle = PICKUP_SPIN * time.delta().as_secs_f32();
+    for mut transform in &mut pickups {
+        transform.rotate_z(angle);
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::net::NetworkClock;
+
+/// Round trip shown in the top-right corner
+#[derive(Component)]
+pub struct PingText;
+
+pub fn setup_ping_text(mut commands: Commands) {
+    commands.spawn((
+        Text::new("Ping: --"),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12.0),
+            right: px(12.0),
+            ..default()
+        },
+        PingText,
+    ));
+}
+
+/// Show the smoothed round trip and its jitter (more with F3)
+pub fn update_ping_text(network_clock: Res<NetworkClock>, mut texts: Query<&mut Text, With<PingText>>) {
+    let Some(rtt) = network_clock.rtt.rtt_ms() else {
+        return;
+    };
+    for mut text in texts.iter_mut() {
+        text.0 = format!("Ping: {:.0} ms (±{:.0})", rtt, network_clock.rtt.jitter_ms());
+    }
+}
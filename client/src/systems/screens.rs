@@ -0,0 +1,125 @@
+use battlestar_shared::network::{RoundResults, Winner};
+use bevy::prelude::*;
+
+/// Match lifecycle as announced by the server
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchState {
+    /// Waiting for enough players to join
+    #[default]
+    Lobby,
+    /// Round about to start
+    Countdown,
+    /// Round in progress (no screen, just the game)
+    Playing,
+    /// Round over, scoreboard shown until the next lobby or countdown
+    Results,
+    /// Server runs a different version: the page must be reloaded
+    Incompatible,
+}
+
+/// Latest match information received from the server
+#[derive(Resource, Default)]
+pub struct MatchStatus {
+    /// State the client should be in (applied by `sync_match_state`)
+    pub state: MatchState,
+    pub players: u32,
+    pub min_players: u32,
+    pub countdown_secs: u32,
+    pub results: Option<RoundResults>,
+}
+
+/// Text of the lobby, countdown or scoreboard screen
+#[derive(Component)]
+pub struct ScreenText;
+
+/// Switch `MatchState` when the server announces a new phase
+pub fn sync_match_state(
+    status: Res<MatchStatus>,
+    state: Res<State<MatchState>>,
+    mut next_state: ResMut<NextState<MatchState>>,
+) {
+    if status.state != *state.get() {
+        next_state.set(status.state);
+    }
+}
+
+/// Spawn the overlay for the state just entered (despawned on exit)
+pub fn spawn_screen(mut commands: Commands, state: Res<State<MatchState>>, status: Res<MatchStatus>) {
+    let state = *state.get();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: percent(100.0),
+                top: px(80.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            DespawnOnExit(state),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(screen_text(state, &status)),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                ScreenText,
+            ));
+        });
+}
+
+/// Refresh the overlay as player counts and countdown seconds arrive
+pub fn update_screen_text(
+    state: Res<State<MatchState>>,
+    status: Res<MatchStatus>,
+    mut texts: Query<&mut Text, With<ScreenText>>,
+) {
+    if !status.is_changed() {
+        return;
+    }
+    for mut text in texts.iter_mut() {
+        text.0 = screen_text(*state.get(), &status);
+    }
+}
+
+fn screen_text(state: MatchState, status: &MatchStatus) -> String {
+    match state {
+        MatchState::Lobby => format!(
+            "Waiting for players ({}/{})",
+            status.players, status.min_players
+        ),
+        MatchState::Countdown => format!("Round starts in {}", status.countdown_secs),
+        MatchState::Playing => String::new(),
+        MatchState::Results => status
+            .results
+            .as_ref()
+            .map(scoreboard_text)
+            .unwrap_or_default(),
+        MatchState::Incompatible => {
+            "The game has been updated.\nPlease refresh the page to keep playing.".to_string()
+        }
+    }
+}
+
+fn scoreboard_text(results: &RoundResults) -> String {
+    let mut lines = vec![match results.winner {
+        Winner::Player { id } => format!("Player {} wins!", id),
+        Winner::Team { team } => format!("{:?} team wins!", team),
+        Winner::Draw => "Draw!".to_string(),
+    }];
+
+    for team in &results.team_scores {
+        lines.push(format!("{:?}: {} kills", team.team, team.kills));
+    }
+    lines.push(String::new());
+    for score in &results.scores {
+        let team = score.team.map(|team| format!(" ({:?})", team)).unwrap_or_default();
+        lines.push(format!(
+            "Player {}{}  {} kills  {} deaths",
+            score.player_id, team, score.kills, score.deaths
+        ));
+    }
+    lines.join("\n")
+}
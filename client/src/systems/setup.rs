@@ -14,7 +14,7 @@ pub fn setup(
 
 pub fn setup_instructions(mut commands: Commands) {
     commands.spawn((
-        Text::new("Move the light with WASD.\nThe camera will smoothly track the light."),
+        Text::new("Move the light with WASD. Press Enter to chat.\nThe camera will smoothly track the light."),
         Node {
             position_type: PositionType::Absolute,
             bottom: px(12.0),
@@ -0,0 +1,131 @@
+// Tick time at 500 simulated players: shared mutex vs command channel
+//
+// Each player sends input at ~60Hz from its own task, like a connection
+// task would. We measure how long one game tick takes while that traffic
+// is running:
+// - shared_mutex: legacy layout, every input locks the mutex the tick holds
+// - command_channel: current layout, inputs go through an mpsc channel and
+//   the game loop owns `Game`
+//
+// Run with: cargo bench -p battlestar-server --bench input_pipeline
+
+use battlestar_server::domain::Game;
+use battlestar_server::simulation::{GameCommand, Simulation, COMMAND_CHANNEL_CAPACITY};
+use battlestar_shared::ClientInput;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, Mutex};
+
+const PLAYERS: u32 = 500;
+const DT: f32 = 1.0 / 20.0;
+const INPUT_INTERVAL: Duration = Duration::from_millis(16);
+
+fn input(player_id: u32, frame: u64) -> ClientInput {
+    ClientInput {
+        player_id,
+        thrust: 1.0,
+        rotate: if frame % 2 == 0 { 1.0 } else { -1.0 },
+    }
+}
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+/// Legacy game snapshot shared between connection tasks and the game loop
+struct LockedGame {
+    game: Game,
+    player_inputs: HashMap<u32, ClientInput>,
+}
+
+fn bench_shared_mutex(c: &mut Criterion) {
+    let rt = runtime();
+
+    let mut game = Game::new();
+    for id in 1..=PLAYERS {
+        game.spawn_player(id);
+    }
+    let shared = Arc::new(Mutex::new(LockedGame {
+        game,
+        player_inputs: HashMap::new(),
+    }));
+
+    // One producer per player, each locking the shared snapshot per input
+    for id in 1..=PLAYERS {
+        let shared = shared.clone();
+        rt.spawn(async move {
+            let mut interval = tokio::time::interval(INPUT_INTERVAL);
+            for frame in 0.. {
+                interval.tick().await;
+                shared.lock().await.player_inputs.insert(id, input(id, frame));
+            }
+        });
+    }
+
+    c.bench_function("tick_500_players/shared_mutex", |b| {
+        b.iter_custom(|iters| {
+            rt.block_on(async {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let start = Instant::now();
+                    {
+                        let mut snapshot = shared.lock().await;
+                        let inputs: Vec<_> = snapshot.player_inputs.values().cloned().collect();
+                        for input in inputs {
+                            snapshot.game.apply_input(input, DT);
+                        }
+                        snapshot.game.tick(DT);
+                        std::hint::black_box(snapshot.game.to_delta_state());
+                    }
+                    total += start.elapsed();
+                }
+                total
+            })
+        })
+    });
+}
+
+fn bench_command_channel(c: &mut Criterion) {
+    let rt = runtime();
+
+    let (tx, rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+    let mut simulation = Simulation::new(Game::new(), rx);
+    for id in 1..=PLAYERS {
+        simulation.handle_command(GameCommand::Connect { player_id: id, name: format!("Bot {}", id) });
+        simulation.handle_command(GameCommand::Input(input(id, 0)));
+    }
+
+    // One producer per player, each pushing into the command channel
+    for id in 1..=PLAYERS {
+        let tx = tx.clone();
+        rt.spawn(async move {
+            let mut interval = tokio::time::interval(INPUT_INTERVAL);
+            for frame in 0.. {
+                interval.tick().await;
+                let _ = tx.try_send(GameCommand::Input(input(id, frame)));
+            }
+        });
+    }
+
+    c.bench_function("tick_500_players/command_channel", |b| {
+        b.iter_custom(|iters| {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let start = Instant::now();
+                std::hint::black_box(simulation.step(DT));
+                total += start.elapsed();
+            }
+            total
+        })
+    });
+}
+
+criterion_group!(benches, bench_shared_mutex, bench_command_channel);
+criterion_main!(benches);
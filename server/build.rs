@@ -8,6 +8,28 @@ fn get_float(value: &toml::Value, key: &str) -> f32 {
         .unwrap_or_else(|| panic!("Missing or invalid {}", key))
 }
 
+fn get_int(value: &toml::Value, key: &str) -> i64 {
+    value.as_integer()
+        .unwrap_or_else(|| panic!("Missing or invalid {}", key))
+}
+
+fn get_bool(value: &toml::Value, key: &str) -> bool {
+    value.as_bool()
+        .unwrap_or_else(|| panic!("Missing or invalid {}", key))
+}
+
+/// FNV-1a hash of the parsed constants (must match client/build.rs)
+///
+/// Hashes the values as re-serialized by `toml`, so comments and formatting
+/// don't change it. Exchanged in the handshake to catch client/server builds
+/// made from different `game-constants.toml` files.
+fn constants_hash(config: &toml::Value) -> u64 {
+    let canonical = toml::to_string(config).expect("Failed to serialize game-constants.toml");
+    canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
 fn main() {
     // Read the shared constants file
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
@@ -32,6 +54,11 @@ fn main() {
     // Extract values
     let physics = config.get("physics").expect("Missing [physics] section");
     let gameplay = config.get("gameplay").expect("Missing [gameplay] section");
+    let collision = config.get("collision").expect("Missing [collision] section");
+    let damage = config.get("damage").expect("Missing [damage] section");
+    let pickups = config.get("pickups").expect("Missing [pickups] section");
+    let rounds = config.get("rounds").expect("Missing [rounds] section");
+    let asteroids = config.get("asteroids").expect("Missing [asteroids] section");
     
     let thrust_accel = get_float(physics.get("thrust_accel").unwrap(), "thrust_accel");
     let rotation_speed = get_float(physics.get("rotation_speed").unwrap(), "rotation_speed");
@@ -40,6 +67,36 @@ fn main() {
     let world_limit = get_float(physics.get("world_limit").unwrap(), "world_limit");
     let ship_radius = get_float(gameplay.get("ship_radius").unwrap(), "ship_radius");
     let invincibility_duration = get_float(gameplay.get("invincibility_duration").unwrap(), "invincibility_duration");
+    let collision_response = get_bool(collision.get("response").unwrap(), "response");
+    let restitution = get_float(collision.get("restitution").unwrap(), "restitution");
+    let polygon_hitboxes = get_bool(collision.get("polygon_hitboxes").unwrap(), "polygon_hitboxes");
+    let max_health = get_float(damage.get("max_health").unwrap(), "max_health");
+    let max_shield = get_float(damage.get("max_shield").unwrap(), "max_shield");
+    let shield_regen_rate = get_float(damage.get("shield_regen_rate").unwrap(), "shield_regen_rate");
+    let shield_regen_delay = get_float(damage.get("shield_regen_delay").unwrap(), "shield_regen_delay");
+    let impact_damage_factor = get_float(damage.get("impact_damage_factor").unwrap(), "impact_damage_factor");
+    let min_impact_speed = get_float(damage.get("min_impact_speed").unwrap(), "min_impact_speed");
+    let asteroid_count = get_int(asteroids.get("count").unwrap(), "count");
+    let asteroid_min_radius = get_float(asteroids.get("min_radius").unwrap(), "min_radius");
+    let asteroid_max_radius = get_float(asteroids.get("max_radius").unwrap(), "max_radius");
+    let asteroid_size_bias = get_float(asteroids.get("size_bias").unwrap(), "size_bias");
+    let asteroid_min_spacing = get_float(asteroids.get("min_spacing").unwrap(), "min_spacing");
+    let asteroid_min_speed = get_float(asteroids.get("min_speed").unwrap(), "min_speed");
+    let asteroid_max_speed = get_float(asteroids.get("max_speed").unwrap(), "max_speed");
+    let asteroid_max_spin = get_float(asteroids.get("max_spin").unwrap(), "max_spin");
+    let pickup_spawn_interval = get_float(pickups.get("spawn_interval").unwrap(), "spawn_interval");
+    let pickup_max_active = get_int(pickups.get("max_active").unwrap(), "max_active");
+    let pickup_radius = get_float(pickups.get("radius").unwrap(), "radius");
+    let pickup_duration = get_float(pickups.get("duration").unwrap(), "duration");
+    let speed_boost = get_float(pickups.get("speed_boost").unwrap(), "speed_boost");
+    let rapid_fire = get_float(pickups.get("rapid_fire").unwrap(), "rapid_fire");
+    let round_duration = get_float(rounds.get("duration").unwrap(), "duration");
+    let kill_limit = get_int(rounds.get("kill_limit").unwrap(), "kill_limit");
+    let team_kill_limit = get_int(rounds.get("team_kill_limit").unwrap(), "team_kill_limit");
+    let friendly_fire = get_bool(rounds.get("friendly_fire").unwrap(), "friendly_fire");
+    let min_players = get_int(rounds.get("min_players").unwrap(), "min_players");
+    let countdown_duration = get_float(rounds.get("countdown").unwrap(), "countdown");
+    let results_duration = get_float(rounds.get("results").unwrap(), "results");
     
     // Generate the constants.rs file
     let out_dir = env::var("OUT_DIR").unwrap();
@@ -49,6 +106,7 @@ fn main() {
         r#"// This file is auto-generated by build.rs from game-constants.toml
 // DO NOT EDIT MANUALLY - your changes will be overwritten
 
+pub const CONSTANTS_HASH: u64 = {};  // compared with the client's in the handshake
 pub const THRUST_ACCEL: f32 = {:.1};  // pixels/sec²
 pub const ROTATION_SPEED: f32 = {:.1};   // radians/sec
 pub const MAX_SPEED: f32 = {:.1};     // pixels/sec
@@ -56,7 +114,38 @@ pub const DRAG: f32 = {};            // velocity multiplier per frame
 pub const SHIP_RADIUS: f32 = {:.1};     // pixels
 pub const WORLD_LIMIT: f32 = {:.1};    // world boundary for wrapping
 pub const INVINCIBILITY_DURATION: f32 = {:.1};  // seconds after respawn
+pub const COLLISION_RESPONSE: bool = {};  // bounce instead of destroy on contact
+pub const RESTITUTION: f32 = {:?};  // fraction of closing speed kept after a bounce
+pub const POLYGON_HITBOXES: bool = {};  // collide with asteroid outlines, not bounding circles
+pub const MAX_HEALTH: f32 = {:?};  // hull points
+pub const MAX_SHIELD: f32 = {:?};  // shield points
+pub const SHIELD_REGEN_RATE: f32 = {:?};  // shield points per second
+pub const SHIELD_REGEN_DELAY: f32 = {:?};  // seconds without damage before regenerating
+pub const IMPACT_DAMAGE_FACTOR: f32 = {:?};  // damage per pixel/sec of closing speed
+pub const MIN_IMPACT_SPEED: f32 = {:?};  // closing speed below which impacts do no damage
+pub const ASTEROID_COUNT: usize = {};  // asteroids kept in the field
+pub const ASTEROID_MIN_RADIUS: f32 = {:?};  // pixels
+pub const ASTEROID_MAX_RADIUS: f32 = {:?};  // pixels
+pub const ASTEROID_SIZE_BIAS: f32 = {:?};  // >1 favours small asteroids
+pub const ASTEROID_MIN_SPACING: f32 = {:?};  // gap between asteroid surfaces when placed
+pub const ASTEROID_MIN_SPEED: f32 = {:?};  // pixels/sec
+pub const ASTEROID_MAX_SPEED: f32 = {:?};  // pixels/sec
+pub const ASTEROID_MAX_SPIN: f32 = {:?};  // radians/sec
+pub const PICKUP_SPAWN_INTERVAL: f32 = {:?};  // seconds between pickup spawns
+pub const PICKUP_MAX_ACTIVE: usize = {};  // pickups in the world at once
+pub const PICKUP_RADIUS: f32 = {:?};  // collection radius (pixels)
+pub const PICKUP_DURATION: f32 = {:?};  // seconds timed effects last
+pub const SPEED_BOOST: f32 = {:?};  // thrust and top speed multiplier while boosted
+pub const RAPID_FIRE: f32 = {:?};  // fire rate multiplier with rapid fire
+pub const ROUND_DURATION: f32 = {:?};  // seconds per round
+pub const KILL_LIMIT: u32 = {};  // free-for-all kills to win
+pub const TEAM_KILL_LIMIT: u32 = {};  // team deathmatch kills to win
+pub const FRIENDLY_FIRE: bool = {};  // teammates can damage each other
+pub const MIN_PLAYERS: u32 = {};  // players needed to start a match
+pub const COUNTDOWN_DURATION: f32 = {:?};  // seconds of countdown before a round
+pub const RESULTS_DURATION: f32 = {:?};  // seconds the results are shown
 "#,
+        constants_hash(&config),
         thrust_accel,
         rotation_speed,
         max_speed,
@@ -64,6 +153,36 @@ pub const INVINCIBILITY_DURATION: f32 = {:.1};  // seconds after respawn
         ship_radius,
         world_limit,
         invincibility_duration,
+        collision_response,
+        restitution,
+        polygon_hitboxes,
+        max_health,
+        max_shield,
+        shield_regen_rate,
+        shield_regen_delay,
+        impact_damage_factor,
+        min_impact_speed,
+        asteroid_count,
+        asteroid_min_radius,
+        asteroid_max_radius,
+        asteroid_size_bias,
+        asteroid_min_spacing,
+        asteroid_min_speed,
+        asteroid_max_speed,
+        asteroid_max_spin,
+        pickup_spawn_interval,
+        pickup_max_active,
+        pickup_radius,
+        pickup_duration,
+        speed_boost,
+        rapid_fire,
+        round_duration,
+        kill_limit,
+        team_kill_limit,
+        friendly_fire,
+        min_players,
+        countdown_duration,
+        results_duration,
     );
     
     fs::write(&dest_path, generated_code)
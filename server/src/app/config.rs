@@ -0,0 +1,234 @@
+use battlestar_shared::input::SuspicionPolicy;
+use battlestar_shared::network::{IdleAction, ModeKind};
+use std::env;
+use std::time::Duration;
+
+use super::link::LinkConditions;
+use crate::domain::{ChatFilter, LagCompensationConfig, NamePolicy, SpawnZone};
+use crate::simulation::{IdleConfig, MatchConfig};
+
+/// Server configuration, read from environment variables at startup
+///
+/// - `BATTLESTAR_INPUT_EXPIRY_MS`: held input older than this is neutral
+/// - `BATTLESTAR_IDLE_TIMEOUT_SECS`: idle timeout (0 disables)
+/// - `BATTLESTAR_IDLE_ACTION`: `park` or `kick`
+/// - `BATTLESTAR_SUSPICION_LOG` / `_THROTTLE` / `_KICK`: anti-cheat score thresholds
+/// - `BATTLESTAR_SPAWN_ZONES`: `x,y,half_width,half_height` rectangles separated
+///   by `;` (unset = anywhere in the world)
+/// - `BATTLESTAR_ASTEROID_SEED`: asteroid field seed (unset = random, logged at startup)
+/// - `BATTLESTAR_MODE`: `ffa` (free-for-all, default) or `tdm` (team deathmatch)
+/// - `BATTLESTAR_BLOCKED_NAMES`: file of words refused in player names (one per
+///   line, replaces the built-in list)
+/// - `BATTLESTAR_CHAT_FILTER`: file of words masked in chat (one per line,
+///   replaces the built-in list)
+/// - `BATTLESTAR_MIN_PLAYERS`: players needed to start a match
+/// - `BATTLESTAR_COUNTDOWN_SECS` / `BATTLESTAR_RESULTS_SECS`: countdown before a
+///   round and how long its results are shown
+/// - `BATTLESTAR_MAX_REWIND_MS`: furthest back hits are checked for laggy shooters
+/// - `BATTLESTAR_INTERPOLATION_DELAY_MS`: how far behind clients draw remote ships
+/// - `BATTLESTAR_LINK_PROFILE`: simulated network for local testing (`perfect`,
+///   `lan`, `broadband`, `mobile`, `bad`; also `--link-profile` on the command line)
+/// - `BATTLESTAR_LINK_LATENCY_MS` / `_JITTER_MS` / `_BANDWIDTH` (bytes per second)
+///   / `_REORDER` (`true`/`false`): override parts of the profile
+#[derive(Debug, Clone, Default)]
+pub struct ServerConfig {
+    pub idle: IdleConfig,
+    pub suspicion: SuspicionPolicy,
+    pub spawn_zones: Vec<SpawnZone>,
+    pub asteroid_seed: Option<u64>,
+    pub mode: ModeKind,
+    pub match_config: MatchConfig,
+    pub names: NamePolicy,
+    pub chat_filter: ChatFilter,
+    pub lag_compensation: LagCompensationConfig,
+    pub link: LinkConditions,
+}
+
+impl ServerConfig {
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Some(ms) = env_parse::<u64>("BATTLESTAR_INPUT_EXPIRY_MS") {
+            config.idle.input_expiry = Duration::from_millis(ms);
+        }
+        if let Some(secs) = env_parse::<u64>("BATTLESTAR_IDLE_TIMEOUT_SECS") {
+            config.idle.idle_timeout = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+        match env::var("BATTLESTAR_IDLE_ACTION").as_deref() {
+            Ok("park") => config.idle.action = IdleAction::Parked,
+            Ok("kick") => config.idle.action = IdleAction::Kicked,
+            Ok(other) => eprintln!("Ignoring unknown BATTLESTAR_IDLE_ACTION '{}'", other),
+            Err(_) => {}
+        }
+
+        if let Some(score) = env_parse("BATTLESTAR_SUSPICION_LOG") {
+            config.suspicion.log_at = score;
+        }
+        if let Some(score) = env_parse("BATTLESTAR_SUSPICION_THROTTLE") {
+            config.suspicion.throttle_at = score;
+        }
+        if let Some(score) = env_parse("BATTLESTAR_SUSPICION_KICK") {
+            config.suspicion.kick_at = score;
+        }
+
+        if let Ok(zones) = env::var("BATTLESTAR_SPAWN_ZONES") {
+            match parse_spawn_zones(&zones) {
+                Ok(parsed) => config.spawn_zones = parsed,
+                Err(e) => eprintln!("Ignoring invalid BATTLESTAR_SPAWN_ZONES: {}", e),
+            }
+        }
+
+        config.asteroid_seed = env_parse("BATTLESTAR_ASTEROID_SEED");
+
+        match env::var("BATTLESTAR_MODE").as_deref() {
+            Ok("ffa") => config.mode = ModeKind::FreeForAll,
+            Ok("tdm") => config.mode = ModeKind::TeamDeathmatch,
+            Ok(other) => eprintln!("Ignoring unknown BATTLESTAR_MODE '{}'", other),
+            Err(_) => {}
+        }
+
+        if let Ok(path) = env::var("BATTLESTAR_BLOCKED_NAMES") {
+            match NamePolicy::from_file(&path) {
+                Ok(policy) => config.names = policy,
+                Err(e) => eprintln!("Ignoring BATTLESTAR_BLOCKED_NAMES '{}': {}", path, e),
+            }
+        }
+
+        if let Ok(path) = env::var("BATTLESTAR_CHAT_FILTER") {
+            match ChatFilter::from_file(&path) {
+                Ok(filter) => config.chat_filter = filter,
+                Err(e) => eprintln!("Ignoring BATTLESTAR_CHAT_FILTER '{}': {}", path, e),
+            }
+        }
+
+        if let Some(players) = env_parse("BATTLESTAR_MIN_PLAYERS") {
+            config.match_config.min_players = players;
+        }
+        if let Some(secs) = env_parse::<u64>("BATTLESTAR_COUNTDOWN_SECS") {
+            config.match_config.countdown = Duration::from_secs(secs);
+        }
+        if let Some(secs) = env_parse::<u64>("BATTLESTAR_RESULTS_SECS") {
+            config.match_config.results = Duration::from_secs(secs);
+        }
+
+        if let Some(ms) = env_parse::<u64>("BATTLESTAR_MAX_REWIND_MS") {
+            config.lag_compensation.max_rewind = Duration::from_millis(ms);
+        }
+        if let Some(ms) = env_parse::<u64>("BATTLESTAR_INTERPOLATION_DELAY_MS") {
+            config.lag_compensation.interpolation_delay = Duration::from_millis(ms);
+        }
+
+        config.link = link_conditions(env::var("BATTLESTAR_LINK_PROFILE").ok().as_deref());
+
+        config
+    }
+
+    /// Apply command line options (`--link-profile <name>` or
+    /// `--link-profile=<name>`)
+    ///
+    /// A profile given here replaces the one from the environment; the
+    /// `BATTLESTAR_LINK_*` overrides still apply on top of it.
+    pub fn apply_args(&mut self, args: impl IntoIterator<Item = String>) {
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            match flag {
+                "--link-profile" => {
+                    // The next token is the value unless it is another flag
+                    let value = inline_value.or_else(|| args.next_if(|next| !next.starts_with("--")));
+                    match value {
+                        Some(profile) => self.link = link_conditions(Some(&profile)),
+                        None => eprintln!("Ignoring --link-profile without a profile name"),
+                    }
+                }
+                other => eprintln!("Ignoring unknown argument '{}'", other),
+            }
+        }
+    }
+}
+
+/// Simulated network: the named profile (if any) with the
+/// `BATTLESTAR_LINK_*` per-field overrides applied on top
+fn link_conditions(profile: Option<&str>) -> LinkConditions {
+    let mut link = match profile.map(str::parse::<LinkConditions>) {
+        Some(Ok(link)) => link,
+        Some(Err(e)) => {
+            eprintln!("Ignoring link profile: {}", e);
+            LinkConditions::PERFECT
+        }
+        None => LinkConditions::PERFECT,
+    };
+
+    if let Some(ms) = env_parse::<u64>("BATTLESTAR_LINK_LATENCY_MS") {
+        link.latency = Duration::from_millis(ms);
+    }
+    if let Some(ms) = env_parse::<u64>("BATTLESTAR_LINK_JITTER_MS") {
+        link.jitter = Duration::from_millis(ms);
+    }
+    if let Some(bytes) = env_parse::<u32>("BATTLESTAR_LINK_BANDWIDTH") {
+        link.bandwidth = (bytes > 0).then_some(bytes);
+    }
+    if let Some(reorder) = env_parse("BATTLESTAR_LINK_REORDER") {
+        link.reorder = reorder;
+    }
+    link
+}
+
+/// Parse `;`-separated spawn zones
+fn parse_spawn_zones(value: &str) -> Result<Vec<SpawnZone>, String> {
+    value
+        .split(';')
+        .filter(|zone| !zone.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Parse an environment variable, warning (and ignoring it) if malformed
+fn env_parse<T: std::str::FromStr>(key: &str) -> Option<T> {
+    let value = env::var(key).ok()?;
+    match value.parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            eprintln!("Ignoring invalid {}='{}'", key, value);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_link_profile_argument() {
+        let lan: LinkConditions = "lan".parse().unwrap();
+
+        let mut config = ServerConfig::default();
+        config.apply_args(args(&["--link-profile", "lan"]));
+        assert_eq!(config.link.latency, lan.latency);
+
+        let mut config = ServerConfig::default();
+        config.apply_args(args(&["--link-profile=lan"]));
+        assert_eq!(config.link.latency, lan.latency);
+    }
+
+    #[test]
+    fn test_unknown_and_valueless_flags_keep_the_next_argument() {
+        let lan: LinkConditions = "lan".parse().unwrap();
+
+        let mut config = ServerConfig::default();
+        config.apply_args(args(&["--verbose", "--link-profile", "lan"]));
+        assert_eq!(config.link.latency, lan.latency);
+
+        let mut config = ServerConfig::default();
+        config.apply_args(args(&["--link-profile", "--verbose"]));
+        assert!(config.link.is_perfect());
+    }
+}
@@ -0,0 +1,140 @@
+use axum::extract::ws::{CloseFrame, Message};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::outbound::{OutboundKind, OutboundQueue, QueueStats, OUTBOUND_QUEUE_CAPACITY};
+
+/// WebSocket close code sent when the server kicks a client
+/// (4000-4999 is reserved for application use)
+pub const KICK_CLOSE_CODE: u16 = 4000;
+
+/// Round trip measured by a connection's pings
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Latency {
+    /// Smoothed round trip
+    pub rtt_ms: f64,
+
+    /// Mean deviation of the round trip
+    pub jitter_ms: f64,
+}
+
+/// Registry of connected clients and their outbound queues
+///
+/// Replaces the single `broadcast` channel: each connection owns a bounded
+/// queue, so one slow socket can't make every other client lag behind.
+#[derive(Default)]
+pub struct Connections {
+    clients: Mutex<HashMap<u32, Arc<OutboundQueue>>>,
+    latencies: Mutex<HashMap<u32, Latency>>,
+}
+
+impl Connections {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a connection and return its outbound queue
+    pub fn register(&self, player_id: u32) -> Arc<OutboundQueue> {
+        self.insert(player_id, Arc::new(OutboundQueue::new(OUTBOUND_QUEUE_CAPACITY)))
+    }
+
+    /// Register a connection whose queue starts with `first`
+    ///
+    /// The message is queued before the connection is visible, so no
+    /// broadcast can get ahead of it.
+    pub fn register_with(&self, player_id: u32, first: Message) -> Arc<OutboundQueue> {
+        let queue = Arc::new(OutboundQueue::new(OUTBOUND_QUEUE_CAPACITY));
+        queue.push(OutboundKind::Control, first);
+        self.insert(player_id, queue)
+    }
+
+    fn insert(&self, player_id: u32, queue: Arc<OutboundQueue>) -> Arc<OutboundQueue> {
+        self.clients
+            .lock()
+            .unwrap()
+            .insert(player_id, queue.clone());
+        queue
+    }
+
+    /// Unregister a connection and close its queue
+    pub fn remove(&self, player_id: u32) {
+        if let Some(queue) = self.clients.lock().unwrap().remove(&player_id) {
+            queue.close();
+        }
+        self.latencies.lock().unwrap().remove(&player_id);
+    }
+
+    /// Push a text message to every connected client
+    ///
+    /// Never blocks: full queues drop deltas instead of waiting.
+    pub fn broadcast(&self, kind: OutboundKind, payload: String) {
+        let message = Message::Text(payload.into());
+        for queue in self.clients.lock().unwrap().values() {
+            queue.push(kind, message.clone());
+        }
+    }
+
+    /// Push a text message to a single client
+    pub fn send_to(&self, player_id: u32, kind: OutboundKind, payload: String) -> bool {
+        match self.clients.lock().unwrap().get(&player_id) {
+            Some(queue) => queue.push(kind, Message::Text(payload.into())),
+            None => false,
+        }
+    }
+
+    /// Clients that dropped deltas and are waiting for a full state
+    pub fn needing_full_state(&self) -> Vec<u32> {
+        self.clients
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, queue)| queue.needs_full_state())
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Close a client's connection after its queued messages are written
+    pub fn kick(&self, player_id: u32) {
+        if let Some(queue) = self.clients.lock().unwrap().get(&player_id) {
+            queue.push(
+                OutboundKind::Control,
+                Message::Close(Some(CloseFrame {
+                    code: KICK_CLOSE_CODE,
+                    reason: "kicked".into(),
+                })),
+            );
+            queue.close();
+        }
+    }
+
+    /// Record a connection's latest round-trip estimate
+    pub fn set_latency(&self, player_id: u32, latency: Latency) {
+        self.latencies.lock().unwrap().insert(player_id, latency);
+    }
+
+    /// Round trip of every connection measured so far, sorted by player ID
+    pub fn latencies(&self) -> Vec<(u32, Latency)> {
+        let mut latencies: Vec<_> = self
+            .latencies
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, latency)| (*id, *latency))
+            .collect();
+        latencies.sort_by_key(|(id, _)| *id);
+        latencies
+    }
+
+    /// Queue counters for every connection, sorted by player ID
+    pub fn queue_stats(&self) -> Vec<(u32, QueueStats)> {
+        let mut stats: Vec<_> = self
+            .clients
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, queue)| (*id, queue.stats()))
+            .collect();
+        stats.sort_by_key(|(id, _)| *id);
+        stats
+    }
+}
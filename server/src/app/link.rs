@@ -0,0 +1,270 @@
+use rand::{rngs::StdRng, RngExt};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Simulated network conditions applied to every connection (testing only)
+///
+/// Each direction of each connection gets its own conditioner, so the round
+/// trip sees twice the `latency`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkConditions {
+    /// Delay added to every message, one way
+    pub latency: Duration,
+
+    /// Up to this much more or less delay, picked per message
+    pub jitter: Duration,
+
+    /// Let jittered messages overtake each other (otherwise order is kept
+    /// and a late message holds back the ones behind it)
+    pub reorder: bool,
+
+    /// Bytes per second through the link, one way (None = unlimited)
+    pub bandwidth: Option<u32>,
+}
+
+impl LinkConditions {
+    /// No added delay, jitter or cap
+    pub const PERFECT: Self = Self {
+        latency: Duration::ZERO,
+        jitter: Duration::ZERO,
+        reorder: false,
+        bandwidth: None,
+    };
+
+    /// Whether messages go through untouched
+    pub fn is_perfect(&self) -> bool {
+        *self == Self::PERFECT
+    }
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self::PERFECT
+    }
+}
+
+/// Named presets for `BATTLESTAR_LINK_PROFILE` / `--link-profile`
+///
+/// - `perfect`: no conditioning
+/// - `lan`: 2ms, no jitter
+/// - `broadband`: 20ms ±5ms (~40ms ping)
+/// - `mobile`: 100ms ±30ms, 1 Mbit/s (~200ms ping)
+/// - `bad`: 250ms ±100ms, reordering, 256 kbit/s
+impl FromStr for LinkConditions {
+    type Err = String;
+
+    fn from_str(profile: &str) -> Result<Self, Self::Err> {
+        let ms = Duration::from_millis;
+        match profile {
+            "perfect" | "none" => Ok(Self::PERFECT),
+            "lan" => Ok(Self { latency: ms(2), ..Self::PERFECT }),
+            "broadband" => Ok(Self { latency: ms(20), jitter: ms(5), ..Self::PERFECT }),
+            "mobile" => Ok(Self {
+                latency: ms(100),
+                jitter: ms(30),
+                reorder: false,
+                bandwidth: Some(125_000),
+            }),
+            "bad" => Ok(Self {
+                latency: ms(250),
+                jitter: ms(100),
+                reorder: true,
+                bandwidth: Some(32_000),
+            }),
+            other => Err(format!(
+                "unknown link profile '{}' (perfect, lan, broadband, mobile, bad)",
+                other
+            )),
+        }
+    }
+}
+
+/// Decides when each message of one direction comes out of the link
+pub struct LinkConditioner {
+    conditions: LinkConditions,
+    rng: StdRng,
+    /// When the link is done transmitting the previous message (bandwidth)
+    link_free_at: Option<Instant>,
+    /// Latest release so far (keeps order when reordering is off)
+    last_release: Option<Instant>,
+}
+
+impl LinkConditioner {
+    pub fn new(conditions: LinkConditions) -> Self {
+        Self::with_rng(conditions, rand::make_rng())
+    }
+
+    pub fn with_rng(conditions: LinkConditions, rng: StdRng) -> Self {
+        Self {
+            conditions,
+            rng,
+            link_free_at: None,
+            last_release: None,
+        }
+    }
+
+    /// When the capped link is done transmitting what was scheduled so far
+    /// (None until the first message)
+    pub fn free_at(&self) -> Option<Instant> {
+        self.link_free_at
+    }
+
+    /// Time at which a message of `bytes` sent at `now` is delivered
+    pub fn schedule(&mut self, now: Instant, bytes: usize) -> Instant {
+        // Wait for the messages ahead to go through the capped link
+        let mut sent = self.link_free_at.map_or(now, |free| free.max(now));
+        if let Some(bandwidth) = self.conditions.bandwidth {
+            sent += Duration::from_secs_f64(bytes as f64 / bandwidth.max(1) as f64);
+        }
+        self.link_free_at = Some(sent);
+
+        let jitter = self.conditions.jitter.as_secs_f64();
+        let offset = if jitter > 0.0 {
+            self.rng.random_range(-jitter..=jitter)
+        } else {
+            0.0
+        };
+        let delay = (self.conditions.latency.as_secs_f64() + offset).max(0.0);
+        let mut release = sent + Duration::from_secs_f64(delay);
+
+        if !self.conditions.reorder {
+            release = self.last_release.map_or(release, |last| release.max(last));
+        }
+        self.last_release = Some(self.last_release.map_or(release, |last| last.max(release)));
+        release
+    }
+}
+
+/// Messages waiting in the link, handed out by release time
+pub struct DelayLine<T> {
+    /// Sorted by release time (same release: first in, first out)
+    queue: VecDeque<(Instant, T)>,
+}
+
+impl<T> Default for DelayLine<T> {
+    fn default() -> Self {
+        Self { queue: VecDeque::new() }
+    }
+}
+
+impl<T> DelayLine<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold `item` until `release`
+    pub fn push(&mut self, release: Instant, item: T) {
+        let index = self.queue.partition_point(|(queued, _)| *queued <= release);
+        self.queue.insert(index, (release, item));
+    }
+
+    /// When the next item comes out
+    pub fn next_release(&self) -> Option<Instant> {
+        self.queue.front().map(|(release, _)| *release)
+    }
+
+    /// The next item if its release time has passed
+    pub fn pop_ready(&mut self, now: Instant) -> Option<T> {
+        if self.next_release()? > now {
+            return None;
+        }
+        self.queue.pop_front().map(|(_, item)| item)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn conditioner(conditions: LinkConditions) -> LinkConditioner {
+        LinkConditioner::with_rng(conditions, StdRng::seed_from_u64(7))
+    }
+
+    #[test]
+    fn test_profiles_parse() {
+        assert!("perfect".parse::<LinkConditions>().unwrap().is_perfect());
+        let mobile: LinkConditions = "mobile".parse().unwrap();
+        assert_eq!(mobile.latency, Duration::from_millis(100));
+        assert!("dialup".parse::<LinkConditions>().is_err());
+    }
+
+    #[test]
+    fn test_latency_and_jitter_bounds() {
+        let conditions = LinkConditions {
+            latency: Duration::from_millis(100),
+            jitter: Duration::from_millis(30),
+            reorder: true,
+            bandwidth: None,
+        };
+        let mut link = conditioner(conditions);
+        let now = Instant::now();
+        for _ in 0..100 {
+            let delay = link.schedule(now, 10) - now;
+            assert!(delay >= Duration::from_millis(70) && delay <= Duration::from_millis(130), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn test_order_kept_unless_reordering() {
+        let jittery = LinkConditions {
+            latency: Duration::from_millis(50),
+            jitter: Duration::from_millis(50),
+            reorder: false,
+            bandwidth: None,
+        };
+        let now = Instant::now();
+        let releases = |conditions| {
+            let mut link = conditioner(conditions);
+            (0..50u64)
+                .map(|i| link.schedule(now + Duration::from_millis(i), 10))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(releases(jittery).windows(2).all(|pair| pair[0] <= pair[1]));
+        let reordered = releases(LinkConditions { reorder: true, ..jittery });
+        assert!(reordered.windows(2).any(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn test_bandwidth_queues_messages() {
+        // 1000 bytes per second: each 100-byte message takes 100ms
+        let conditions = LinkConditions {
+            bandwidth: Some(1000),
+            ..LinkConditions::PERFECT
+        };
+        let mut link = conditioner(conditions);
+        let now = Instant::now();
+        assert_eq!(link.schedule(now, 100) - now, Duration::from_millis(100));
+        assert_eq!(link.schedule(now, 100) - now, Duration::from_millis(200));
+        assert_eq!(link.free_at(), Some(now + Duration::from_millis(200)));
+
+        // Idle link: no backlog left
+        let later = now + Duration::from_secs(1);
+        assert_eq!(link.schedule(later, 100) - later, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_delay_line_releases_in_time_order() {
+        let now = Instant::now();
+        let mut line = DelayLine::new();
+        line.push(now + Duration::from_millis(20), "late");
+        line.push(now + Duration::from_millis(10), "early");
+        line.push(now + Duration::from_millis(10), "early too");
+
+        assert_eq!(line.next_release(), Some(now + Duration::from_millis(10)));
+        assert_eq!(line.pop_ready(now), None);
+
+        let at = now + Duration::from_millis(15);
+        assert_eq!(line.pop_ready(at), Some("early"));
+        assert_eq!(line.pop_ready(at), Some("early too"));
+        assert_eq!(line.pop_ready(at), None);
+        assert_eq!(line.pop_ready(now + Duration::from_millis(20)), Some("late"));
+        assert!(line.is_empty());
+    }
+}
@@ -1,3 +1,10 @@
 pub mod state;
+pub mod config;
+pub mod outbound;
+pub mod connections;
+pub mod link;
 
 pub use state::*;
+pub use config::*;
+pub use outbound::*;
+pub use link::*;
@@ -0,0 +1,286 @@
+use axum::extract::ws::Message;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// Default number of messages a connection may have waiting to be written
+///
+/// At 20 Hz this is ~0.4s of game state. A client that falls further behind
+/// than that gets coalesced snapshots instead of an ever-growing backlog.
+pub const OUTBOUND_QUEUE_CAPACITY: usize = 8;
+
+/// Control messages a connection may have waiting before it is dropped
+///
+/// Control messages can't be coalesced, so a client that stops reading
+/// would otherwise grow its queue without bound.
+pub const OUTBOUND_CONTROL_CAPACITY: usize = 256;
+
+/// How an outbound message may be treated when the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundKind {
+    /// Must be delivered (Welcome, per-player notices, close frames)
+    Control,
+
+    /// Delta snapshot: only what changed this tick
+    ///
+    /// Deltas carry one-shot data (removed ships, spawn colors, pickups,
+    /// knocked asteroids), so dropping one loses it. When deltas have to be
+    /// dropped, the connection waits for a full state instead.
+    Delta,
+
+    /// Full game state: supersedes every snapshot queued before it
+    FullState,
+}
+
+/// Counters exposed through the `/metrics` endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Messages currently waiting to be written
+    pub depth: usize,
+
+    /// Highest depth observed since the connection opened
+    pub high_water: usize,
+
+    /// Snapshots dropped because the queue was full or a full state replaced them
+    pub coalesced: u64,
+
+    /// Messages handed to the writer task
+    pub sent: u64,
+}
+
+struct QueueInner {
+    messages: VecDeque<(OutboundKind, Message)>,
+    closed: bool,
+    /// Deltas were dropped: discard the rest until the next full state
+    needs_full_state: bool,
+    stats: QueueStats,
+}
+
+/// Bounded per-connection outbound queue
+///
+/// The game loop pushes without ever awaiting, and a dedicated writer task
+/// drains the queue into the socket. A slow client therefore only delays its
+/// own writer, never the game loop or its own input processing.
+///
+/// When the queue is full, queued snapshots are discarded and the client
+/// skips deltas until a full state resyncs it (see `needs_full_state`).
+/// Control messages are never dropped; past `OUTBOUND_CONTROL_CAPACITY`
+/// the queue closes instead, which ends the connection.
+pub struct OutboundQueue {
+    inner: Mutex<QueueInner>,
+    notify: Notify,
+    capacity: usize,
+}
+
+impl OutboundQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(QueueInner {
+                messages: VecDeque::with_capacity(capacity),
+                closed: false,
+                needs_full_state: false,
+                stats: QueueStats::default(),
+            }),
+            notify: Notify::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Queue a message for the writer task
+    ///
+    /// Returns false if the queue has been closed.
+    pub fn push(&self, kind: OutboundKind, message: Message) -> bool {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.closed {
+                return false;
+            }
+
+            match kind {
+                OutboundKind::Control => {
+                    let controls = inner.messages.iter().filter(|(k, _)| *k == OutboundKind::Control).count();
+                    if controls >= OUTBOUND_CONTROL_CAPACITY {
+                        // Not reading at all: give up on this client
+                        inner.messages.clear();
+                        inner.stats.depth = 0;
+                        inner.closed = true;
+                        drop(inner);
+                        self.notify.notify_one();
+                        return false;
+                    }
+                }
+                OutboundKind::Delta => {
+                    if inner.needs_full_state {
+                        inner.stats.coalesced += 1;
+                        return true;
+                    }
+                    // Full: drop every snapshot and wait for a full state
+                    if inner.messages.len() >= self.capacity {
+                        inner.drop_snapshots();
+                        inner.stats.coalesced += 1;
+                        inner.needs_full_state = true;
+                        return true;
+                    }
+                }
+                OutboundKind::FullState => {
+                    // Whatever is queued is older than this state
+                    inner.drop_snapshots();
+                    inner.needs_full_state = false;
+                }
+            }
+
+            inner.messages.push_back((kind, message));
+            inner.stats.depth = inner.messages.len();
+            inner.stats.high_water = inner.stats.high_water.max(inner.stats.depth);
+        }
+
+        self.notify.notify_one();
+        true
+    }
+
+    /// Wait for the next message to write
+    ///
+    /// Returns None once the queue is closed and drained.
+    pub async fn pop(&self) -> Option<Message> {
+        loop {
+            {
+                let mut inner = self.inner.lock().unwrap();
+                if let Some((_, message)) = inner.messages.pop_front() {
+                    inner.stats.depth = inner.messages.len();
+                    inner.stats.sent += 1;
+                    return Some(message);
+                }
+                if inner.closed {
+                    return None;
+                }
+            }
+
+            self.notify.notified().await;
+        }
+    }
+
+    /// Whether deltas were dropped and the client needs a full state
+    pub fn needs_full_state(&self) -> bool {
+        self.inner.lock().unwrap().needs_full_state
+    }
+
+    /// Stop accepting messages and wake the writer so it can exit
+    pub fn close(&self) {
+        self.inner.lock().unwrap().closed = true;
+        self.notify.notify_one();
+    }
+
+    /// Snapshot of the queue counters
+    pub fn stats(&self) -> QueueStats {
+        self.inner.lock().unwrap().stats
+    }
+}
+
+impl QueueInner {
+    fn drop_snapshots(&mut self) {
+        let before = self.messages.len();
+        self.messages.retain(|(k, _)| *k == OutboundKind::Control);
+        self.stats.coalesced += (before - self.messages.len()) as u64;
+        self.stats.depth = self.messages.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Message {
+        Message::Text(s.into())
+    }
+
+    fn as_text(message: Message) -> String {
+        match message {
+            Message::Text(text) => text.to_string(),
+            other => panic!("expected text message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_preserves_order() {
+        let queue = OutboundQueue::new(4);
+        queue.push(OutboundKind::Control, text("welcome"));
+        queue.push(OutboundKind::Delta, text("tick-1"));
+
+        assert_eq!(as_text(queue.pop().await.unwrap()), "welcome");
+        assert_eq!(as_text(queue.pop().await.unwrap()), "tick-1");
+        assert_eq!(queue.stats().sent, 2);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_waits_for_full_state() {
+        let queue = OutboundQueue::new(3);
+        queue.push(OutboundKind::Control, text("welcome"));
+        queue.push(OutboundKind::Delta, text("tick-1"));
+        queue.push(OutboundKind::Delta, text("tick-2"));
+
+        // Queue is full: every delta is dropped until a full state arrives
+        queue.push(OutboundKind::Delta, text("tick-3"));
+        assert!(queue.needs_full_state());
+        queue.push(OutboundKind::Delta, text("tick-4"));
+        queue.push(OutboundKind::FullState, text("full-5"));
+        assert!(!queue.needs_full_state());
+        queue.push(OutboundKind::Delta, text("tick-6"));
+
+        let stats = queue.stats();
+        assert_eq!(stats.depth, 3);
+        assert_eq!(stats.coalesced, 4);
+        assert_eq!(stats.high_water, 3);
+
+        assert_eq!(as_text(queue.pop().await.unwrap()), "welcome");
+        assert_eq!(as_text(queue.pop().await.unwrap()), "full-5");
+        assert_eq!(as_text(queue.pop().await.unwrap()), "tick-6");
+    }
+
+    #[tokio::test]
+    async fn test_full_state_replaces_queued_snapshots() {
+        let queue = OutboundQueue::new(8);
+        queue.push(OutboundKind::FullState, text("full-1"));
+        queue.push(OutboundKind::Delta, text("tick-2"));
+        queue.push(OutboundKind::Control, text("chat"));
+        queue.push(OutboundKind::FullState, text("full-3"));
+
+        assert_eq!(as_text(queue.pop().await.unwrap()), "chat");
+        assert_eq!(as_text(queue.pop().await.unwrap()), "full-3");
+        assert_eq!(queue.stats().coalesced, 2);
+    }
+
+    #[tokio::test]
+    async fn test_control_overflow_closes_queue() {
+        let queue = OutboundQueue::new(1);
+        for _ in 0..OUTBOUND_CONTROL_CAPACITY {
+            assert!(queue.push(OutboundKind::Control, text("event")));
+        }
+
+        assert!(!queue.push(OutboundKind::Control, text("one too many")));
+        assert!(queue.pop().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_control_messages_are_never_dropped() {
+        let queue = OutboundQueue::new(1);
+        queue.push(OutboundKind::Control, text("a"));
+        queue.push(OutboundKind::Control, text("b"));
+        queue.push(OutboundKind::Delta, text("tick"));
+
+        // Over capacity: the delta goes, the control messages stay
+        assert_eq!(queue.stats().depth, 2);
+        assert_eq!(as_text(queue.pop().await.unwrap()), "a");
+        assert_eq!(as_text(queue.pop().await.unwrap()), "b");
+    }
+
+    #[tokio::test]
+    async fn test_closed_queue_drains_then_ends() {
+        let queue = OutboundQueue::new(4);
+        queue.push(OutboundKind::Delta, text("tick"));
+        queue.close();
+
+        assert!(!queue.push(OutboundKind::Delta, text("late")));
+        assert!(queue.pop().await.is_some());
+        assert!(queue.pop().await.is_none());
+    }
+}
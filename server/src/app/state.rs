@@ -1,71 +1,104 @@
-use battlestar_shared::ClientInput;
-use std::{
-    collections::{HashMap, HashSet},
-    sync::{atomic::AtomicU32, Arc},
-    time::Instant,
-};
-use tokio::sync::{broadcast, Mutex};
+use battlestar_shared::input::SuspicionPolicy;
+use std::sync::{atomic::{AtomicU32, AtomicU64}, Arc};
+use std::time::Instant;
+use tokio::sync::mpsc;
 
-use crate::domain::Game;
-use crate::simulation;
+use super::config::ServerConfig;
+use super::connections::Connections;
+use super::link::LinkConditions;
+use crate::domain::{create_mode, ChatFilter, Game, NamePolicy};
+use crate::simulation::{
+    self, GameCommand, MatchLifecycle, Simulation, TickDriver, TickSource, COMMAND_CHANNEL_CAPACITY,
+};
 
-/// Optimized application state with single mutex for game snapshot
-///
-/// Previous architecture had 4 separate Arc<Mutex<>>:
-/// - game_state, player_inputs, connected_players, last_input_time
+/// Application state shared by connection tasks
 ///
-/// This caused 4+ lock acquisitions per tick, creating contention.
-///
-/// New architecture uses a single GameSnapshot mutex containing all
-/// mutable state, reducing lock contention by 50-70%.
+/// Holds no game state: `Game` is owned by the game loop task, and
+/// connection tasks talk to it through the `commands` channel. Rate limiting
+/// lives in each connection task, so nothing here is locked per input.
 pub struct AppState {
-    /// Broadcast channel for sending game state updates to all clients
-    pub broadcaster: broadcast::Sender<String>,
+    /// Connected clients and their bounded outbound queues
+    pub connections: Connections,
 
-    /// Single mutex containing all game state (reduces contention)
-    pub game: Arc<Mutex<GameSnapshot>>,
+    /// Command channel into the game loop (connects, inputs, disconnects)
+    pub commands: mpsc::Sender<GameCommand>,
 
     /// Lock-free atomic counter for player IDs
     pub next_player_id: AtomicU32,
-}
 
-/// Snapshot of all mutable game state
-///
-/// All fields that need to be accessed together are grouped here.
-/// This ensures consistent snapshots and reduces the number of locks.
-pub struct GameSnapshot {
-    /// Core game logic and state
-    pub game: Game,
+    /// Anti-cheat thresholds applied by each connection's input monitor
+    pub suspicion: SuspicionPolicy,
+
+    /// Checks the names players pick in their handshake
+    pub names: NamePolicy,
+
+    /// Masks filtered words in chat messages
+    pub chat_filter: ChatFilter,
 
-    /// Latest input per player (one per tick)
-    pub player_inputs: HashMap<u32, ClientInput>,
+    /// Origin of the server clock sent in pings and pongs
+    pub started_at: Instant,
 
-    /// Set of currently connected player IDs
-    pub connected_players: HashSet<u32>,
+    /// Latest game tick (written by the game loop)
+    pub tick: AtomicU64,
 
-    /// Rate limiting: track last input time per player (anti-cheat)
-    pub last_input_time: HashMap<u32, Instant>,
+    /// Simulated network applied to every connection (local testing)
+    pub link: LinkConditions,
 }
 
 impl AppState {
     pub fn new() -> Arc<Self> {
-        let (tx, _rx) = broadcast::channel(256);
+        Self::with_config(ServerConfig::default())
+    }
 
-        let game_snapshot = GameSnapshot {
-            game: Game::new(),
-            player_inputs: HashMap::new(),
-            connected_players: HashSet::new(),
-            last_input_time: HashMap::new(),
-        };
+    /// Milliseconds since the server started
+    pub fn server_time_ms(&self) -> f64 {
+        self.started_at.elapsed().as_secs_f64() * 1000.0
+    }
+
+    pub fn with_config(config: ServerConfig) -> Arc<Self> {
+        Self::start(config, TickSource::Interval)
+    }
+
+    /// State whose game loop only ticks when told to (integration tests)
+    pub fn with_manual_ticks(config: ServerConfig) -> (Arc<Self>, TickDriver) {
+        let (driver, source) = TickDriver::new();
+        (Self::start(config, source), driver)
+    }
+
+    fn start(config: ServerConfig, source: TickSource) -> Arc<Self> {
+        let (tx, rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
 
         let state = Arc::new(AppState {
-            broadcaster: tx,
-            game: Arc::new(Mutex::new(game_snapshot)),
+            connections: Connections::new(),
+            commands: tx,
             next_player_id: AtomicU32::new(1),
+            suspicion: config.suspicion,
+            names: config.names,
+            chat_filter: config.chat_filter,
+            started_at: Instant::now(),
+            tick: AtomicU64::new(0),
+            link: config.link,
         });
 
-        // Spawn game loop in background
-        simulation::spawn_game_loop(state.clone());
+        if !config.link.is_perfect() {
+            println!("Simulating network conditions: {:?}", config.link);
+        }
+
+        let seed = config.asteroid_seed.unwrap_or_else(rand::random);
+        println!("Asteroid field seed: {}", seed);
+
+        let mut game = Game::with_seed(seed);
+        game.spawn.zones = config.spawn_zones;
+        game.mode = create_mode(config.mode);
+        game.set_lag_compensation(config.lag_compensation);
+        println!("Game mode: {:?}", config.mode);
+
+        let mut simulation = Simulation::new(game, rx);
+        simulation.idle = config.idle;
+        simulation.lifecycle = MatchLifecycle::new(config.match_config);
+
+        // Spawn game loop in background; it owns the game from here on
+        simulation::spawn_game_loop(state.clone(), simulation, source);
 
         state
     }
@@ -0,0 +1,227 @@
+use battlestar_shared::{entities::Asteroid, math::SeededRng, Vec2};
+use std::f32::consts::{PI, TAU};
+
+use crate::constants::*;
+
+/// Seed used by `Game::new` (tests and local runs get the same field)
+pub const DEFAULT_ASTEROID_SEED: u64 = 0x0BA7_7E57_A125;
+
+/// Placement attempts per asteroid before giving up until the next top-up
+const MAX_PLACEMENT_ATTEMPTS: usize = 32;
+
+/// Minimum distance from any ship for asteroids added mid-game
+///
+/// Keeps replacements from appearing on top of (or right in front of) a player.
+pub const SHIP_CLEARANCE: f32 = 400.0;
+
+/// Shape of a generated asteroid field (from game-constants.toml)
+#[derive(Debug, Clone)]
+pub struct FieldConfig {
+    /// Asteroids kept in the field
+    pub count: usize,
+
+    pub min_radius: f32,
+    pub max_radius: f32,
+
+    /// Radius = min + range * u^bias (>1 favours small asteroids)
+    pub size_bias: f32,
+
+    /// Minimum gap between asteroid surfaces when placed
+    pub min_spacing: f32,
+
+    pub min_speed: f32,
+    pub max_speed: f32,
+
+    /// Spin is drawn from [-max_spin, max_spin] (radians/sec)
+    pub max_spin: f32,
+}
+
+impl Default for FieldConfig {
+    fn default() -> Self {
+        Self {
+            count: ASTEROID_COUNT,
+            min_radius: ASTEROID_MIN_RADIUS,
+            max_radius: ASTEROID_MAX_RADIUS,
+            size_bias: ASTEROID_SIZE_BIAS,
+            min_spacing: ASTEROID_MIN_SPACING,
+            min_speed: ASTEROID_MIN_SPEED,
+            max_speed: ASTEROID_MAX_SPEED,
+            max_spin: ASTEROID_MAX_SPIN,
+        }
+    }
+}
+
+/// Seeded asteroid field generator and density maintainer
+///
+/// Every random draw comes from one `SeededRng`, so the same seed and the
+/// same sequence of calls produce the same asteroids.
+#[derive(Debug, Clone)]
+pub struct AsteroidField {
+    pub config: FieldConfig,
+    rng: SeededRng,
+    next_id: u32,
+}
+
+impl AsteroidField {
+    pub fn new(seed: u64, config: FieldConfig) -> Self {
+        Self {
+            config,
+            rng: SeededRng::new(seed),
+            next_id: 1,
+        }
+    }
+
+    /// Fill an empty world with `config.count` asteroids
+    pub fn generate(&mut self, world_limit: f32) -> Vec<Asteroid> {
+        let mut asteroids = Vec::with_capacity(self.config.count);
+        self.maintain(&mut asteroids, &[], world_limit);
+        asteroids
+    }
+
+    /// Top the field back up to `config.count`
+    ///
+    /// New asteroids keep `min_spacing` from the others and `SHIP_CLEARANCE`
+    /// from every ship. If there's no room the rest are added on a later call.
+    /// Returns the IDs of the asteroids added.
+    pub fn maintain(
+        &mut self,
+        asteroids: &mut Vec<Asteroid>,
+        ships: &[Vec2],
+        world_limit: f32,
+    ) -> Vec<u32> {
+        let mut added = Vec::new();
+
+        while asteroids.len() < self.config.count {
+            let Some(asteroid) = self.place(asteroids, ships, world_limit) else {
+                break;
+            };
+            added.push(asteroid.id);
+            asteroids.push(asteroid);
+        }
+
+        added
+    }
+
+    /// Try to place one new asteroid
+    fn place(&mut self, asteroids: &[Asteroid], ships: &[Vec2], world_limit: f32) -> Option<Asteroid> {
+        let radius = self.radius();
+
+        for _ in 0..MAX_PLACEMENT_ATTEMPTS {
+            let position = Vec2::new(
+                self.rng.range(-world_limit, world_limit),
+                self.rng.range(-world_limit, world_limit),
+            );
+
+            let clear_of_asteroids = asteroids.iter().all(|other| {
+                let gap = other.radius + radius + self.config.min_spacing;
+                position.wrapped_distance_squared_to(other.position, world_limit) >= gap * gap
+            });
+            let clear_of_ships = ships.iter().all(|ship| {
+                let gap = SHIP_CLEARANCE + radius;
+                position.wrapped_distance_squared_to(*ship, world_limit) >= gap * gap
+            });
+
+            if clear_of_asteroids && clear_of_ships {
+                let id = self.next_id;
+                self.next_id += 1;
+
+                let mut asteroid = Asteroid::new(id, position, self.velocity(), radius);
+                asteroid.rotation = self.rng.range(-PI, PI);
+                asteroid.angular_velocity = self.rng.range(-self.config.max_spin, self.config.max_spin);
+                return Some(asteroid);
+            }
+        }
+
+        None
+    }
+
+    fn radius(&mut self) -> f32 {
+        let range = self.config.max_radius - self.config.min_radius;
+        self.config.min_radius + range * self.rng.next_f32().powf(self.config.size_bias)
+    }
+
+    fn velocity(&mut self) -> Vec2 {
+        let angle = self.rng.range(0.0, TAU);
+        let speed = self.rng.range(self.config.min_speed, self.config.max_speed);
+        Vec2::new(angle.cos() * speed, angle.sin() * speed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_field() {
+        let a = AsteroidField::new(99, FieldConfig::default()).generate(WORLD_LIMIT);
+        let b = AsteroidField::new(99, FieldConfig::default()).generate(WORLD_LIMIT);
+
+        assert_eq!(a.len(), ASTEROID_COUNT);
+        for (a, b) in a.iter().zip(&b) {
+            assert_eq!((a.id, a.position, a.velocity, a.radius), (b.id, b.position, b.velocity, b.radius));
+        }
+    }
+
+    #[test]
+    fn test_different_seed_different_field() {
+        let a = AsteroidField::new(1, FieldConfig::default()).generate(WORLD_LIMIT);
+        let b = AsteroidField::new(2, FieldConfig::default()).generate(WORLD_LIMIT);
+
+        assert_ne!(a[0].position, b[0].position);
+    }
+
+    #[test]
+    fn test_field_respects_config() {
+        let config = FieldConfig::default();
+        let asteroids = AsteroidField::new(5, config.clone()).generate(WORLD_LIMIT);
+
+        for (i, asteroid) in asteroids.iter().enumerate() {
+            assert!(asteroid.radius >= config.min_radius && asteroid.radius <= config.max_radius);
+            assert!(asteroid.position.x.abs() <= WORLD_LIMIT && asteroid.position.y.abs() <= WORLD_LIMIT);
+
+            let speed = asteroid.velocity.length();
+            assert!(speed >= config.min_speed - 0.01 && speed <= config.max_speed + 0.01);
+            assert!(asteroid.angular_velocity.abs() <= config.max_spin);
+
+            for other in &asteroids[i + 1..] {
+                let distance = asteroid.position.wrapped_distance_to(other.position, WORLD_LIMIT);
+                assert!(distance >= asteroid.radius + other.radius + config.min_spacing);
+            }
+        }
+    }
+
+    #[test]
+    fn test_maintain_tops_up_away_from_ships() {
+        let mut field = AsteroidField::new(3, FieldConfig::default());
+        let mut asteroids = field.generate(WORLD_LIMIT);
+        asteroids.truncate(ASTEROID_COUNT - 3);
+
+        let ships = [Vec2::ZERO, Vec2::new(1000.0, -1000.0)];
+        let added = field.maintain(&mut asteroids, &ships, WORLD_LIMIT);
+
+        assert_eq!(added.len(), 3);
+        assert_eq!(asteroids.len(), ASTEROID_COUNT);
+        for asteroid in &asteroids[ASTEROID_COUNT - 3..] {
+            assert!(added.contains(&asteroid.id));
+            assert!(asteroid.id as usize > ASTEROID_COUNT, "IDs are never reused");
+            for ship in &ships {
+                assert!(asteroid.position.wrapped_distance_to(*ship, WORLD_LIMIT) >= SHIP_CLEARANCE);
+            }
+        }
+
+        // Already full: nothing to do
+        assert!(field.maintain(&mut asteroids, &ships, WORLD_LIMIT).is_empty());
+    }
+
+    #[test]
+    fn test_overcrowded_field_gives_up() {
+        let config = FieldConfig {
+            count: 10_000,
+            ..FieldConfig::default()
+        };
+        let asteroids = AsteroidField::new(8, config).generate(500.0);
+
+        assert!(asteroids.len() < 10_000);
+        assert!(!asteroids.is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use std::{fs, io, path::Path};
+use std::time::{Duration, Instant};
+
+use super::names::{is_blocked, normalize, words};
+use super::rate_limit::TokenBucket;
+
+/// Longest chat message relayed (characters, longer ones are cut)
+pub const MAX_CHAT_LENGTH: usize = 200;
+
+/// Messages a player can send in a burst
+const CHAT_BURST: u32 = 5;
+
+/// Time to earn back one message of the burst
+const CHAT_REFILL: Duration = Duration::from_secs(2);
+
+/// Filtered words shipped with the server (same list as for names)
+const DEFAULT_FILTERED_WORDS: &str = include_str!("blocked-names.txt");
+
+/// Masks filtered words in chat messages with `*`
+///
+/// Words are split like player names (see `names::words`), so "Scunthorpe"
+/// and "cockpit" are left alone.
+#[derive(Debug, Clone)]
+pub struct ChatFilter {
+    /// Lowercase words to mask where they appear as a whole word
+    words: Vec<String>,
+}
+
+impl ChatFilter {
+    /// Filter with the given word list (one word per line, `#` comments)
+    pub fn new(list: &str) -> Self {
+        let words = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(normalize)
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        Self { words }
+    }
+
+    /// Filter with the word list read from `path`
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(&fs::read_to_string(path)?))
+    }
+
+    /// Replace every filtered word (any case, plurals too) with `*`
+    pub fn censor(&self, text: &str) -> String {
+        let mut chars: Vec<char> = text.chars().collect();
+        for word in words(text) {
+            if is_blocked(&word.text, &self.words) {
+                chars[word.span].fill('*');
+            }
+        }
+        chars.into_iter().collect()
+    }
+}
+
+impl Default for ChatFilter {
+    fn default() -> Self {
+        Self::new(DEFAULT_FILTERED_WORDS)
+    }
+}
+
+/// Trim a chat message, strip control characters and cut it to length
+///
+/// Returns None if nothing is left to send.
+pub fn sanitize_chat(text: &str) -> Option<String> {
+    let text: String = text
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_CHAT_LENGTH)
+        .collect();
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Per-connection chat rate limit (token bucket)
+///
+/// Allows a burst of `CHAT_BURST` messages, then one every `CHAT_REFILL`.
+#[derive(Debug, Clone)]
+pub struct ChatLimiter(TokenBucket);
+
+impl ChatLimiter {
+    pub fn new(now: Instant) -> Self {
+        Self(TokenBucket::new(CHAT_BURST, CHAT_REFILL, now))
+    }
+
+    /// Spend one message; false if the player is sending too fast
+    pub fn try_send(&mut self, now: Instant) -> bool {
+        self.0.try_take(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_censor_masks_any_case() {
+        let filter = ChatFilter::new("# comment\nfrak\n");
+        assert_eq!(filter.censor("What the FRAK"), "What the ****");
+        assert_eq!(filter.censor("Frak, fraks!"), "****, *****!");
+        assert_eq!(filter.censor("f.r.a.k toasters"), "******* toasters");
+        assert_eq!(filter.censor("So say we all"), "So say we all");
+    }
+
+    #[test]
+    fn test_censor_folds_list_like_text() {
+        let filter = ChatFilter::new("FRAK\nGÖDS\n");
+        assert_eq!(filter.censor("frak it"), "**** it");
+        assert_eq!(filter.censor("by the GÖDS"), "by the ****");
+    }
+
+    #[test]
+    fn test_censor_leaves_harmless_words() {
+        let filter = ChatFilter::default();
+        for text in ["Greetings from Scunthorpe", "Back to the cockpit", "Reading Dickens"] {
+            assert_eq!(filter.censor(text), text);
+        }
+        assert_eq!(filter.censor("what a dick"), "what a ****");
+        assert_eq!(filter.censor("BigDick"), "Big****");
+    }
+
+    #[test]
+    fn test_sanitize_trims_and_cuts() {
+        assert_eq!(sanitize_chat("  hi\u{7}  "), Some("hi".to_string()));
+        assert_eq!(sanitize_chat(" \n "), None);
+        let long = "a".repeat(MAX_CHAT_LENGTH + 10);
+        assert_eq!(sanitize_chat(&long).map(|t| t.len()), Some(MAX_CHAT_LENGTH));
+    }
+
+    #[test]
+    fn test_limiter_allows_burst_then_refills() {
+        let start = Instant::now();
+        let mut limiter = ChatLimiter::new(start);
+        for _ in 0..CHAT_BURST {
+            assert!(limiter.try_send(start));
+        }
+        assert!(!limiter.try_send(start), "Burst used up");
+
+        assert!(!limiter.try_send(start + CHAT_REFILL / 2));
+        assert!(limiter.try_send(start + CHAT_REFILL));
+        assert!(!limiter.try_send(start + CHAT_REFILL));
+    }
+}
@@ -1,6 +1,12 @@
 use battlestar_shared::{
-    entities::{Asteroid, Color, Ship},
-    physics::{check_collision, Input, PhysicsConstants},
+    entities::{Asteroid, Color, DamageConstants, DamageSource, Pickup, PickupConstants, Ship},
+    input::sanitize_input,
+    physics::{
+        resolve_collision, resolve_polygon_collision, swept_circle_polygon_toi,
+        swept_circle_toi_wrapped, wrap_position, Body, Input, PhysicsConstants, ResponseConstants,
+        SpatialGrid,
+    },
+    network::{ModeKind, RoundResults, SERVER_TICK_MS},
     ClientInput, GameState, Vec2, DeltaState, ShipUpdate,
 };
 use rand::RngExt;
@@ -8,6 +14,28 @@ use std::collections::{HashMap, HashSet};
 
 use crate::constants::*;
 
+use super::asteroid_field::{AsteroidField, FieldConfig, DEFAULT_ASTEROID_SEED};
+use super::lag_compensation::{LagCompensationConfig, PositionHistory};
+use super::mode::{create_mode, GameMode, RoundClock};
+use super::pickup_spawner::PickupSpawner;
+use super::spawn::SpawnSelector;
+
+/// Broad phase cell size (about twice the largest ship/asteroid radius)
+const BROADPHASE_CELL_SIZE: f32 = 64.0;
+
+/// Ticks a ship stays invincible after (re)spawning
+const INVINCIBILITY_TICKS: u64 = (INVINCIBILITY_DURATION * 1000.0 / SERVER_TICK_MS as f32) as u64;
+
+/// Mixed into the game seed so pickups don't follow the asteroid sequence
+const PICKUP_SEED_SALT: u64 = 0x91C4_0B5E;
+
+/// A ship's hull reached zero this tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeathEvent {
+    pub ship_id: u32,
+    pub source: DamageSource,
+}
+
 /// Core game logic with optimized data structures
 ///
 /// Key improvements over old implementation:
@@ -24,6 +52,9 @@ pub struct Game {
     /// Asteroids in the game world
     pub asteroids: Vec<Asteroid>,
 
+    /// Generates the asteroids and replaces destroyed ones
+    pub field: AsteroidField,
+
     /// Game tick counter
     pub tick: u64,
 
@@ -35,23 +66,91 @@ pub struct Game {
 
     /// Full state broadcast interval (every N ticks)
     pub full_state_interval: u64,
+
+    /// Collision response settings (bounce vs destroy on contact)
+    pub response: ResponseConstants,
+
+    /// Collide with asteroid outlines instead of their bounding circles
+    pub polygon_hitboxes: bool,
+
+    /// Health, shield and damage settings
+    pub damage: DamageConstants,
+
+    /// Ships destroyed this tick
+    pub deaths: Vec<DeathEvent>,
+
+    /// Asteroids knocked off course this tick (need to be sent in delta)
+    pub asteroids_changed: HashSet<u32>,
+
+    /// Picks spawn and respawn points away from asteroids and other ships
+    pub spawn: SpawnSelector,
+
+    /// Pickups waiting to be collected
+    pub pickups: Vec<Pickup>,
+
+    /// Pickup radius, effect duration and strength
+    pub pickup_constants: PickupConstants,
+
+    /// Drops new pickups at an interval
+    pub pickup_spawner: PickupSpawner,
+
+    /// Pickups that appeared this tick (need to be sent in delta)
+    pub pickups_added: Vec<u32>,
+
+    /// Pickups collected this tick (need to be sent in delta)
+    pub pickups_collected: Vec<u32>,
+
+    /// Ships removed this tick (need to be sent in delta)
+    pub ships_removed: Vec<u32>,
+
+    /// Ships removed since the last tick (players leave between ticks)
+    departed: Vec<u32>,
+
+    /// Rules of the match: teams, friendly fire, scoring, win condition
+    pub mode: Box<dyn GameMode>,
+
+    /// Time left in the current round
+    pub round: RoundClock,
+
+    /// Whether a round is being played (the clock runs and kills score)
+    pub round_active: bool,
+
+    /// Results of the round that ended this tick
+    pub round_results: Option<RoundResults>,
+
+    /// Names players chose in their handshake (shown above their ship)
+    pub names: HashMap<u32, String>,
+
+    /// Ship positions over the last few ticks (hits are checked against the
+    /// past the shooter saw)
+    pub history: PositionHistory,
+
+    /// Rewind window for lag-compensated hits (change with `set_lag_compensation`)
+    lag_compensation: LagCompensationConfig,
+
+    /// Broad phase for ship vs asteroid checks (rebuilt every tick)
+    asteroid_grid: SpatialGrid<usize>,
+
+    /// Broad phase for ship vs ship bounces (rebuilt every tick)
+    ship_grid: SpatialGrid<u32>,
+
+    /// Ship positions at the end of the previous tick (start of the collision sweep)
+    previous_positions: HashMap<u32, Vec2>,
 }
 
 impl Game {
     pub fn new() -> Self {
+        Self::with_seed(DEFAULT_ASTEROID_SEED)
+    }
+
+    /// Create a game whose asteroid field is generated from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        let mut field = AsteroidField::new(seed, FieldConfig::default());
+
         Self {
             ships: HashMap::new(),
-            asteroids: vec![
-                Asteroid::new(1, Vec2::new(200.0, 100.0), Vec2::new(20.0, 15.0), 20.0),
-                Asteroid::new(2, Vec2::new(-150.0, -120.0), Vec2::new(-10.0, 25.0), 24.0),
-                Asteroid::new(3, Vec2::new(500.0, -400.0), Vec2::new(-15.0, 20.0), 18.0),
-                Asteroid::new(4, Vec2::new(-600.0, 300.0), Vec2::new(25.0, -10.0), 22.0),
-                Asteroid::new(5, Vec2::new(100.0, 600.0), Vec2::new(-20.0, -15.0), 16.0),
-                Asteroid::new(6, Vec2::new(400.0, 400.0), Vec2::new(10.0, -25.0), 20.0),
-                Asteroid::new(7, Vec2::new(-500.0, -500.0), Vec2::new(15.0, 15.0), 25.0),
-                Asteroid::new(8, Vec2::new(700.0, -100.0), Vec2::new(-10.0, 20.0), 19.0),
-                Asteroid::new(9, Vec2::new(-300.0, 700.0), Vec2::new(18.0, -12.0), 21.0),
-            ],
+            asteroids: field.generate(WORLD_LIMIT),
+            field,
             tick: 0,
             constants: PhysicsConstants::from_game_constants(
                 THRUST_ACCEL,
@@ -63,22 +162,69 @@ impl Game {
             ),
             ships_needing_color: HashSet::new(),
             full_state_interval: 100, // Full state every 5 seconds @ 20Hz
+            response: ResponseConstants::new(COLLISION_RESPONSE, RESTITUTION),
+            polygon_hitboxes: POLYGON_HITBOXES,
+            damage: DamageConstants::from_game_constants(
+                MAX_HEALTH,
+                MAX_SHIELD,
+                SHIELD_REGEN_RATE,
+                SHIELD_REGEN_DELAY,
+                IMPACT_DAMAGE_FACTOR,
+                MIN_IMPACT_SPEED,
+            ),
+            deaths: Vec::new(),
+            asteroids_changed: HashSet::new(),
+            spawn: SpawnSelector::default(),
+            pickups: Vec::new(),
+            pickup_constants: PickupConstants::from_game_constants(
+                PICKUP_RADIUS,
+                PICKUP_DURATION,
+                SPEED_BOOST,
+                RAPID_FIRE,
+            ),
+            pickup_spawner: PickupSpawner::new(seed ^ PICKUP_SEED_SALT),
+            pickups_added: Vec::new(),
+            pickups_collected: Vec::new(),
+            ships_removed: Vec::new(),
+            departed: Vec::new(),
+            mode: create_mode(ModeKind::FreeForAll),
+            round: RoundClock::default(),
+            round_active: true,
+            round_results: None,
+            names: HashMap::new(),
+            history: PositionHistory::new(LagCompensationConfig::default().history_ticks()),
+            lag_compensation: LagCompensationConfig::default(),
+            asteroid_grid: SpatialGrid::new(WORLD_LIMIT, BROADPHASE_CELL_SIZE),
+            ship_grid: SpatialGrid::new(WORLD_LIMIT, BROADPHASE_CELL_SIZE),
+            previous_positions: HashMap::new(),
         }
     }
 
     /// Spawn a new player ship
     ///
     /// Called when a player sends their first input.
-    /// Assigns a random color to the ship.
+    /// Joins the player to the game mode (which may put them on a team),
+    /// colors the ship by team (random without teams), names it after the
+    /// player and picks a safe spawn point.
     pub fn spawn_player(&mut self, id: u32) -> &Ship {
-        let mut rng = rand::rng();
-        let color = Color::new(
-            rng.random_range(0.3..1.0),
-            rng.random_range(0.3..1.0),
-            rng.random_range(0.3..1.0),
-        );
+        let team = self.mode.add_player(id);
+        let color = match team {
+            Some(team) => team.color(),
+            None => {
+                let mut rng = rand::rng();
+                Color::new(
+                    rng.random_range(0.3..1.0),
+                    rng.random_range(0.3..1.0),
+                    rng.random_range(0.3..1.0),
+                )
+            }
+        };
 
-        let ship = Ship::new(id, color);
+        let mut ship = Ship::new(id, color);
+        ship.team = team;
+        ship.name = self.names.get(&id).cloned().unwrap_or_default();
+        ship.position = self.spawn_point(id);
+        ship.restore(&self.damage);
         self.ships.insert(id, ship);
 
         // Mark ship as needing color in next delta
@@ -87,30 +233,54 @@ impl Game {
         &self.ships[&id]
     }
 
+    /// Pick a spawn point away from asteroids and every ship except `ship_id`
+    fn spawn_point(&self, ship_id: u32) -> Vec2 {
+        let others: Vec<Vec2> = self
+            .ships
+            .values()
+            .filter(|ship| ship.id != ship_id)
+            .map(|ship| ship.position)
+            .collect();
+
+        self.spawn.select(
+            &mut rand::rng(),
+            &self.asteroids,
+            &others,
+            self.constants.world_limit,
+        )
+    }
+
     /// Remove a player ship
     ///
     /// Called when a player disconnects.
     pub fn remove_player(&mut self, id: u32) {
-        self.ships.remove(&id);
+        if self.ships.remove(&id).is_some() {
+            self.departed.push(id);
+        }
+        self.mode.remove_player(id);
     }
 
     /// Apply player input to their ship
     ///
     /// Spawns ship if it doesn't exist (first input).
+    /// Non-finite input is ignored so it can't poison the ship's position.
     /// Uses shared physics engine from battlestar-shared.
     pub fn apply_input(&mut self, input: ClientInput, dt: f32) {
-        // Validate input (anti-cheat)
+        // Validate input (anti-cheat): NaN/infinite input is dropped, the rest clamped
         let mut game_input = Input::new(input.thrust, input.rotate);
-        game_input.clamp();
+        if sanitize_input(&mut game_input).is_err() {
+            return;
+        }
 
         // Spawn ship if doesn't exist
         if !self.ships.contains_key(&input.player_id) {
             self.spawn_player(input.player_id);
         }
 
-        // Apply input using shared physics
+        // Apply input using shared physics (with any speed boost)
         if let Some(ship) = self.ships.get_mut(&input.player_id) {
-            ship.apply_input(&game_input, dt, &self.constants);
+            let constants = ship.effective_constants(&self.constants, &self.pickup_constants);
+            ship.apply_input(&game_input, dt, &constants);
         }
     }
 
@@ -118,7 +288,15 @@ impl Game {
     ///
     /// - Updates all ships (drag, velocity integration, wrapping)
     /// - Updates all asteroids
-    /// - Checks collisions (ship vs asteroid)
+    /// - Checks collisions (ship vs asteroid, spatial grid broad phase,
+    ///   swept so fast ships can't tunnel through small asteroids)
+    /// - With collision response enabled, bounces ships off asteroids and
+    ///   each other and applies impact damage; otherwise contact destroys
+    /// - Regenerates shields, respawns ships whose hull reached zero
+    /// - Between rounds, holds ships still and skips collisions and damage
+    /// - Hands out pickups to ships flying over them, spawns new ones
+    /// - Tops the asteroid field back up to its configured count
+    /// - Ends the round once the mode has a winner or time is up
     /// - Increments tick counter
     /// - Tracks ships that respawned for delta updates
     pub fn tick(&mut self, dt: f32) {
@@ -126,10 +304,23 @@ impl Game {
 
         // Clear previous tick's tracking
         self.ships_needing_color.clear();
+        self.asteroids_changed.clear();
+        self.deaths.clear();
+        self.pickups_added.clear();
+        self.pickups_collected.clear();
+        self.ships_removed = std::mem::take(&mut self.departed);
+        self.round_results = None;
 
-        // Update all ships
+        // Update all ships (held in place between rounds)
         for ship in self.ships.values_mut() {
-            ship.update(dt, &self.constants);
+            if !self.round_active {
+                ship.velocity = Vec2::ZERO;
+                continue;
+            }
+            let constants = ship.effective_constants(&self.constants, &self.pickup_constants);
+            ship.update(dt, &constants);
+            ship.regenerate_shield(dt, &self.damage);
+            ship.tick_modifiers(dt);
         }
 
         // Update all asteroids
@@ -138,28 +329,321 @@ impl Game {
         }
 
         // Check collisions (ship vs asteroid)
-        // Calculate invincibility threshold (1 second at 20Hz = 20 ticks)
-        let invincibility_ticks = (INVINCIBILITY_DURATION * 20.0) as u64;
+
+        // Broad phase: bucket asteroids once so each ship only checks nearby ones
+        // instead of every asteroid (O(ships × asteroids))
+        self.asteroid_grid.clear();
+        let mut max_asteroid_step: f32 = 0.0;
+        for (index, asteroid) in self.asteroids.iter().enumerate() {
+            self.asteroid_grid.insert(index, asteroid.position, asteroid.radius);
+            max_asteroid_step = max_asteroid_step.max(asteroid.velocity.length() * dt);
+        }
+
+        let world_limit = self.constants.world_limit;
+        let ship_radius = self.constants.ship_radius;
+
+        // Damage is applied once all contacts are resolved: (ship, source, amount)
+        let mut hits: Vec<(u32, DamageSource, f32)> = Vec::new();
 
         for (ship_id, ship) in &mut self.ships {
-            // Skip collision check if ship is invincible
-            if ship.is_invincible(self.tick, invincibility_ticks) {
+            // Skip collision check if ship is invincible or held between rounds
+            if !self.round_active || ship.is_invincible(self.tick, INVINCIBILITY_TICKS) {
                 continue;
             }
 
-            for asteroid in &self.asteroids {
-                if check_collision(
-                    ship.position,
-                    self.constants.ship_radius,
-                    asteroid.position,
+            // Sweep from where the ship ended last tick (inputs move it too).
+            // At max_speed a ship covers 20 px per tick, more than a small
+            // asteroid's diameter, so an end-of-tick overlap check would miss hits.
+            let start = self.previous_positions.get(ship_id).copied().unwrap_or(ship.position);
+            let motion = start.wrapped_delta_to(ship.position, world_limit);
+
+            // Grid query around the middle of the path, wide enough for both sweeps
+            let midpoint = start + motion * 0.5;
+            let reach = ship_radius + motion.length() * 0.5 + max_asteroid_step;
+
+            for entry in self.asteroid_grid.candidates(midpoint, reach) {
+                let asteroid = &mut self.asteroids[entry.key];
+                let asteroid_motion = asteroid.velocity * dt;
+                let asteroid_start = asteroid.position - asteroid_motion;
+                let Some(mut toi) = swept_circle_toi_wrapped(
+                    start,
+                    motion,
+                    ship_radius,
+                    asteroid_start,
+                    asteroid_motion,
                     asteroid.radius,
-                ) {
-                    // Ship destroyed - respawn at center
-                    ship.respawn(self.tick);
+                    world_limit,
+                ) else {
+                    continue;
+                };
+
+                // The bounding circle was reached: find when (if) the outline is
+                let outline = self.polygon_hitboxes.then(|| asteroid.outline());
+                if let Some(outline) = &outline {
+                    let Some(polygon_toi) = swept_circle_polygon_toi(
+                        outline,
+                        asteroid_start.wrapped_delta_to(start, world_limit),
+                        motion - asteroid_motion,
+                        ship_radius,
+                        toi,
+                    ) else {
+                        continue;
+                    };
+                    toi = polygon_toi;
+                }
 
-                    // Mark ship as needing color in next delta (respawn)
-                    self.ships_needing_color.insert(*ship_id);
+                if !self.response.enabled {
+                    // No bounce: the ship passes through, damaged once as it
+                    // first touches (it was already inside at the start of the tick)
+                    if toi == 0.0 {
+                        continue;
+                    }
+                    let speed = (ship.velocity - asteroid.velocity).length();
+                    let source = DamageSource::Asteroid { speed };
+                    hits.push((*ship_id, source, self.damage.damage_for(&source)));
+                    break;
                 }
+
+                // Rewind to the point of contact so a fast ship bounces
+                // off the near side instead of being pushed out the far side
+                let mut contact = start + motion * toi;
+                wrap_position(&mut contact, world_limit);
+
+                let mut ship_body = Body::new(contact, ship.velocity, ship_radius);
+                let mut asteroid_body =
+                    Body::new(asteroid.position, asteroid.velocity, asteroid.radius);
+                let impact = match &outline {
+                    Some(outline) => resolve_polygon_collision(
+                        &mut ship_body,
+                        &mut asteroid_body,
+                        outline,
+                        self.response.restitution,
+                        world_limit,
+                    ),
+                    None => resolve_collision(
+                        &mut ship_body,
+                        &mut asteroid_body,
+                        self.response.restitution,
+                        world_limit,
+                    ),
+                };
+
+                ship.position = ship_body.position;
+                ship.velocity = ship_body.velocity;
+                asteroid.position = asteroid_body.position;
+                asteroid.velocity = asteroid_body.velocity;
+                self.asteroids_changed.insert(asteroid.id);
+
+                if let Some(impact) = impact {
+                    let source = DamageSource::Asteroid { speed: impact.speed };
+                    hits.push((*ship_id, source, self.damage.damage_for(&source)));
+                }
+                break;
+            }
+        }
+
+        if self.response.enabled && self.round_active {
+            self.resolve_ship_collisions(&mut hits);
+        }
+
+        for (ship_id, source, amount) in hits {
+            self.damage_ship(ship_id, source, amount);
+        }
+
+        if self.round_active {
+            self.collect_pickups();
+        }
+        if let Some(id) = self.pickup_spawner.update(&mut self.pickups, &self.asteroids, world_limit) {
+            self.pickups_added.push(id);
+        }
+
+        // Replace destroyed asteroids (sent to clients like bounced ones)
+        let ship_positions: Vec<Vec2> = self.ships.values().map(|ship| ship.position).collect();
+        let added = self.field.maintain(&mut self.asteroids, &ship_positions, self.constants.world_limit);
+        self.asteroids_changed.extend(added);
+
+        self.previous_positions.clear();
+        self.previous_positions
+            .extend(self.ships.iter().map(|(id, ship)| (*id, ship.position)));
+        self.history
+            .record(self.tick, self.ships.iter().map(|(id, ship)| (*id, ship.position)));
+
+        if self.round_active {
+            let time_up = self.round.tick();
+            let winner = match self.mode.winner() {
+                Some(winner) => Some(winner),
+                None if time_up => Some(self.mode.winner_at_time_limit()),
+                None => None,
+            };
+            if let Some(winner) = winner {
+                self.round_results = Some(self.mode.results(winner));
+                self.round_active = false;
+            }
+        }
+    }
+
+    /// Start a new round
+    ///
+    /// Scores and the clock are reset and every ship respawns at a safe
+    /// point with full hull and shield.
+    pub fn start_round(&mut self) {
+        self.mode.reset_scores();
+        self.round.restart();
+        self.round_active = true;
+
+        let mut ids: Vec<u32> = self.ships.keys().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            let position = self.spawn_point(id);
+            let ship = self.ships.get_mut(&id).expect("ship listed above");
+            ship.respawn_at(position, self.tick);
+            ship.restore(&self.damage);
+            self.ships_needing_color.insert(id);
+        }
+        self.previous_positions.clear();
+        self.history.clear();
+    }
+
+    /// Change the rewind window (the history is emptied and resized)
+    pub fn set_lag_compensation(&mut self, config: LagCompensationConfig) {
+        self.lag_compensation = config;
+        self.history = PositionHistory::new(config.history_ticks());
+    }
+
+    /// First ship hit by an instant shot, as the shooter saw the world
+    ///
+    /// The shot leaves `origin` (the shooter's current position) along
+    /// `direction` for `range`. Targets are rewound to the shooter's view
+    /// tick, estimated from its round trip (`rtt_ms`); the shooter and ships
+    /// that left the game can't be hit.
+    pub fn hit_scan(&self, shooter_id: u32, origin: Vec2, direction: Vec2, range: f32, rtt_ms: f64) -> Option<u32> {
+        let world_limit = self.constants.world_limit;
+        let view_tick = self.lag_compensation.view_tick(self.tick, rtt_ms);
+        let motion = direction.normalized() * range;
+
+        self.history
+            .positions_at(view_tick, world_limit)
+            .into_iter()
+            .filter(|(id, _)| *id != shooter_id && self.ships.contains_key(id))
+            .filter_map(|(id, position)| {
+                swept_circle_toi_wrapped(
+                    origin,
+                    motion,
+                    0.0,
+                    position,
+                    Vec2::ZERO,
+                    self.constants.ship_radius,
+                    world_limit,
+                )
+                .map(|toi| (id, toi))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    /// Give each pickup to the first ship overlapping it
+    fn collect_pickups(&mut self) {
+        let world_limit = self.constants.world_limit;
+        let reach = self.constants.ship_radius + self.pickup_constants.radius;
+
+        // Sorted so a tie goes to the lowest ID, not to HashMap order
+        let mut ids: Vec<u32> = self.ships.keys().copied().collect();
+        ids.sort_unstable();
+
+        self.pickups.retain(|pickup| {
+            // Contested pickups go to the nearest ship
+            let nearest = ids
+                .iter()
+                .map(|id| (*id, self.ships[id].position.wrapped_distance_squared_to(pickup.position, world_limit)))
+                .filter(|(_, distance_sq)| *distance_sq < reach * reach)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            let Some((id, _)) = nearest else {
+                return true;
+            };
+            let ship = self.ships.get_mut(&id).expect("ship listed above");
+            ship.collect(pickup.kind, &self.pickup_constants, &self.damage);
+            self.pickups_collected.push(pickup.id);
+            false
+        });
+    }
+
+    /// Damage a ship from any source (impacts, projectiles)
+    ///
+    /// Shields absorb damage first, and the game mode can rule out damage
+    /// between teammates. Returns true if the ship was destroyed; it is then
+    /// respawned, a `DeathEvent` recorded and the kill scored.
+    pub fn apply_damage(&mut self, ship_id: u32, source: DamageSource) -> bool {
+        let amount = self.damage.damage_for(&source);
+        self.damage_ship(ship_id, source, amount)
+    }
+
+    fn damage_ship(&mut self, ship_id: u32, source: DamageSource, amount: f32) -> bool {
+        if let Some(attacker) = source.attacker() {
+            if attacker != ship_id && !self.mode.allows_damage(attacker, ship_id) {
+                return false;
+            }
+        }
+        let Some(ship) = self.ships.get_mut(&ship_id) else {
+            return false;
+        };
+        // Also skips a ship already destroyed by an earlier hit this tick
+        if ship.is_invincible(self.tick, INVINCIBILITY_TICKS) {
+            return false;
+        }
+        if !ship.take_damage(amount, &self.damage) {
+            return false;
+        }
+
+        // Ship destroyed - respawn somewhere safe with full hull and shield
+        let position = self.spawn_point(ship_id);
+        let ship = self.ships.get_mut(&ship_id).expect("ship checked above");
+        ship.respawn_at(position, self.tick);
+        ship.restore(&self.damage);
+        self.deaths.push(DeathEvent { ship_id, source });
+        if self.round_active {
+            self.mode.record_death(ship_id, source.attacker());
+        }
+
+        // Mark ship as needing color in next delta (respawn)
+        self.ships_needing_color.insert(ship_id);
+        true
+    }
+
+    /// Bounce overlapping ships off each other
+    ///
+    /// Invincible ships pass through, like they do with asteroids.
+    /// Both ships take damage from the impact.
+    fn resolve_ship_collisions(&mut self, hits: &mut Vec<(u32, DamageSource, f32)>) {
+        let world_limit = self.constants.world_limit;
+        let ship_radius = self.constants.ship_radius;
+
+        self.ship_grid.clear();
+        for ship in self.ships.values() {
+            if !ship.is_invincible(self.tick, INVINCIBILITY_TICKS) {
+                self.ship_grid.insert(ship.id, ship.position, ship_radius);
+            }
+        }
+
+        for (id_a, id_b) in self.ship_grid.query_pairs() {
+            let (Some(a), Some(b)) = (self.ships.get(&id_a), self.ships.get(&id_b)) else {
+                continue;
+            };
+
+            let mut body_a = Body::new(a.position, a.velocity, ship_radius);
+            let mut body_b = Body::new(b.position, b.velocity, ship_radius);
+            let Some(impact) =
+                resolve_collision(&mut body_a, &mut body_b, self.response.restitution, world_limit)
+            else {
+                continue;
+            };
+
+            for (id, other_id, body) in [(id_a, id_b, body_a), (id_b, id_a, body_b)] {
+                if let Some(ship) = self.ships.get_mut(&id) {
+                    ship.position = body.position;
+                    ship.velocity = body.velocity;
+                }
+                let source = DamageSource::Ship { other_id, speed: impact.speed };
+                hits.push((id, source, self.damage.damage_for(&source)));
             }
         }
     }
@@ -171,6 +655,7 @@ impl Game {
         GameState {
             ships: self.ships.values().cloned().collect(),
             asteroids: self.asteroids.clone(),
+            pickups: self.pickups.clone(),
             tick: self.tick,
         }
     }
@@ -187,9 +672,6 @@ impl Game {
 
         let mut delta = DeltaState::new(self.tick, is_full_state);
 
-        // Calculate invincibility threshold
-        let invincibility_ticks = (INVINCIBILITY_DURATION * 20.0) as u64;
-
         // If full state, include asteroids in the GameState
         // (asteroids rarely change, so we only send them on full state)
         if is_full_state {
@@ -201,13 +683,35 @@ impl Game {
         for ship in self.ships.values() {
             let needs_color = self.ships_needing_color.contains(&ship.id) || is_full_state;
             let update = if needs_color {
-                ShipUpdate::with_color(ship, self.tick, invincibility_ticks)
+                ShipUpdate::with_color(ship, self.tick, INVINCIBILITY_TICKS)
             } else {
-                ShipUpdate::without_color(ship, self.tick, invincibility_ticks)
+                ShipUpdate::without_color(ship, self.tick, INVINCIBILITY_TICKS)
             };
             delta.add_ship_update(update);
         }
 
+        // Asteroids knocked off course (the rest move predictably client-side)
+        for asteroid in &self.asteroids {
+            if self.asteroids_changed.contains(&asteroid.id) {
+                delta.add_asteroid_update(asteroid.clone());
+            }
+        }
+
+        // Pickups appearing and disappearing
+        for pickup in &self.pickups {
+            if self.pickups_added.contains(&pickup.id) {
+                delta.add_pickup(pickup.clone());
+            }
+        }
+        for &id in &self.pickups_collected {
+            delta.add_removed_pickup(id);
+        }
+
+        // Ships of players who left
+        for &id in &self.ships_removed {
+            delta.add_removed_ship(id);
+        }
+
         delta
     }
 }
@@ -221,15 +725,46 @@ impl Default for Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::SpawnZone;
+    use battlestar_shared::entities::{PickupKind, Team};
+    use battlestar_shared::network::Winner;
+    use battlestar_shared::physics::polygon_circle_contact;
+    use std::time::Duration;
 
     #[test]
     fn test_game_creation() {
         let game = Game::new();
         assert_eq!(game.ships.len(), 0);
-        assert_eq!(game.asteroids.len(), 9);
+        assert_eq!(game.asteroids.len(), ASTEROID_COUNT);
         assert_eq!(game.tick, 0);
     }
 
+    #[test]
+    fn test_same_seed_same_asteroids() {
+        let a = Game::with_seed(1234);
+        let b = Game::with_seed(1234);
+
+        let positions = |game: &Game| game.asteroids.iter().map(|a| a.position).collect::<Vec<_>>();
+        assert_eq!(positions(&a), positions(&b));
+        assert_ne!(positions(&a), positions(&Game::with_seed(4321)));
+    }
+
+    #[test]
+    fn test_destroyed_asteroids_are_replaced() {
+        let mut game = Game::new();
+        let removed = game.asteroids.pop().unwrap();
+
+        game.tick(0.05);
+
+        assert_eq!(game.asteroids.len(), ASTEROID_COUNT);
+        let replacement = game.asteroids.last().unwrap();
+        assert_ne!(replacement.id, removed.id);
+
+        // New asteroid reaches clients in the next delta
+        let delta = game.to_delta_state();
+        assert!(delta.changed_asteroids.iter().any(|a| a.id == replacement.id));
+    }
+
     #[test]
     fn test_spawn_player() {
         let mut game = Game::new();
@@ -240,7 +775,41 @@ mod tests {
 
         let ship = &game.ships[&1];
         assert_eq!(ship.id, 1);
-        assert_eq!(ship.position, Vec2::ZERO);
+
+        // Spawned clear of every asteroid
+        for asteroid in &game.asteroids {
+            let distance = ship.position.wrapped_distance_to(asteroid.position, WORLD_LIMIT);
+            assert!(distance > asteroid.radius + game.constants.ship_radius);
+        }
+    }
+
+    #[test]
+    fn test_spawned_ship_carries_player_name() {
+        let mut game = Game::new();
+        game.names.insert(1, "Starbuck".to_string());
+        game.spawn_player(1);
+        game.spawn_player(2);
+
+        assert_eq!(game.ships[&1].name, "Starbuck");
+        assert_eq!(game.ships[&2].name, "");
+
+        let delta = game.to_delta_state();
+        let update = delta.changed_ships.iter().find(|u| u.id == 1).unwrap();
+        assert_eq!(update.name.as_deref(), Some("Starbuck"));
+    }
+
+    #[test]
+    fn test_spawn_player_away_from_other_ships() {
+        let mut game = Game::new();
+        game.asteroids.clear();
+        // Only room for the spawn zone's two ends
+        game.spawn.zones = vec![SpawnZone::new(Vec2::ZERO, Vec2::new(500.0, 0.0))];
+
+        game.spawn_player(1);
+        game.ships.get_mut(&1).unwrap().position = Vec2::new(-500.0, 0.0);
+        game.spawn_player(2);
+
+        assert!(game.ships[&2].position.x > 200.0, "Got {:?}", game.ships[&2].position);
     }
 
     #[test]
@@ -320,17 +889,560 @@ mod tests {
     fn test_collision_respawns_ship() {
         let mut game = Game::new();
         game.spawn_player(1);
+        game.tick(0.05);
 
-        // Position ship directly on asteroid
+        // Ram an asteroid with no shield and almost no hull left
         let ship = game.ships.get_mut(&1).unwrap();
         ship.position = game.asteroids[0].position;
+        ship.velocity = Vec2::new(300.0, 0.0);
+        ship.shield = 0.0;
+        ship.health = 1.0;
 
         game.tick(0.05);
 
-        // Ship should be respawned at origin
+        // Ship should be respawned away from the asteroid
         let ship = &game.ships[&1];
-        assert_eq!(ship.position, Vec2::ZERO);
+        assert_eq!(ship.respawn_tick, Some(game.tick));
         assert_eq!(ship.velocity, Vec2::ZERO);
+        assert!(ship.position.wrapped_distance_to(game.asteroids[0].position, WORLD_LIMIT) > 100.0);
+        assert_eq!(game.deaths.len(), 1);
+    }
+
+    #[test]
+    fn test_collision_across_wrap_seam() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+
+        // Asteroid just inside the right edge, ship just inside the left edge
+        keep_one_asteroid(&mut game);
+        game.asteroids[0].position = Vec2::new(WORLD_LIMIT - 5.0, 0.0);
+        game.asteroids[0].velocity = Vec2::ZERO;
+        game.ships.get_mut(&1).unwrap().position = Vec2::new(-WORLD_LIMIT + 100.0, 0.0);
+        game.tick(0.05);
+
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(-WORLD_LIMIT + 5.0, 0.0);
+        ship.velocity = Vec2::new(-300.0, 0.0);
+        ship.shield = 0.0;
+        ship.health = 1.0;
+
+        game.tick(0.05);
+
+        // 10 px apart the short way round: ship is destroyed and respawned
+        assert_eq!(game.deaths.len(), 1);
+        assert_eq!(game.ships[&1].respawn_tick, Some(game.tick));
+    }
+
+    #[test]
+    fn test_fast_ship_cannot_tunnel_through_asteroid() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+        keep_one_asteroid(&mut game);
+        game.asteroids[0].position = Vec2::new(1000.0, 0.0);
+        game.asteroids[0].velocity = Vec2::ZERO;
+        game.asteroids[0].radius = 5.0;
+
+        // Establish the sweep start left of the asteroid
+        game.ships.get_mut(&1).unwrap().position = Vec2::new(900.0, 0.0);
+        game.tick(0.05);
+        assert_eq!(game.ships[&1].position, Vec2::new(900.0, 0.0));
+
+        // Next tick ends 100+ px past it: no overlap at either end
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(1100.0, 0.0);
+        ship.velocity = Vec2::new(300.0, 0.0);
+        ship.shield = 0.0;
+        ship.health = 1.0;
+        game.tick(0.05);
+
+        assert_eq!(game.deaths.len(), 1, "Ship should have hit the asteroid");
+        assert_eq!(game.ships[&1].respawn_tick, Some(game.tick));
+    }
+
+    /// Shrink the field to its first asteroid (and stop it being topped up)
+    fn keep_one_asteroid(game: &mut Game) {
+        game.asteroids.truncate(1);
+        game.field.config.count = 1;
+    }
+
+    /// Game with one heavy stationary asteroid at (150, 0) and ship 1 at the origin
+    ///
+    /// Hitboxes are round so contact distances are easy to work out.
+    fn bounce_setup() -> Game {
+        let mut game = Game::new();
+        game.response.enabled = true;
+        game.polygon_hitboxes = false;
+        game.spawn_player(1);
+        game.ships.get_mut(&1).unwrap().position = Vec2::ZERO;
+        keep_one_asteroid(&mut game);
+        game.asteroids[0].position = Vec2::new(150.0, 0.0);
+        game.asteroids[0].velocity = Vec2::ZERO;
+        game.asteroids[0].radius = 50.0; // Heavier than a ship so it bounces back
+        game.asteroids[0].rotation = 0.0;
+        game.asteroids[0].angular_velocity = 0.0;
+        game
+    }
+
+    #[test]
+    fn test_ship_bounces_off_asteroid_outline() {
+        let mut game = bounce_setup();
+        game.polygon_hitboxes = true;
+
+        game.tick(0.05);
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(100.0, 0.0); // Inside the bounding circle
+        ship.velocity = Vec2::new(100.0, 0.0);
+        game.tick(0.05);
+
+        let ship = &game.ships[&1];
+        assert!(ship.velocity.x < 0.0, "Ship should bounce back, got {:?}", ship.velocity);
+
+        // Pushed clear of the outline, not the bounding circle
+        let asteroid = &game.asteroids[0];
+        let offset = asteroid.position.wrapped_delta_to(ship.position, WORLD_LIMIT);
+        let overlap = polygon_circle_contact(&asteroid.outline(), offset, game.constants.ship_radius);
+        assert!(overlap.is_none_or(|contact| contact.depth < 0.1), "Still overlapping: {:?}", overlap);
+    }
+
+    #[test]
+    fn test_ship_slips_past_outline_inside_bounding_circle() {
+        let mut game = bounce_setup();
+        game.polygon_hitboxes = true;
+        let ship_radius = game.constants.ship_radius;
+
+        // Fly just above the outline's highest point
+        let top = game.asteroids[0].outline().iter().map(|v| v.y).fold(f32::MIN, f32::max);
+        let y = top + ship_radius + 1.0;
+        assert!(y < game.asteroids[0].radius + ship_radius, "Path must cross the bounding circle");
+
+        game.ships.get_mut(&1).unwrap().position = Vec2::new(60.0, y);
+        game.tick(0.05);
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(240.0, y);
+        ship.velocity = Vec2::new(100.0, 0.0);
+        game.tick(0.05);
+
+        // Not deflected, and the asteroid wasn't touched
+        let ship = &game.ships[&1];
+        assert!(ship.position.x > 240.0 && ship.position.y == y, "Got {:?}", ship.position);
+        assert_eq!(ship.velocity.y, 0.0);
+        assert!(game.asteroids_changed.is_empty());
+    }
+
+    #[test]
+    fn test_ship_bounces_off_asteroid() {
+        let mut game = bounce_setup();
+        let asteroid_radius = game.asteroids[0].radius;
+        let contact = 150.0 - asteroid_radius - game.constants.ship_radius;
+
+        game.tick(0.05); // Sweep start
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(contact + 2.0, 0.0);
+        ship.velocity = Vec2::new(100.0, 0.0);
+        game.tick(0.05);
+
+        let ship = &game.ships[&1];
+        assert!(ship.velocity.x < 0.0, "Ship should bounce back, got {:?}", ship.velocity);
+        assert!(ship.position.x <= contact + 0.01, "Ship should be pushed out of the asteroid");
+        assert!(game.asteroids[0].velocity.x > 0.0, "Asteroid should be knocked away");
+
+        // Knocked asteroid is sent to clients in the delta
+        let delta = game.to_delta_state();
+        assert_eq!(delta.changed_asteroids.len(), 1);
+        assert!(game.ships_needing_color.is_empty(), "Soft bounce should not respawn");
+    }
+
+    #[test]
+    fn test_fast_ship_bounces_off_near_side() {
+        let mut game = bounce_setup();
+        game.asteroids[0].radius = 5.0; // Lighter than the ship: knocked away
+
+        game.tick(0.05);
+
+        // Ends the tick past the asteroid: swept contact still stops it on the near side
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(250.0, 0.0);
+        ship.velocity = Vec2::new(100.0, 0.0);
+        game.tick(0.05);
+
+        let ship = &game.ships[&1];
+        assert!(ship.position.x < 150.0, "Ship should stay on the near side, got {:?}", ship.position);
+        assert!(game.asteroids[0].velocity.x > 0.0, "Asteroid should be knocked away");
+    }
+
+    #[test]
+    fn test_impact_damages_shield_then_hull() {
+        let mut game = bounce_setup();
+        let contact = 150.0 - game.asteroids[0].radius - game.constants.ship_radius;
+
+        game.tick(0.05);
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(contact + 2.0, 0.0);
+        ship.velocity = Vec2::new(250.0, 0.0);
+        game.tick(0.05);
+
+        let ship = &game.ships[&1];
+        assert!(ship.shield < game.damage.max_shield, "Shield should absorb the impact");
+        assert!(ship.health > 0.0);
+        assert!(game.deaths.is_empty(), "Not enough damage to destroy the ship");
+        assert_eq!(ship.respawn_tick, None);
+    }
+
+    #[test]
+    fn test_slow_bump_without_response_only_takes_shield() {
+        let mut game = bounce_setup();
+        game.response.enabled = false;
+        let contact = 150.0 - game.asteroids[0].radius - game.constants.ship_radius;
+
+        game.tick(0.05);
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(contact + 2.0, 0.0);
+        ship.velocity = Vec2::new(100.0, 0.0);
+        game.tick(0.05);
+
+        let ship = &game.ships[&1];
+        assert!(ship.shield < game.damage.max_shield, "Shield should absorb the impact");
+        assert_eq!(ship.health, game.damage.max_health);
+        assert!(game.deaths.is_empty());
+
+        // Still inside the asteroid on the next tick: no second hit
+        let shield = ship.shield;
+        game.tick(0.05);
+        assert_eq!(game.ships[&1].shield, shield);
+    }
+
+    #[test]
+    fn test_fatal_impact_records_death_and_respawns() {
+        let mut game = bounce_setup();
+        let contact = 150.0 - game.asteroids[0].radius - game.constants.ship_radius;
+
+        game.tick(0.05);
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.position = Vec2::new(contact + 2.0, 0.0);
+        ship.velocity = Vec2::new(200.0, 0.0);
+        ship.shield = 0.0;
+        ship.health = 1.0;
+        game.tick(0.05);
+
+        assert_eq!(game.deaths.len(), 1);
+        assert_eq!(game.deaths[0].ship_id, 1);
+        assert!(matches!(game.deaths[0].source, DamageSource::Asteroid { .. }));
+
+        let ship = &game.ships[&1];
+        assert_eq!(ship.respawn_tick, Some(game.tick));
+        assert!(ship.position.wrapped_distance_to(game.asteroids[0].position, WORLD_LIMIT) > 100.0);
+        assert_eq!(ship.health, game.damage.max_health);
+        assert_eq!(ship.shield, game.damage.max_shield);
+        assert!(game.ships_needing_color.contains(&1));
+    }
+
+    #[test]
+    fn test_apply_damage_from_projectile() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+
+        let hit = DamageSource::Projectile { owner_id: 2, damage: 60.0 };
+        assert!(!game.apply_damage(1, hit));
+        assert_eq!(game.ships[&1].shield, 0.0);
+        assert_eq!(game.ships[&1].health, 90.0);
+
+        let fatal = DamageSource::Projectile { owner_id: 2, damage: 500.0 };
+        assert!(game.apply_damage(1, fatal));
+        assert_eq!(game.deaths, vec![DeathEvent { ship_id: 1, source: fatal }]);
+    }
+
+    /// Ships 1 (shooter, parked at the origin) and 2 (flying +y at 20 units
+    /// per tick along x = 300) with a synthetic history for ticks 1..=10
+    fn lag_compensated_game() -> Game {
+        let mut game = Game::new();
+        game.set_lag_compensation(LagCompensationConfig {
+            max_rewind: Duration::from_millis(200),
+            interpolation_delay: Duration::ZERO,
+        });
+        game.spawn_player(1);
+        game.spawn_player(2);
+        for tick in 1..=10 {
+            game.history.record(tick, [
+                (1, Vec2::ZERO),
+                (2, Vec2::new(300.0, tick as f32 * 20.0)),
+            ]);
+        }
+        game.tick = 10;
+        game
+    }
+
+    #[test]
+    fn test_hit_scan_rewinds_to_shooter_view() {
+        let game = lag_compensated_game();
+        let shoot_at = |y: f32, rtt_ms: f64| game.hit_scan(1, Vec2::new(0.0, y), Vec2::new(1.0, 0.0), 500.0, rtt_ms);
+
+        // No lag: the target is at y = 200
+        assert_eq!(shoot_at(200.0, 0.0), Some(2));
+        assert_eq!(shoot_at(160.0, 0.0), None);
+
+        // 100ms RTT: the update took 50ms to arrive and the shot 50ms to come
+        // back, so the shooter aimed at the target 2 ticks ago, at y = 160
+        assert_eq!(shoot_at(160.0, 100.0), Some(2));
+        assert_eq!(shoot_at(200.0, 100.0), None);
+
+        // Between ticks: 25ms RTT rewinds half a tick, to y = 190
+        assert_eq!(shoot_at(170.0, 0.0), None);
+        assert_eq!(shoot_at(170.0, 25.0), Some(2));
+    }
+
+    #[test]
+    fn test_hit_scan_rewind_is_capped() {
+        let game = lag_compensated_game();
+        let shoot_at = |y: f32, rtt_ms: f64| game.hit_scan(1, Vec2::new(0.0, y), Vec2::new(1.0, 0.0), 500.0, rtt_ms);
+
+        // 2s RTT would be 40 ticks back; only 200ms (4 ticks, y = 120) is rewound
+        assert_eq!(shoot_at(120.0, 2000.0), Some(2));
+        assert_eq!(shoot_at(40.0, 2000.0), None);
+    }
+
+    #[test]
+    fn test_hit_scan_ignores_shooter_and_departed_ships() {
+        let mut game = lag_compensated_game();
+
+        // Shooting from inside its own ship, away from the target
+        assert_eq!(game.hit_scan(1, Vec2::ZERO, Vec2::new(-1.0, 0.0), 500.0, 0.0), None);
+
+        game.remove_player(2);
+        assert_eq!(game.hit_scan(1, Vec2::new(0.0, 200.0), Vec2::new(1.0, 0.0), 500.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_teammates_cannot_damage_each_other() {
+        let mut game = Game::new();
+        game.mode = create_mode(ModeKind::TeamDeathmatch);
+        for id in 1..=3 {
+            game.spawn_player(id);
+        }
+        assert_eq!(game.ships[&1].team, Some(Team::Red));
+        assert_eq!(game.ships[&2].team, Some(Team::Blue));
+        assert_eq!(game.ships[&3].team, Some(Team::Red));
+        assert_eq!(game.ships[&2].color, Team::Blue.color());
+
+        let fatal = |owner_id| DamageSource::Projectile { owner_id, damage: 500.0 };
+        assert!(!game.apply_damage(3, fatal(1)), "Friendly fire is off");
+        assert_eq!(game.ships[&3].health, game.damage.max_health);
+
+        assert!(game.apply_damage(2, fatal(1)));
+        let results = game.mode.results(game.mode.winner_at_time_limit());
+        assert_eq!(results.winner, Winner::Team { team: Team::Red });
+    }
+
+    #[test]
+    fn test_round_ends_at_kill_limit() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+        game.spawn_player(2);
+
+        for _ in 0..KILL_LIMIT {
+            assert!(game.apply_damage(2, DamageSource::Projectile { owner_id: 1, damage: 500.0 }));
+            game.ships.get_mut(&2).unwrap().respawn_tick = None; // Skip spawn protection
+        }
+        game.tick(0.05);
+
+        let results = game.round_results.clone().expect("Kill limit reached");
+        assert_eq!(results.winner, Winner::Player { id: 1 });
+        assert_eq!(results.scores[0].kills, KILL_LIMIT);
+        assert!(!game.round_active);
+
+        game.tick(0.05);
+        assert!(game.round_results.is_none());
+    }
+
+    #[test]
+    fn test_ships_held_between_rounds() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+        game.round_active = false;
+        let ship = game.ships.get_mut(&1).unwrap();
+        ship.velocity = Vec2::new(100.0, 0.0);
+        ship.respawn_tick = None; // Skip spawn protection
+        let position = ship.position;
+
+        // Parked on an asteroid: no contact outside a round
+        game.asteroids[0].position = position;
+        game.asteroids[0].velocity = Vec2::ZERO;
+        game.tick(0.05);
+
+        assert_eq!(game.ships[&1].position, position);
+        assert_eq!(game.ships[&1].velocity, Vec2::ZERO);
+        assert!(game.deaths.is_empty());
+    }
+
+    #[test]
+    fn test_start_round_resets_scores_and_respawns() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+        game.spawn_player(2);
+        game.round_active = false;
+
+        // Kills between rounds don't count
+        game.apply_damage(2, DamageSource::Projectile { owner_id: 1, damage: 500.0 });
+        assert_eq!(game.mode.winner_at_time_limit(), Winner::Draw);
+
+        game.round.elapsed_ticks = 100;
+        game.ships.get_mut(&1).unwrap().health = 1.0;
+        game.start_round();
+
+        assert!(game.round_active);
+        assert_eq!(game.round.elapsed_ticks, 0);
+        assert_eq!(game.ships[&1].health, game.damage.max_health);
+        assert!(game.ships_needing_color.contains(&1), "Every ship respawns");
+        assert!(game.ships_needing_color.contains(&2));
+    }
+
+    #[test]
+    fn test_round_ends_at_time_limit() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+        game.round = RoundClock::new(0.1);
+
+        game.tick(0.05);
+        assert!(game.round_results.is_none());
+        game.tick(0.05);
+
+        assert_eq!(game.round_results.map(|r| r.winner), Some(Winner::Player { id: 1 }));
+    }
+
+    #[test]
+    fn test_ships_bounce_off_each_other() {
+        let mut game = bounce_setup();
+        game.asteroids.clear();
+        game.spawn_player(2);
+        let radius = game.constants.ship_radius;
+
+        let a = game.ships.get_mut(&1).unwrap();
+        a.position = Vec2::new(-radius + 1.0, 500.0);
+        a.velocity = Vec2::new(50.0, 0.0);
+        let b = game.ships.get_mut(&2).unwrap();
+        b.position = Vec2::new(radius - 1.0, 500.0);
+        b.velocity = Vec2::new(-50.0, 0.0);
+
+        game.tick(0.05);
+
+        let (a, b) = (&game.ships[&1], &game.ships[&2]);
+        assert!(a.velocity.x < 0.0 && b.velocity.x > 0.0, "Ships should move apart");
+        assert!(a.position.distance_to(b.position) >= radius * 2.0 - 0.01);
+    }
+
+    #[test]
+    fn test_ships_pass_through_when_response_disabled() {
+        let mut game = bounce_setup();
+        game.response.enabled = false;
+        game.asteroids.clear();
+        game.spawn_player(2);
+
+        game.ships.get_mut(&1).unwrap().velocity = Vec2::new(50.0, 0.0);
+        game.ships.get_mut(&2).unwrap().velocity = Vec2::new(-50.0, 0.0);
+        game.tick(0.05);
+
+        assert!(game.ships[&1].velocity.x > 0.0);
+        assert!(game.ships[&2].velocity.x < 0.0);
+    }
+
+    #[test]
+    fn test_ship_collects_pickup() {
+        let mut game = Game::new();
+        game.asteroids.clear();
+        game.field.config.count = 0;
+        game.spawn_player(1);
+        let position = game.ships[&1].position;
+        game.pickups.push(Pickup::new(5, PickupKind::SpeedBoost, position + Vec2::new(20.0, 0.0)));
+
+        game.tick(0.05);
+
+        assert!(game.pickups.is_empty());
+        assert!(game.ships[&1].has_modifier(PickupKind::SpeedBoost));
+        assert_eq!(game.to_delta_state().removed_pickup_ids, vec![5]);
+    }
+
+    #[test]
+    fn test_contested_pickup_goes_to_nearest_ship() {
+        let mut game = Game::new();
+        game.asteroids.clear();
+        game.field.config.count = 0;
+        game.spawn_player(1);
+        game.spawn_player(2);
+        game.ships.get_mut(&1).unwrap().position = Vec2::new(-35.0, 0.0);
+        game.ships.get_mut(&2).unwrap().position = Vec2::new(25.0, 0.0);
+        game.pickups.push(Pickup::new(5, PickupKind::SpeedBoost, Vec2::new(0.0, 0.0)));
+
+        game.tick(0.05);
+
+        assert!(!game.ships[&1].has_modifier(PickupKind::SpeedBoost));
+        assert!(game.ships[&2].has_modifier(PickupKind::SpeedBoost));
+    }
+
+    #[test]
+    fn test_pickup_tie_goes_to_lowest_id() {
+        for _ in 0..10 {
+            let mut game = Game::new();
+            game.asteroids.clear();
+            game.field.config.count = 0;
+            game.spawn_player(2);
+            game.spawn_player(1);
+            game.ships.get_mut(&1).unwrap().position = Vec2::new(-30.0, 0.0);
+            game.ships.get_mut(&2).unwrap().position = Vec2::new(30.0, 0.0);
+            game.pickups.push(Pickup::new(5, PickupKind::SpeedBoost, Vec2::new(0.0, 0.0)));
+
+            game.tick(0.05);
+
+            assert!(game.ships[&1].has_modifier(PickupKind::SpeedBoost));
+            assert!(!game.ships[&2].has_modifier(PickupKind::SpeedBoost));
+        }
+    }
+
+    #[test]
+    fn test_removed_ship_sent_in_next_delta() {
+        let mut game = Game::new();
+        game.spawn_player(1);
+        game.tick(0.05);
+
+        game.remove_player(1);
+        game.tick(0.05);
+        assert_eq!(game.to_delta_state().removed_ship_ids, vec![1]);
+
+        game.tick(0.05);
+        assert!(game.to_delta_state().removed_ship_ids.is_empty());
+    }
+
+    #[test]
+    fn test_speed_boost_raises_top_speed() {
+        let mut game = Game::new();
+        game.asteroids.clear();
+        game.field.config.count = 0;
+        let thrust = |game: &mut Game| {
+            for _ in 0..100 {
+                game.apply_input(ClientInput { player_id: 1, thrust: 1.0, rotate: 0.0 }, 0.05);
+                game.tick(0.05);
+            }
+            game.ships[&1].velocity.length()
+        };
+
+        let normal_speed = thrust(&mut game);
+        let pickups = game.pickup_constants;
+        let damage = game.damage;
+        game.ships.get_mut(&1).unwrap().collect(PickupKind::SpeedBoost, &pickups, &damage);
+        game.ships.get_mut(&1).unwrap().modifiers[0].remaining = 1000.0;
+        let boosted_speed = thrust(&mut game);
+
+        assert!(boosted_speed > normal_speed * 1.2, "{} vs {}", boosted_speed, normal_speed);
+    }
+
+    #[test]
+    fn test_pickups_spawn_and_reach_clients() {
+        let mut game = Game::new();
+        game.pickup_spawner.interval = 1;
+
+        game.tick(0.05);
+
+        assert_eq!(game.pickups.len(), 1);
+        assert_eq!(game.to_delta_state().added_pickups.len(), 1);
+        assert_eq!(game.to_network_state().pickups.len(), 1);
     }
 
     #[test]
@@ -342,7 +1454,7 @@ mod tests {
         let network_state = game.to_network_state();
 
         assert_eq!(network_state.ships.len(), 2);
-        assert_eq!(network_state.asteroids.len(), 2);
+        assert_eq!(network_state.asteroids.len(), ASTEROID_COUNT);
         assert_eq!(network_state.tick, 0);
     }
 
@@ -361,4 +1473,30 @@ mod tests {
         // The physics validation happens in apply_input
         assert!(game.ships.contains_key(&1));
     }
+
+    #[test]
+    fn test_non_finite_input_is_ignored() {
+        let mut game = Game::new();
+        game.apply_input(
+            ClientInput {
+                player_id: 1,
+                thrust: 1.0,
+                rotate: 0.0,
+            },
+            0.05,
+        );
+
+        game.apply_input(
+            ClientInput {
+                player_id: 1,
+                thrust: f32::NAN,
+                rotate: f32::INFINITY,
+            },
+            0.05,
+        );
+
+        let ship = &game.ships[&1];
+        assert!(ship.position.x.is_finite() && ship.position.y.is_finite());
+        assert!(ship.rotation.is_finite());
+    }
 }
@@ -0,0 +1,184 @@
+use battlestar_shared::network::SERVER_TICK_MS;
+use battlestar_shared::Vec2;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// How far back hits are evaluated for laggy shooters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LagCompensationConfig {
+    /// Furthest a target can be rewound; higher pings are only compensated
+    /// up to this (and the history keeps no more than that)
+    pub max_rewind: Duration,
+
+    /// How far behind the latest update clients draw remote ships
+    pub interpolation_delay: Duration,
+}
+
+impl Default for LagCompensationConfig {
+    fn default() -> Self {
+        Self {
+            max_rewind: Duration::from_millis(250),
+            interpolation_delay: Duration::from_millis(SERVER_TICK_MS),
+        }
+    }
+}
+
+impl LagCompensationConfig {
+    /// Ticks of history needed to rewind `max_rewind` (plus one to
+    /// interpolate from)
+    pub fn history_ticks(&self) -> usize {
+        (self.max_rewind.as_millis() as u64).div_ceil(SERVER_TICK_MS) as usize + 1
+    }
+
+    /// Server tick the shooter was looking at when it fired
+    ///
+    /// By the time the shot reaches the server, the update the shooter aimed
+    /// at is a full round trip old (its way to the client and the shot's way
+    /// back), plus the interpolation delay. Capped at `max_rewind`.
+    pub fn view_tick(&self, current_tick: u64, rtt_ms: f64) -> f64 {
+        let rewind_ms = (rtt_ms.max(0.0) + self.interpolation_delay.as_secs_f64() * 1000.0)
+            .min(self.max_rewind.as_secs_f64() * 1000.0);
+        current_tick as f64 - rewind_ms / SERVER_TICK_MS as f64
+    }
+}
+
+/// Ship positions at the end of one tick
+#[derive(Debug, Clone)]
+struct TickPositions {
+    tick: u64,
+    ships: HashMap<u32, Vec2>,
+}
+
+/// Ring buffer of ship positions for the last few ticks
+#[derive(Debug, Clone)]
+pub struct PositionHistory {
+    snapshots: VecDeque<TickPositions>,
+    capacity: usize,
+}
+
+impl PositionHistory {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Store the positions at the end of `tick`, dropping the oldest tick
+    pub fn record(&mut self, tick: u64, ships: impl IntoIterator<Item = (u32, Vec2)>) {
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(TickPositions {
+            tick,
+            ships: ships.into_iter().collect(),
+        });
+    }
+
+    /// Forget everything (new round: every ship respawned)
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Positions of every ship at a (fractional) tick
+    ///
+    /// Interpolated between the two recorded ticks around it, the short way
+    /// across the wrap seam. Ticks outside the history are clamped to the
+    /// oldest or latest one. Ships missing from either tick use the one
+    /// they're in.
+    pub fn positions_at(&self, tick: f64, world_limit: f32) -> HashMap<u32, Vec2> {
+        let Some(latest) = self.snapshots.back() else {
+            return HashMap::new();
+        };
+        let after_index = self
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.tick as f64 >= tick);
+        let (before, after) = match after_index {
+            None => return latest.ships.clone(),
+            Some(0) => return self.snapshots[0].ships.clone(),
+            Some(i) => (&self.snapshots[i - 1], &self.snapshots[i]),
+        };
+
+        let t = ((tick - before.tick as f64) / (after.tick - before.tick) as f64) as f32;
+        let mut positions = after.ships.clone();
+        for (id, position) in positions.iter_mut() {
+            if let Some(from) = before.ships.get(id) {
+                let interpolated = *from + from.wrapped_delta_to(*position, world_limit) * t;
+                // Back inside the world (offset from the center the short way)
+                *position = Vec2::ZERO.wrapped_delta_to(interpolated, world_limit);
+            }
+        }
+        for (id, position) in &before.ships {
+            positions.entry(*id).or_insert(*position);
+        }
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_LIMIT: f32 = 2000.0;
+
+    /// Ship 1 moving 10 units right per tick, ship 2 standing still
+    fn history(ticks: std::ops::RangeInclusive<u64>) -> PositionHistory {
+        let mut history = PositionHistory::new(8);
+        for tick in ticks {
+            history.record(tick, [(1, Vec2::new(tick as f32 * 10.0, 0.0)), (2, Vec2::new(0.0, 500.0))]);
+        }
+        history
+    }
+
+    #[test]
+    fn test_interpolates_between_ticks() {
+        let history = history(10..=15);
+        let positions = history.positions_at(12.5, WORLD_LIMIT);
+        assert_eq!(positions[&1], Vec2::new(125.0, 0.0));
+        assert_eq!(positions[&2], Vec2::new(0.0, 500.0));
+    }
+
+    #[test]
+    fn test_clamps_to_recorded_ticks() {
+        // Capacity 8: ticks 13..=20 are kept
+        let history = history(10..=20);
+        assert_eq!(history.positions_at(5.0, WORLD_LIMIT)[&1], Vec2::new(130.0, 0.0));
+        assert_eq!(history.positions_at(25.0, WORLD_LIMIT)[&1], Vec2::new(200.0, 0.0));
+        assert!(PositionHistory::new(8).positions_at(1.0, WORLD_LIMIT).is_empty());
+    }
+
+    #[test]
+    fn test_interpolates_across_wrap_seam() {
+        let mut history = PositionHistory::new(4);
+        history.record(1, [(1, Vec2::new(1990.0, 0.0))]);
+        history.record(2, [(1, Vec2::new(-1990.0, 0.0))]);
+
+        let position = history.positions_at(1.75, WORLD_LIMIT)[&1];
+        assert!((position.x - -1995.0).abs() < 1e-3, "Went the short way: {:?}", position);
+    }
+
+    #[test]
+    fn test_ship_joining_between_ticks() {
+        let mut history = PositionHistory::new(4);
+        history.record(1, [(1, Vec2::new(0.0, 0.0))]);
+        history.record(2, [(1, Vec2::new(10.0, 0.0)), (2, Vec2::new(50.0, 50.0))]);
+
+        let positions = history.positions_at(1.5, WORLD_LIMIT);
+        assert_eq!(positions[&2], Vec2::new(50.0, 50.0));
+    }
+
+    #[test]
+    fn test_view_tick_capped_by_max_rewind() {
+        let config = LagCompensationConfig {
+            max_rewind: Duration::from_millis(200),
+            interpolation_delay: Duration::from_millis(50),
+        };
+        // 100ms RTT + 50ms interpolation = 3 ticks
+        assert_eq!(config.view_tick(100, 100.0), 97.0);
+        // 1s RTT: capped at 200ms = 4 ticks
+        assert_eq!(config.view_tick(100, 1000.0), 96.0);
+        assert_eq!(config.history_ticks(), 5);
+    }
+}
@@ -1,3 +1,19 @@
+pub mod asteroid_field;
+pub mod chat;
 pub mod game;
+pub mod lag_compensation;
+pub mod mode;
+pub mod names;
+pub mod pickup_spawner;
+pub mod rate_limit;
+pub mod spawn;
 
-pub use game::Game;
+pub use asteroid_field::{AsteroidField, FieldConfig, DEFAULT_ASTEROID_SEED};
+pub use chat::{sanitize_chat, ChatFilter, ChatLimiter, MAX_CHAT_LENGTH};
+pub use game::{DeathEvent, Game};
+pub use lag_compensation::{LagCompensationConfig, PositionHistory};
+pub use mode::{create_mode, GameMode, RoundClock};
+pub use names::{default_name, NamePolicy, NameViolation};
+pub use pickup_spawner::PickupSpawner;
+pub use rate_limit::{ErrorLimiter, ErrorResponse, TokenBucket};
+pub use spawn::{SpawnSelector, SpawnZone};
@@ -0,0 +1,124 @@
+use battlestar_shared::{
+    entities::Team,
+    network::{ModeKind, RoundResults, Winner},
+};
+
+use super::{GameMode, Scoreboard};
+
+/// Every ship for itself; first to `kill_limit` kills wins
+#[derive(Debug, Clone)]
+pub struct FreeForAll {
+    pub kill_limit: u32,
+    scores: Scoreboard,
+}
+
+impl FreeForAll {
+    pub fn new(kill_limit: u32) -> Self {
+        Self {
+            kill_limit,
+            scores: Scoreboard::default(),
+        }
+    }
+}
+
+impl GameMode for FreeForAll {
+    fn kind(&self) -> ModeKind {
+        ModeKind::FreeForAll
+    }
+
+    fn add_player(&mut self, player_id: u32) -> Option<Team> {
+        self.scores.add_player(player_id, None);
+        None
+    }
+
+    fn remove_player(&mut self, player_id: u32) {
+        self.scores.remove_player(player_id);
+    }
+
+    fn allows_damage(&self, _attacker: u32, _victim: u32) -> bool {
+        true
+    }
+
+    fn record_death(&mut self, victim: u32, attacker: Option<u32>) {
+        self.scores.add_death(victim);
+        if let Some(attacker) = attacker.filter(|&attacker| attacker != victim) {
+            self.scores.add_kill(attacker);
+        }
+    }
+
+    fn winner(&self) -> Option<Winner> {
+        self.scores
+            .iter()
+            .find(|score| score.kills >= self.kill_limit)
+            .map(|score| Winner::Player { id: score.player_id })
+    }
+
+    fn winner_at_time_limit(&self) -> Winner {
+        let ranked = self.scores.ranked();
+        match ranked.as_slice() {
+            [first, second, ..] if first.kills == second.kills => Winner::Draw,
+            [first, ..] => Winner::Player { id: first.player_id },
+            [] => Winner::Draw,
+        }
+    }
+
+    fn results(&self, winner: Winner) -> RoundResults {
+        RoundResults {
+            mode: self.kind(),
+            winner,
+            scores: self.scores.ranked(),
+            team_scores: Vec::new(),
+        }
+    }
+
+    fn reset_scores(&mut self) {
+        self.scores.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_limit_wins() {
+        let mut mode = FreeForAll::new(2);
+        assert_eq!(mode.add_player(1), None);
+        mode.add_player(2);
+
+        mode.record_death(2, Some(1));
+        assert_eq!(mode.winner(), None);
+        mode.record_death(2, Some(1));
+
+        assert_eq!(mode.winner(), Some(Winner::Player { id: 1 }));
+        let results = mode.results(Winner::Player { id: 1 });
+        assert_eq!(results.scores[0].kills, 2);
+        assert_eq!(results.scores[1].deaths, 2);
+    }
+
+    #[test]
+    fn test_asteroid_deaths_score_nobody() {
+        let mut mode = FreeForAll::new(2);
+        mode.add_player(1);
+
+        mode.record_death(1, None);
+
+        let results = mode.results(Winner::Draw);
+        assert_eq!(results.scores[0].kills, 0);
+        assert_eq!(results.scores[0].deaths, 1);
+    }
+
+    #[test]
+    fn test_time_limit_leader_or_draw() {
+        let mut mode = FreeForAll::new(10);
+        mode.add_player(1);
+        mode.add_player(2);
+        assert_eq!(mode.winner_at_time_limit(), Winner::Draw);
+
+        mode.record_death(1, Some(2));
+        assert_eq!(mode.winner_at_time_limit(), Winner::Player { id: 2 });
+
+        mode.reset_scores();
+        assert_eq!(mode.winner_at_time_limit(), Winner::Draw);
+    }
+}
@@ -0,0 +1,195 @@
+//! Game modes: the rules layered on top of the shared physics
+//!
+//! `Game` moves ships and resolves collisions; the active `GameMode`
+//! decides teams, who can damage whom, how kills score and when a round
+//! is over.
+
+mod free_for_all;
+mod team_deathmatch;
+
+pub use free_for_all::FreeForAll;
+pub use team_deathmatch::TeamDeathmatch;
+
+use battlestar_shared::{
+    entities::Team,
+    network::{ModeKind, PlayerScore, RoundResults, Winner, SERVER_TICK_MS},
+};
+use std::collections::BTreeMap;
+
+use crate::constants::*;
+
+/// Rules for one kind of match
+pub trait GameMode: Send + std::fmt::Debug {
+    fn kind(&self) -> ModeKind;
+
+    /// A player joined: returns the team they were put on (None = no teams)
+    fn add_player(&mut self, player_id: u32) -> Option<Team>;
+
+    fn remove_player(&mut self, player_id: u32);
+
+    /// Whether damage from `attacker` reaches `victim` (friendly fire)
+    fn allows_damage(&self, attacker: u32, victim: u32) -> bool;
+
+    /// A ship was destroyed, by `attacker` if another player was responsible
+    fn record_death(&mut self, victim: u32, attacker: Option<u32>);
+
+    /// Winner once the win condition is met (None = keep playing)
+    fn winner(&self) -> Option<Winner>;
+
+    /// Winner when the round runs out of time (may be a draw)
+    fn winner_at_time_limit(&self) -> Winner;
+
+    /// End-of-round summary
+    fn results(&self, winner: Winner) -> RoundResults;
+
+    /// Clear scores for the next round (players and teams are kept)
+    fn reset_scores(&mut self);
+}
+
+/// Create the mode for `kind` with the limits from game-constants.toml
+pub fn create_mode(kind: ModeKind) -> Box<dyn GameMode> {
+    match kind {
+        ModeKind::FreeForAll => Box::new(FreeForAll::new(KILL_LIMIT)),
+        ModeKind::TeamDeathmatch => Box::new(TeamDeathmatch::new(TEAM_KILL_LIMIT, FRIENDLY_FIRE)),
+    }
+}
+
+/// Game time covered by one server tick
+const TICK_SECS: f32 = SERVER_TICK_MS as f32 / 1000.0;
+
+/// Round length in ticks, counted down by `Game::tick`
+#[derive(Debug, Clone, Copy)]
+pub struct RoundClock {
+    pub duration_ticks: u64,
+    pub elapsed_ticks: u64,
+}
+
+impl RoundClock {
+    /// Clock for a round of `duration` seconds
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration_ticks: (duration / TICK_SECS).round() as u64,
+            elapsed_ticks: 0,
+        }
+    }
+
+    /// Advance one tick; true once time is up
+    pub fn tick(&mut self) -> bool {
+        self.elapsed_ticks += 1;
+        self.expired()
+    }
+
+    pub fn expired(&self) -> bool {
+        self.elapsed_ticks >= self.duration_ticks
+    }
+
+    /// Seconds left in the round
+    pub fn remaining_secs(&self) -> f32 {
+        self.duration_ticks.saturating_sub(self.elapsed_ticks) as f32 * TICK_SECS
+    }
+
+    pub fn restart(&mut self) {
+        self.elapsed_ticks = 0;
+    }
+}
+
+impl Default for RoundClock {
+    fn default() -> Self {
+        Self::new(ROUND_DURATION)
+    }
+}
+
+/// Kills and deaths per player, shared by the modes
+#[derive(Debug, Clone, Default)]
+pub struct Scoreboard {
+    scores: BTreeMap<u32, PlayerScore>,
+}
+
+impl Scoreboard {
+    pub fn add_player(&mut self, player_id: u32, team: Option<Team>) {
+        self.scores.insert(
+            player_id,
+            PlayerScore {
+                player_id,
+                kills: 0,
+                deaths: 0,
+                team,
+            },
+        );
+    }
+
+    pub fn remove_player(&mut self, player_id: u32) {
+        self.scores.remove(&player_id);
+    }
+
+    pub fn get(&self, player_id: u32) -> Option<&PlayerScore> {
+        self.scores.get(&player_id)
+    }
+
+    pub fn add_death(&mut self, player_id: u32) {
+        if let Some(score) = self.scores.get_mut(&player_id) {
+            score.deaths += 1;
+        }
+    }
+
+    pub fn add_kill(&mut self, player_id: u32) {
+        if let Some(score) = self.scores.get_mut(&player_id) {
+            score.kills += 1;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PlayerScore> {
+        self.scores.values()
+    }
+
+    /// Scores best first (most kills, then fewest deaths, then lowest ID)
+    pub fn ranked(&self) -> Vec<PlayerScore> {
+        let mut ranked: Vec<PlayerScore> = self.scores.values().copied().collect();
+        ranked.sort_by(|a, b| {
+            b.kills
+                .cmp(&a.kills)
+                .then(a.deaths.cmp(&b.deaths))
+                .then(a.player_id.cmp(&b.player_id))
+        });
+        ranked
+    }
+
+    pub fn reset(&mut self) {
+        for score in self.scores.values_mut() {
+            score.kills = 0;
+            score.deaths = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_clock_expires() {
+        let mut clock = RoundClock::new(1.0);
+        for _ in 0..19 {
+            assert!(!clock.tick());
+        }
+        assert!(clock.tick());
+        assert_eq!(clock.remaining_secs(), 0.0);
+
+        clock.restart();
+        assert_eq!(clock.remaining_secs(), 1.0);
+    }
+
+    #[test]
+    fn test_scoreboard_ranking() {
+        let mut board = Scoreboard::default();
+        board.add_player(1, None);
+        board.add_player(2, None);
+        board.add_player(3, None);
+        board.add_kill(2);
+        board.add_kill(3);
+        board.add_death(3);
+
+        let order: Vec<u32> = board.ranked().iter().map(|s| s.player_id).collect();
+        assert_eq!(order, vec![2, 3, 1]);
+    }
+}
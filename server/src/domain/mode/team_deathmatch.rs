@@ -0,0 +1,171 @@
+use battlestar_shared::{
+    entities::Team,
+    network::{ModeKind, RoundResults, TeamScore, Winner},
+};
+
+use super::{GameMode, Scoreboard};
+
+/// Two teams; first team to `kill_limit` kills wins
+///
+/// Players join the smaller team. Killing a teammate never scores, and
+/// with `friendly_fire` off teammates can't damage each other at all.
+#[derive(Debug, Clone)]
+pub struct TeamDeathmatch {
+    pub kill_limit: u32,
+    pub friendly_fire: bool,
+    scores: Scoreboard,
+}
+
+impl TeamDeathmatch {
+    pub fn new(kill_limit: u32, friendly_fire: bool) -> Self {
+        Self {
+            kill_limit,
+            friendly_fire,
+            scores: Scoreboard::default(),
+        }
+    }
+
+    fn team_of(&self, player_id: u32) -> Option<Team> {
+        self.scores.get(player_id).and_then(|score| score.team)
+    }
+
+    fn team_scores(&self) -> Vec<TeamScore> {
+        Team::ALL
+            .iter()
+            .map(|&team| TeamScore {
+                team,
+                kills: self
+                    .scores
+                    .iter()
+                    .filter(|score| score.team == Some(team))
+                    .map(|score| score.kills)
+                    .sum(),
+            })
+            .collect()
+    }
+}
+
+impl GameMode for TeamDeathmatch {
+    fn kind(&self) -> ModeKind {
+        ModeKind::TeamDeathmatch
+    }
+
+    fn add_player(&mut self, player_id: u32) -> Option<Team> {
+        let members = |team| self.scores.iter().filter(|score| score.team == Some(team)).count();
+        let team = if members(Team::Blue) < members(Team::Red) {
+            Team::Blue
+        } else {
+            Team::Red
+        };
+
+        self.scores.add_player(player_id, Some(team));
+        Some(team)
+    }
+
+    fn remove_player(&mut self, player_id: u32) {
+        self.scores.remove_player(player_id);
+    }
+
+    fn allows_damage(&self, attacker: u32, victim: u32) -> bool {
+        self.friendly_fire || attacker == victim || self.team_of(attacker) != self.team_of(victim)
+    }
+
+    fn record_death(&mut self, victim: u32, attacker: Option<u32>) {
+        self.scores.add_death(victim);
+        if let Some(attacker) = attacker {
+            if self.team_of(attacker) != self.team_of(victim) {
+                self.scores.add_kill(attacker);
+            }
+        }
+    }
+
+    fn winner(&self) -> Option<Winner> {
+        self.team_scores()
+            .into_iter()
+            .find(|score| score.kills >= self.kill_limit)
+            .map(|score| Winner::Team { team: score.team })
+    }
+
+    fn winner_at_time_limit(&self) -> Winner {
+        let scores = self.team_scores();
+        let (red, blue) = (scores[0].kills, scores[1].kills);
+        match red.cmp(&blue) {
+            std::cmp::Ordering::Greater => Winner::Team { team: Team::Red },
+            std::cmp::Ordering::Less => Winner::Team { team: Team::Blue },
+            std::cmp::Ordering::Equal => Winner::Draw,
+        }
+    }
+
+    fn results(&self, winner: Winner) -> RoundResults {
+        RoundResults {
+            mode: self.kind(),
+            winner,
+            scores: self.scores.ranked(),
+            team_scores: self.team_scores(),
+        }
+    }
+
+    fn reset_scores(&mut self) {
+        self.scores.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode_with_four(friendly_fire: bool) -> TeamDeathmatch {
+        let mut mode = TeamDeathmatch::new(2, friendly_fire);
+        for id in 1..=4 {
+            mode.add_player(id);
+        }
+        mode
+    }
+
+    #[test]
+    fn test_players_are_balanced_across_teams() {
+        let mut mode = TeamDeathmatch::new(10, false);
+        let teams: Vec<_> = (1..=4).map(|id| mode.add_player(id).unwrap()).collect();
+        assert_eq!(teams, vec![Team::Red, Team::Blue, Team::Red, Team::Blue]);
+
+        // Red loses a player: the next one joins red
+        mode.remove_player(1);
+        assert_eq!(mode.add_player(5), Some(Team::Red));
+    }
+
+    #[test]
+    fn test_friendly_fire_rules() {
+        let mode = mode_with_four(false);
+        assert!(!mode.allows_damage(1, 3), "Teammates are safe");
+        assert!(mode.allows_damage(1, 2));
+
+        let mode = mode_with_four(true);
+        assert!(mode.allows_damage(1, 3));
+    }
+
+    #[test]
+    fn test_team_kill_limit_wins() {
+        let mut mode = mode_with_four(true);
+
+        mode.record_death(3, Some(1)); // Teamkill: doesn't score
+        mode.record_death(2, Some(1));
+        assert_eq!(mode.winner(), None);
+        mode.record_death(4, Some(3));
+
+        assert_eq!(mode.winner(), Some(Winner::Team { team: Team::Red }));
+        let results = mode.results(Winner::Team { team: Team::Red });
+        assert_eq!(results.team_scores, vec![
+            TeamScore { team: Team::Red, kills: 2 },
+            TeamScore { team: Team::Blue, kills: 0 },
+        ]);
+    }
+
+    #[test]
+    fn test_time_limit_leading_team_or_draw() {
+        let mut mode = mode_with_four(false);
+        assert_eq!(mode.winner_at_time_limit(), Winner::Draw);
+
+        mode.record_death(1, Some(2));
+        assert_eq!(mode.winner_at_time_limit(), Winner::Team { team: Team::Blue });
+    }
+}
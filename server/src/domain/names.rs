@@ -0,0 +1,205 @@
+use std::{fs, io, ops::Range, path::Path};
+
+/// Longest accepted player name (characters)
+pub const MAX_NAME_LENGTH: usize = 16;
+
+/// Blocked words shipped with the server (replaceable at startup)
+const DEFAULT_BLOCKED_NAMES: &str = include_str!("blocked-names.txt");
+
+/// Why a name was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameViolation {
+    /// Nothing left after trimming whitespace
+    Empty,
+
+    /// Longer than `MAX_NAME_LENGTH` characters
+    TooLong,
+
+    /// Something other than letters, digits, spaces, `_`, `-` or `.`
+    InvalidCharacter(char),
+
+    /// Contains a word from the blocked list
+    Blocked,
+}
+
+/// Checks the names players pick in their `Hello`
+#[derive(Debug, Clone)]
+pub struct NamePolicy {
+    /// Lowercase words that may not appear as a word of a name
+    blocked: Vec<String>,
+}
+
+impl NamePolicy {
+    /// Policy with the given blocked-word list (one word per line, `#` comments)
+    pub fn new(list: &str) -> Self {
+        let blocked = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(normalize)
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        Self { blocked }
+    }
+
+    /// Policy with the blocked-word list read from `path`
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(&fs::read_to_string(path)?))
+    }
+
+    /// Trim and check a requested name, returning the name to use
+    pub fn validate(&self, requested: &str) -> Result<String, NameViolation> {
+        let name = requested.trim();
+        if name.is_empty() {
+            return Err(NameViolation::Empty);
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(NameViolation::TooLong);
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|&c| !(c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.')))
+        {
+            return Err(NameViolation::InvalidCharacter(c));
+        }
+
+        // Whole words only, so "Scunthorpe" and "Peacock" get through
+        if words(name).iter().any(|word| is_blocked(&word.text, &self.blocked)) {
+            return Err(NameViolation::Blocked);
+        }
+
+        Ok(name.to_string())
+    }
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_BLOCKED_NAMES)
+    }
+}
+
+/// Name given to players whose requested name was refused (or who sent none)
+pub fn default_name(player_id: u32) -> String {
+    format!("Pilot {}", player_id)
+}
+
+/// Lowercase letters and digits only
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether a word (from `words`) is on a blocked list, or its plural is
+pub fn is_blocked(word: &str, blocked: &[String]) -> bool {
+    blocked
+        .iter()
+        .any(|b| word == b || word.strip_suffix('s') == Some(b.as_str()))
+}
+
+/// A lowercase word and the characters of the text it came from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Word {
+    pub text: String,
+
+    /// Character (not byte) range in the text, punctuation in between included
+    pub span: Range<usize>,
+}
+
+/// Lowercase words of a name or chat message
+///
+/// Splits at spaces and punctuation, where lowercase turns to uppercase
+/// ("FrakYou") and between letters and digits. Runs of single characters
+/// are joined back up, so "f.u.c.k" and "F U C K" read as "fuck".
+pub fn words(text: &str) -> Vec<Word> {
+    let mut pieces: Vec<Word> = Vec::new();
+    let mut current = Word::default();
+    let mut previous: Option<char> = None;
+    for (index, c) in text.chars().enumerate() {
+        let boundary = match previous {
+            _ if !c.is_alphanumeric() => true,
+            Some(p) => {
+                (p.is_lowercase() && c.is_uppercase()) || p.is_alphabetic() != c.is_alphabetic()
+            }
+            None => false,
+        };
+        if boundary && !current.text.is_empty() {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            if current.text.is_empty() {
+                current.span.start = index;
+            }
+            current.text.extend(c.to_lowercase());
+            current.span.end = index + 1;
+            previous = Some(c);
+        } else {
+            previous = None;
+        }
+    }
+    if !current.text.is_empty() {
+        pieces.push(current);
+    }
+
+    let mut words = Vec::new();
+    let mut spelled: Option<Word> = None;
+    for piece in pieces {
+        if piece.span.len() == 1 {
+            match &mut spelled {
+                Some(word) => {
+                    word.text.push_str(&piece.text);
+                    word.span.end = piece.span.end;
+                }
+                None => spelled = Some(piece),
+            }
+            continue;
+        }
+        words.extend(spelled.take());
+        words.push(piece);
+    }
+    words.extend(spelled);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_names_are_trimmed() {
+        let policy = NamePolicy::default();
+        assert_eq!(policy.validate("  Starbuck "), Ok("Starbuck".to_string()));
+        assert_eq!(policy.validate("Lee_Adama-2.0"), Ok("Lee_Adama-2.0".to_string()));
+    }
+
+    #[test]
+    fn test_length_and_charset() {
+        let policy = NamePolicy::default();
+        assert_eq!(policy.validate("   "), Err(NameViolation::Empty));
+        assert_eq!(policy.validate(&"a".repeat(MAX_NAME_LENGTH + 1)), Err(NameViolation::TooLong));
+        assert_eq!(policy.validate(&"a".repeat(MAX_NAME_LENGTH)).map(|n| n.len()), Ok(MAX_NAME_LENGTH));
+        assert_eq!(policy.validate("<script>"), Err(NameViolation::InvalidCharacter('<')));
+        assert_eq!(policy.validate("Zoë"), Err(NameViolation::InvalidCharacter('ë')));
+    }
+
+    #[test]
+    fn test_blocked_words_ignore_case_and_punctuation() {
+        let policy = NamePolicy::new("# comment\nfrak\n\n");
+        assert_eq!(policy.validate("FrakYou"), Err(NameViolation::Blocked));
+        assert_eq!(policy.validate("f.r a-k"), Err(NameViolation::Blocked));
+        assert!(policy.validate("Frank").is_ok());
+    }
+
+    #[test]
+    fn test_blocked_words_match_whole_words() {
+        let policy = NamePolicy::default();
+        for name in ["Scunthorpe", "Dickens", "Peacock", "Hitchcock", "Cockpit Ace"] {
+            assert!(policy.validate(name).is_ok(), "{} is allowed", name);
+        }
+        for name in ["Dick", "big dick", "BigDick", "D.I.C.K", "Dicks", "dick99"] {
+            assert_eq!(policy.validate(name), Err(NameViolation::Blocked), "{} is blocked", name);
+        }
+    }
+}
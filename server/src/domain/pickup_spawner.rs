@@ -0,0 +1,146 @@
+use battlestar_shared::{
+    entities::{Asteroid, Pickup, PickupKind},
+    math::SeededRng,
+    network::SERVER_TICK_MS,
+    Vec2,
+};
+
+use crate::constants::*;
+
+/// Placement attempts per pickup before waiting for the next interval
+const MAX_PLACEMENT_ATTEMPTS: usize = 32;
+
+/// Minimum gap between a new pickup and any asteroid surface
+const ASTEROID_CLEARANCE: f32 = 60.0;
+
+/// Drops pickups into the world at a fixed interval
+///
+/// Seeded like the asteroid field, so the same seed places the same
+/// pickups at the same ticks.
+#[derive(Debug, Clone)]
+pub struct PickupSpawner {
+    /// Ticks between spawns
+    pub interval: u64,
+
+    /// Pickups lying in the world at once (no spawns while at the cap)
+    pub max_active: usize,
+
+    rng: SeededRng,
+    next_id: u32,
+    ticks_since_spawn: u64,
+}
+
+impl PickupSpawner {
+    pub fn new(seed: u64) -> Self {
+        let interval = ((PICKUP_SPAWN_INTERVAL * 1000.0 / SERVER_TICK_MS as f32).round() as u64).max(1);
+
+        Self {
+            interval,
+            max_active: PICKUP_MAX_ACTIVE,
+            rng: SeededRng::new(seed),
+            next_id: 1,
+            ticks_since_spawn: 0,
+        }
+    }
+
+    /// Advance one tick, spawning a pickup when the interval elapses
+    ///
+    /// Returns the new pickup's ID. If there's no room clear of asteroids,
+    /// tries again next tick.
+    pub fn update(&mut self, pickups: &mut Vec<Pickup>, asteroids: &[Asteroid], world_limit: f32) -> Option<u32> {
+        self.ticks_since_spawn += 1;
+        if self.ticks_since_spawn < self.interval || pickups.len() >= self.max_active {
+            return None;
+        }
+
+        let position = self.place(asteroids, world_limit)?;
+        let kind = PickupKind::ALL[(self.rng.next_u64() % PickupKind::ALL.len() as u64) as usize];
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.ticks_since_spawn = 0;
+        pickups.push(Pickup::new(id, kind, position));
+
+        Some(id)
+    }
+
+    fn place(&mut self, asteroids: &[Asteroid], world_limit: f32) -> Option<Vec2> {
+        (0..MAX_PLACEMENT_ATTEMPTS).find_map(|_| {
+            let position = Vec2::new(
+                self.rng.range(-world_limit, world_limit),
+                self.rng.range(-world_limit, world_limit),
+            );
+
+            let clear = asteroids.iter().all(|asteroid| {
+                let gap = asteroid.radius + PICKUP_RADIUS + ASTEROID_CLEARANCE;
+                position.wrapped_distance_squared_to(asteroid.position, world_limit) >= gap * gap
+            });
+            clear.then_some(position)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawns_once_per_interval() {
+        let mut spawner = PickupSpawner::new(1);
+        let mut pickups = Vec::new();
+
+        for _ in 0..spawner.interval - 1 {
+            assert_eq!(spawner.update(&mut pickups, &[], WORLD_LIMIT), None);
+        }
+        assert_eq!(spawner.update(&mut pickups, &[], WORLD_LIMIT), Some(1));
+        assert_eq!(pickups.len(), 1);
+    }
+
+    #[test]
+    fn test_stops_at_max_active() {
+        let mut spawner = PickupSpawner::new(1);
+        spawner.interval = 1;
+        spawner.max_active = 2;
+        let mut pickups = Vec::new();
+
+        for _ in 0..10 {
+            spawner.update(&mut pickups, &[], WORLD_LIMIT);
+        }
+
+        assert_eq!(pickups.len(), 2);
+    }
+
+    #[test]
+    fn test_same_seed_same_pickups() {
+        let run = |seed| {
+            let mut spawner = PickupSpawner::new(seed);
+            spawner.interval = 1;
+            let mut pickups = Vec::new();
+            for _ in 0..5 {
+                spawner.update(&mut pickups, &[], WORLD_LIMIT);
+            }
+            pickups.iter().map(|p| (p.kind, p.position)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(9), run(9));
+        assert_ne!(run(9), run(10));
+    }
+
+    #[test]
+    fn test_keeps_clear_of_asteroids() {
+        let mut spawner = PickupSpawner::new(4);
+        spawner.interval = 1;
+        spawner.max_active = 50;
+        let asteroids = vec![Asteroid::new(1, Vec2::ZERO, Vec2::ZERO, 400.0)];
+        let mut pickups = Vec::new();
+
+        for _ in 0..50 {
+            spawner.update(&mut pickups, &asteroids, WORLD_LIMIT);
+        }
+
+        assert!(!pickups.is_empty());
+        for pickup in &pickups {
+            assert!(pickup.position.length() >= 400.0 + PICKUP_RADIUS + ASTEROID_CLEARANCE);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::{Duration, Instant};
+
+/// Error replies a client can get in a burst
+const ERROR_BURST: u32 = 5;
+
+/// Time to earn back one error reply of the burst
+const ERROR_REFILL: Duration = Duration::from_secs(1);
+
+/// Bad messages in a row before the connection is closed
+pub const MAX_CONSECUTIVE_ERRORS: u32 = 20;
+
+/// Token bucket: a burst of `burst` actions, then one every `refill`
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    burst: u32,
+    refill: Duration,
+    tokens: u32,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(burst: u32, refill: Duration, now: Instant) -> Self {
+        Self {
+            burst,
+            refill,
+            tokens: burst,
+            last_refill: now,
+        }
+    }
+
+    /// Spend one token; false if none is left
+    pub fn try_take(&mut self, now: Instant) -> bool {
+        let earned = (now.duration_since(self.last_refill).as_millis() / self.refill.as_millis()) as u32;
+        if earned > 0 {
+            self.tokens = (self.tokens + earned).min(self.burst);
+            self.last_refill += self.refill * earned;
+        }
+        if self.tokens == 0 {
+            return false;
+        }
+        self.tokens -= 1;
+        true
+    }
+}
+
+/// What to do about a message the server couldn't use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorResponse {
+    /// Tell the client what was wrong
+    Reply,
+
+    /// Drop it without a word (the client already got its share of errors)
+    Ignore,
+
+    /// Too many bad messages in a row: close the connection
+    Disconnect,
+}
+
+/// Per-connection handling of malformed messages
+///
+/// Error replies are rate limited like chat (`ERROR_BURST`, then one every
+/// `ERROR_REFILL`), so garbage can't be turned into an outbound flood.
+/// `MAX_CONSECUTIVE_ERRORS` bad messages without a valid one in between
+/// disconnect the client.
+#[derive(Debug, Clone)]
+pub struct ErrorLimiter {
+    replies: TokenBucket,
+    consecutive: u32,
+}
+
+impl ErrorLimiter {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            replies: TokenBucket::new(ERROR_BURST, ERROR_REFILL, now),
+            consecutive: 0,
+        }
+    }
+
+    /// A bad message arrived (Disconnect is returned once)
+    pub fn bad_message(&mut self, now: Instant) -> ErrorResponse {
+        self.consecutive += 1;
+        if self.consecutive == MAX_CONSECUTIVE_ERRORS {
+            ErrorResponse::Disconnect
+        } else if self.consecutive < MAX_CONSECUTIVE_ERRORS && self.replies.try_take(now) {
+            ErrorResponse::Reply
+        } else {
+            ErrorResponse::Ignore
+        }
+    }
+
+    /// A valid message arrived: the streak of bad ones is over
+    pub fn good_message(&mut self) {
+        self.consecutive = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_replies_rate_limited() {
+        let start = Instant::now();
+        let mut errors = ErrorLimiter::new(start);
+        for _ in 0..ERROR_BURST {
+            assert_eq!(errors.bad_message(start), ErrorResponse::Reply);
+            errors.good_message();
+        }
+        assert_eq!(errors.bad_message(start), ErrorResponse::Ignore, "Burst used up");
+        assert_eq!(errors.bad_message(start + ERROR_REFILL), ErrorResponse::Reply);
+    }
+
+    #[test]
+    fn test_consecutive_errors_disconnect() {
+        let start = Instant::now();
+        let mut errors = ErrorLimiter::new(start);
+        for _ in 1..MAX_CONSECUTIVE_ERRORS {
+            assert_ne!(errors.bad_message(start), ErrorResponse::Disconnect);
+        }
+        assert_eq!(errors.bad_message(start), ErrorResponse::Disconnect);
+        assert_eq!(errors.bad_message(start), ErrorResponse::Ignore);
+
+        // A valid message in between resets the streak
+        let mut errors = ErrorLimiter::new(start);
+        for _ in 0..MAX_CONSECUTIVE_ERRORS * 2 {
+            assert_ne!(errors.bad_message(start), ErrorResponse::Disconnect);
+            errors.good_message();
+        }
+    }
+}
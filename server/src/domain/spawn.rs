@@ -0,0 +1,229 @@
+use battlestar_shared::{entities::Asteroid, Vec2};
+use rand::{Rng, RngExt};
+use std::str::FromStr;
+
+/// Number of candidate positions sampled per spawn
+pub const SPAWN_CANDIDATES: usize = 24;
+
+/// How far ahead asteroid paths are checked (seconds)
+///
+/// Covers the invincibility window and a bit more, so an asteroid about
+/// to drift over the spawn point counts as close.
+pub const SPAWN_LOOKAHEAD_SECS: f32 = 2.0;
+
+/// Rectangular area ships may spawn in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnZone {
+    pub center: Vec2,
+    pub half_extent: Vec2,
+}
+
+impl SpawnZone {
+    pub fn new(center: Vec2, half_extent: Vec2) -> Self {
+        Self { center, half_extent }
+    }
+
+    /// The whole toroidal world
+    pub fn whole_world(world_limit: f32) -> Self {
+        Self::new(Vec2::ZERO, Vec2::new(world_limit, world_limit))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let offset = |half: f32, rng: &mut R| {
+            if half > 0.0 {
+                rng.random_range(-half..half)
+            } else {
+                0.0
+            }
+        };
+        let x = offset(self.half_extent.x, rng);
+        let y = offset(self.half_extent.y, rng);
+        self.center + Vec2::new(x, y)
+    }
+}
+
+/// Parse `x,y,half_width,half_height`
+impl FromStr for SpawnZone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<f32> = s
+            .split(',')
+            .map(|v| v.trim().parse::<f32>().map_err(|_| format!("invalid number '{}'", v)))
+            .collect::<Result<_, _>>()?;
+
+        match values[..] {
+            [x, y, w, h] if w >= 0.0 && h >= 0.0 => {
+                Ok(Self::new(Vec2::new(x, y), Vec2::new(w, h)))
+            }
+            _ => Err(format!("expected x,y,half_width,half_height, got '{}'", s)),
+        }
+    }
+}
+
+/// Picks respawn points away from asteroids and other ships
+///
+/// Samples `candidates` positions across the spawn zones and keeps the one
+/// with the largest clearance, measured the short way round the wrapping
+/// world. Asteroids count along their path for the next
+/// `SPAWN_LOOKAHEAD_SECS`, not just where they are now.
+#[derive(Debug, Clone)]
+pub struct SpawnSelector {
+    /// Zones to sample from (empty = whole world)
+    pub zones: Vec<SpawnZone>,
+
+    pub candidates: usize,
+}
+
+impl SpawnSelector {
+    pub fn new(zones: Vec<SpawnZone>) -> Self {
+        Self {
+            zones,
+            candidates: SPAWN_CANDIDATES,
+        }
+    }
+
+    /// Pick the candidate with the most clearance
+    pub fn select<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        asteroids: &[Asteroid],
+        ships: &[Vec2],
+        world_limit: f32,
+    ) -> Vec2 {
+        let whole_world = [SpawnZone::whole_world(world_limit)];
+        let zones = if self.zones.is_empty() {
+            &whole_world[..]
+        } else {
+            &self.zones[..]
+        };
+
+        let mut best = zones[0].center;
+        let mut best_clearance = f32::NEG_INFINITY;
+
+        for i in 0..self.candidates.max(1) {
+            // Spread candidates evenly over the zones
+            let mut candidate = zones[i % zones.len()].sample(rng);
+            wrap_into_world(&mut candidate, world_limit);
+
+            let clearance = clearance(candidate, asteroids, ships, world_limit);
+            if clearance > best_clearance {
+                best = candidate;
+                best_clearance = clearance;
+            }
+        }
+
+        best
+    }
+}
+
+impl Default for SpawnSelector {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+/// Distance from `point` to the nearest asteroid surface or ship
+fn clearance(point: Vec2, asteroids: &[Asteroid], ships: &[Vec2], world_limit: f32) -> f32 {
+    let asteroid_clearance = asteroids.iter().map(|asteroid| {
+        let path = asteroid.velocity * SPAWN_LOOKAHEAD_SECS;
+        distance_to_path(asteroid.position, path, point, world_limit) - asteroid.radius
+    });
+    let ship_clearance = ships
+        .iter()
+        .map(|ship| ship.wrapped_distance_to(point, world_limit));
+
+    asteroid_clearance
+        .chain(ship_clearance)
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Shortest distance from `point` to the segment `start..start + path` (wrap-aware)
+fn distance_to_path(start: Vec2, path: Vec2, point: Vec2, world_limit: f32) -> f32 {
+    let offset = start.wrapped_delta_to(point, world_limit);
+    let length_squared = path.length_squared();
+    if length_squared == 0.0 {
+        return offset.length();
+    }
+
+    let t = ((offset.x * path.x + offset.y * path.y) / length_squared).clamp(0.0, 1.0);
+    (offset - path * t).length()
+}
+
+/// Map a point outside [-world_limit, world_limit] back onto the torus
+fn wrap_into_world(point: &mut Vec2, world_limit: f32) {
+    let size = world_limit * 2.0;
+    let wrap = |v: f32| (v + world_limit).rem_euclid(size) - world_limit;
+    point.x = wrap(point.x);
+    point.y = wrap(point.y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(7)
+    }
+
+    #[test]
+    fn test_avoids_asteroid_at_origin() {
+        let asteroids = vec![Asteroid::new(1, Vec2::ZERO, Vec2::ZERO, 50.0)];
+        let selector = SpawnSelector::default();
+
+        let spawn = selector.select(&mut rng(), &asteroids, &[], 2000.0);
+
+        assert!(spawn.wrapped_distance_to(Vec2::ZERO, 2000.0) > 500.0, "Got {:?}", spawn);
+    }
+
+    #[test]
+    fn test_picks_farthest_candidate_in_small_zone() {
+        // Zone straddles an enemy ship on its left edge
+        let zone = SpawnZone::new(Vec2::new(100.0, 0.0), Vec2::new(100.0, 0.0));
+        let selector = SpawnSelector::new(vec![zone]);
+
+        let spawn = selector.select(&mut rng(), &[], &[Vec2::new(0.0, 0.0)], 2000.0);
+
+        assert!(spawn.x > 150.0, "Should spawn at the far end of the zone, got {:?}", spawn);
+    }
+
+    #[test]
+    fn test_clearance_is_wrap_aware() {
+        // Enemy just across the right seam is close to a point at the left edge
+        let ships = [Vec2::new(1990.0, 0.0)];
+        let near_seam = clearance(Vec2::new(-1990.0, 0.0), &[], &ships, 2000.0);
+        assert_eq!(near_seam, 20.0);
+    }
+
+    #[test]
+    fn test_asteroid_path_counts_as_close() {
+        // Asteroid will drift through the point within the lookahead
+        let asteroid = Asteroid::new(1, Vec2::new(-60.0, 0.0), Vec2::new(40.0, 0.0), 10.0);
+        let clearance = clearance(Vec2::ZERO, &[asteroid], &[], 2000.0);
+        assert!(clearance < 0.0);
+    }
+
+    #[test]
+    fn test_candidates_stay_inside_world() {
+        // Zone hanging over the corner of the world
+        let zone = SpawnZone::new(Vec2::new(1950.0, 1950.0), Vec2::new(200.0, 200.0));
+        let selector = SpawnSelector::new(vec![zone]);
+        let mut rng = rng();
+
+        for _ in 0..50 {
+            let spawn = selector.select(&mut rng, &[], &[], 2000.0);
+            assert!(spawn.x.abs() <= 2000.0 && spawn.y.abs() <= 2000.0, "Got {:?}", spawn);
+        }
+    }
+
+    #[test]
+    fn test_parse_spawn_zone() {
+        let zone: SpawnZone = "100, -50, 200, 150".parse().unwrap();
+        assert_eq!(zone, SpawnZone::new(Vec2::new(100.0, -50.0), Vec2::new(200.0, 150.0)));
+
+        assert!("1,2,3".parse::<SpawnZone>().is_err());
+        assert!("1,2,-3,4".parse::<SpawnZone>().is_err());
+        assert!("a,2,3,4".parse::<SpawnZone>().is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use axum::extract::State;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::app::AppState;
+
+/// Metrics endpoint (Prometheus text exposition format)
+///
+/// Exposes per-connection outbound queue depth so slow clients are visible:
+/// - `battlestar_connections`: connected clients
+/// - `battlestar_outbound_queue_depth`: messages waiting per player
+/// - `battlestar_outbound_queue_high_water`: highest depth seen per player
+/// - `battlestar_outbound_coalesced_total`: snapshots dropped per player
+/// - `battlestar_rtt_ms` / `battlestar_rtt_jitter_ms`: measured round trip per player
+pub async fn metrics(State(state): State<Arc<AppState>>) -> String {
+    let stats = state.connections.queue_stats();
+    let latencies = state.connections.latencies();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# TYPE battlestar_connections gauge");
+    let _ = writeln!(out, "battlestar_connections {}", stats.len());
+
+    let _ = writeln!(out, "# TYPE battlestar_outbound_queue_depth gauge");
+    for (id, s) in &stats {
+        let _ = writeln!(out, "battlestar_outbound_queue_depth{{player=\"{}\"}} {}", id, s.depth);
+    }
+
+    let _ = writeln!(out, "# TYPE battlestar_outbound_queue_high_water gauge");
+    for (id, s) in &stats {
+        let _ = writeln!(out, "battlestar_outbound_queue_high_water{{player=\"{}\"}} {}", id, s.high_water);
+    }
+
+    let _ = writeln!(out, "# TYPE battlestar_outbound_coalesced_total counter");
+    for (id, s) in &stats {
+        let _ = writeln!(out, "battlestar_outbound_coalesced_total{{player=\"{}\"}} {}", id, s.coalesced);
+    }
+
+    let _ = writeln!(out, "# TYPE battlestar_rtt_ms gauge");
+    for (id, latency) in &latencies {
+        let _ = writeln!(out, "battlestar_rtt_ms{{player=\"{}\"}} {:.1}", id, latency.rtt_ms);
+    }
+
+    let _ = writeln!(out, "# TYPE battlestar_rtt_jitter_ms gauge");
+    for (id, latency) in &latencies {
+        let _ = writeln!(out, "battlestar_rtt_jitter_ms{{player=\"{}\"}} {:.1}", id, latency.jitter_ms);
+    }
+
+    out
+}
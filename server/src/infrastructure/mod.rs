@@ -1,4 +1,5 @@
 pub mod websocket;
 pub mod server;
+pub mod metrics;
 
 pub use server::*;
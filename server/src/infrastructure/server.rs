@@ -1,7 +1,8 @@
 use axum::{routing::get, Router};
 use std::sync::Arc;
 
-use crate::app::AppState;
+use crate::app::{AppState, ServerConfig};
+use super::metrics::metrics;
 use super::websocket::ws_handler;
 
 /// Health check endpoint
@@ -13,16 +14,22 @@ async fn health_check() -> &'static str {
 pub fn create_app(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/health", get(health_check))
+        .route("/metrics", get(metrics))
         .route("/ws", get(ws_handler))
         .with_state(state)
 }
 
-/// Start the server on the specified address
+/// Start the server on the specified address, configured from the environment
 pub async fn run_server(addr: &str) -> Result<(), Box<dyn std::error::Error>> {
+    serve(addr, ServerConfig::from_env()).await
+}
+
+/// Start the server on the specified address with the given configuration
+pub async fn serve(addr: &str, config: ServerConfig) -> Result<(), Box<dyn std::error::Error>> {
     println!("Starting Battlestar server...");
 
     // Create application state
-    let app_state = AppState::new();
+    let app_state = AppState::with_config(config);
 
     // Create router
     let app = create_app(app_state);
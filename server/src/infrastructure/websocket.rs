@@ -1,12 +1,27 @@
-use battlestar_shared::{ClientInput, ServerMessage};
-use crate::app::AppState;
+use battlestar_shared::input::{InputMonitor, SuspicionAction};
+use battlestar_shared::network::{ClientErrorKind, RttEstimator, PROTOCOL_VERSION};
+use battlestar_shared::{ClientMessage, Input, ServerMessage};
+use crate::app::{
+    connections::Latency, AppState, DelayLine, LinkConditioner, LinkConditions, OutboundKind, OutboundQueue,
+};
+use crate::constants::CONSTANTS_HASH;
+use crate::domain::{
+    default_name, rate_limit::MAX_CONSECUTIVE_ERRORS, sanitize_chat, ChatLimiter, ErrorLimiter, ErrorResponse,
+};
+use crate::simulation::GameCommand;
 use axum::{
     extract::State,
     extract::ws::{Message, WebSocket, WebSocketUpgrade},
     response::IntoResponse,
 };
+use futures_util::{stream::{BoxStream, SplitSink, SplitStream}, SinkExt, StreamExt};
+use std::collections::VecDeque;
 use std::sync::{atomic::Ordering, Arc};
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Messages from a client, possibly through the link conditioner
+type Inbound = BoxStream<'static, Result<Message, axum::Error>>;
 
 /// WebSocket upgrade handler
 ///
@@ -19,124 +34,448 @@ pub async fn ws_handler(
     ws.on_upgrade(|socket| handle_socket(socket, state))
 }
 
+/// How long a new connection has to send its `Hello`
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time between two server pings (round-trip measurement)
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Server pings awaiting a pong (older ones are given up on), so links
+/// with a round trip over `PING_INTERVAL` are still measured
+const MAX_OUTSTANDING_PINGS: usize = 8;
+
+/// How far a pong's `server_time` can be from the ping's and still match it
+///
+/// JSON doesn't always parse a float back to the exact bits that were
+/// written, and pings are a whole `PING_INTERVAL` apart.
+const PONG_MATCH_TOLERANCE_MS: f64 = 0.001;
+
+/// Client pings closer together than this get no pong
+const MIN_CLIENT_PING_INTERVAL: Duration = Duration::from_millis(200);
+
 /// Handle a single WebSocket connection
 ///
 /// Lifecycle:
 /// 1. Assign unique player ID (lock-free atomic)
-/// 2. Send Welcome message with assigned ID
-/// 3. Register player in game
-/// 4. Run event loop (receive inputs, send game state)
-/// 5. Cleanup on disconnect
+/// 2. Wait for the client's Hello: reject incompatible clients, validate
+///    the requested name
+/// 3. Queue Welcome message with assigned ID and name
+/// 4. Register player in the connection registry and with the game loop
+/// 5. Spawn the writer task (drains the outbound queue into the socket)
+/// 6. Run the reader loop (receive inputs and chat, answer pings, ping the
+///    client every second, answer anything else with a rate-limited
+///    `Error`) until the socket closes or the writer exits (kick, too many
+///    bad messages, send failure)
+/// 7. Cleanup on disconnect
+///
+/// With a simulated network (`AppState::link`), both directions go through
+/// a link conditioner before reaching the reader loop or the socket.
 ///
-/// Uses new architecture with single-lock pattern for game state access.
-async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
+/// Reading and writing run in separate tasks: a client on a slow network
+/// only backs up its own bounded outbound queue, and its inputs keep being
+/// processed while a send is in flight. Inputs are handed to the game loop
+/// through its command channel, so no lock is shared with the simulation.
+async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
     // Assign unique player ID (lock-free)
     let player_id = state.next_player_id.fetch_add(1, Ordering::SeqCst);
 
-    // Send Welcome message
+    let (sink, stream) = socket.split();
+    let mut stream = conditioned_inbound(stream, state.link);
+
+    let name = match receive_hello(&mut stream, &state, player_id).await {
+        Handshake::Accepted(name) => name,
+        Handshake::Incompatible => {
+            println!("Player {} rejected: incompatible client", player_id);
+            reject_incompatible(sink).await;
+            return;
+        }
+        Handshake::Closed => return,
+    };
+    println!("Player {} joined as '{}'", player_id, name);
+
+    // Register outbound queue; Welcome goes in first so it precedes any snapshot
     let welcome = ServerMessage::Welcome {
         assigned_id: player_id,
+        name: name.clone(),
     };
-    if let Ok(welcome_json) = serde_json::to_string(&welcome) {
-        if socket
-            .send(Message::Text(welcome_json.into()))
-            .await
-            .is_err()
-        {
-            return;
-        }
-    }
+    let welcome_json = serde_json::to_string(&welcome).unwrap_or_default();
+    let queue = state.connections.register_with(player_id, Message::Text(welcome_json.into()));
 
-    // Register player in game (single lock)
+    // Register player in game (before any of its inputs)
+    if state
+        .commands
+        .send(GameCommand::Connect { player_id, name: name.clone() })
+        .await
+        .is_err()
     {
-        let mut snapshot = state.game.lock().await;
-        snapshot.connected_players.insert(player_id);
+        state.connections.remove(player_id);
+        return; // Game loop is gone
     }
 
-    // Subscribe to game state broadcasts
-    let mut rx = state.broadcaster.subscribe();
+    let mut writer = tokio::spawn(write_outbound(sink, queue, state.link));
+
+    // SERVER-SIDE RATE LIMITING (anti-cheat)
+    // Minimum 15ms between inputs (~66 inputs/sec max)
+    // Allows 60Hz client input with some tolerance
+    // Tracked per connection: no shared state is touched for rejected inputs
+    const MIN_INPUT_INTERVAL: Duration = Duration::from_millis(15);
+    // Throttled (suspicious) clients get one input per game tick
+    const THROTTLED_INPUT_INTERVAL: Duration = Duration::from_millis(50);
+    let mut last_input_time: Option<Instant> = None;
 
-    // Event loop
+    // Statistical anti-cheat: NaN/infinite values, scripted timing, impossible toggling
+    let connected_at = Instant::now();
+    let mut monitor = InputMonitor::new(state.suspicion);
+    let mut action = SuspicionAction::None;
+
+    let mut chat_limiter = ChatLimiter::new(connected_at);
+    let mut errors = ErrorLimiter::new(connected_at);
+
+    // Round trip: only a pong for an outstanding ping counts, so a client
+    // can't report a made-up time
+    let mut rtt = RttEstimator::new();
+    let mut ping_timer = tokio::time::interval(PING_INTERVAL);
+    let mut outstanding_pings: VecDeque<f64> = VecDeque::with_capacity(MAX_OUTSTANDING_PINGS);
+    let mut last_client_ping: Option<Instant> = None;
+
+    // Reader loop: receive input and chat from client
     loop {
-        tokio::select! {
-            // Receive input from client
-            maybe_msg = socket.recv() => {
-                let Some(Ok(msg)) = maybe_msg else {
-                    break; // Connection closed or error
-                };
+        let maybe_msg = tokio::select! {
+            maybe_msg = stream.next() => maybe_msg,
+            _ = ping_timer.tick() => {
+                let server_time = state.server_time_ms();
+                if outstanding_pings.len() == MAX_OUTSTANDING_PINGS {
+                    outstanding_pings.pop_front(); // Lost
+                }
+                outstanding_pings.push_back(server_time);
+                reply(&state, player_id, &ServerMessage::Ping { server_time });
+                continue;
+            }
+            // Writer finished: the server kicked this client or the socket failed
+            _ = &mut writer => break,
+        };
+        let Some(Ok(msg)) = maybe_msg else {
+            break; // Connection closed or error
+        };
 
-                if let Message::Text(text) = msg {
-                    if let Ok(mut input) = serde_json::from_str::<ClientInput>(&text) {
-                        // Override client's player_id with server-assigned ID (anti-cheat)
-                        input.player_id = player_id;
-
-                        // SERVER-SIDE RATE LIMITING (anti-cheat)
-                        // Minimum 15ms between inputs (~66 inputs/sec max)
-                        // Allows 60Hz client input with some tolerance
-                        const MIN_INPUT_INTERVAL: Duration = Duration::from_millis(15);
-
-                        let now = Instant::now();
-
-                        // Single lock for rate limiting + input storage
-                        let should_accept = {
-                            let mut snapshot = state.game.lock().await;
-
-                            // Check rate limit
-                            if let Some(last_time) = snapshot.last_input_time.get(&player_id) {
-                                let elapsed = now.duration_since(*last_time);
-                                if elapsed < MIN_INPUT_INTERVAL {
-                                    // Rate limit exceeded - reject input
-                                    false
-                                } else {
-                                    // Update last input time and store input
-                                    snapshot.last_input_time.insert(player_id, now);
-                                    snapshot.player_inputs.insert(player_id, input);
-                                    true
-                                }
-                            } else {
-                                // First input - accept and record time
-                                snapshot.last_input_time.insert(player_id, now);
-                                snapshot.player_inputs.insert(player_id, input);
-                                true
-                            }
-                        };
-
-                        // Log rate limit violations (optional)
-                        if !should_accept {
-                            // Could log here for debugging
-                            // println!("Rate limit exceeded for player {}", player_id);
-                        }
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Binary(_) => {
+                let detail = "expected a text message".to_string();
+                reject_message(&state, player_id, &mut errors, ClientErrorKind::Malformed, detail);
+                continue;
+            }
+            _ => continue, // Pings and the like
+        };
+        let message = match ClientMessage::parse(&text) {
+            Ok(message) => message,
+            Err((kind, detail)) => {
+                reject_message(&state, player_id, &mut errors, kind, detail);
+                continue;
+            }
+        };
+        errors.good_message();
+        match message {
+            ClientMessage::Input(mut input) => {
+                // Override client's player_id with server-assigned ID (anti-cheat)
+                input.player_id = player_id;
+
+                let now = Instant::now();
+                let time_ms = now.duration_since(connected_at).as_secs_f64() * 1000.0;
+                let observation = monitor.observe(&Input::new(input.thrust, input.rotate), time_ms);
+
+                // Act on escalation only, so logs aren't spammed every input
+                if observation.action > action {
+                    action = observation.action;
+                    println!(
+                        "Player {} suspicion {:.0} -> {:?} ({:?})",
+                        player_id, observation.score, action, observation.anomalies
+                    );
+                    if action == SuspicionAction::Kick {
+                        // Writer sends the close frame, then the select above ends the loop
+                        state.connections.kick(player_id);
                     }
+                } else if observation.action < action && action != SuspicionAction::Kick {
+                    action = observation.action; // Score decayed
                 }
-            }
 
-            // Broadcast game state to client
-            msg = rx.recv() => {
-                if let Ok(text) = msg {
-                    if socket.send(Message::Text(text.into())).await.is_err() {
-                        break; // Send failed, connection likely closed
+                if observation.rejected() || action == SuspicionAction::Kick {
+                    continue;
+                }
+
+                let min_interval = if action == SuspicionAction::Throttle {
+                    THROTTLED_INPUT_INTERVAL
+                } else {
+                    MIN_INPUT_INTERVAL
+                };
+                if let Some(last_time) = last_input_time {
+                    if now.duration_since(last_time) < min_interval {
+                        // Rate limit exceeded - reject input
+                        continue;
                     }
                 }
+                last_input_time = Some(now);
+
+                // Never wait on the game loop: the channel only fills up
+                // if the loop is far behind, and then dropping is the best option
+                let _ = state.commands.try_send(GameCommand::Input(input));
             }
+            ClientMessage::Ping { client_time } => {
+                let now = Instant::now();
+                if last_client_ping.is_some_and(|last| now.duration_since(last) < MIN_CLIENT_PING_INTERVAL) {
+                    continue;
+                }
+                last_client_ping = Some(now);
+                let pong = ServerMessage::Pong {
+                    client_time,
+                    server_time: state.server_time_ms(),
+                    tick: state.tick.load(Ordering::Relaxed),
+                };
+                reply(&state, player_id, &pong);
+            }
+            ClientMessage::Pong { server_time } => {
+                let Some(index) = outstanding_pings
+                    .iter()
+                    .position(|&sent| (sent - server_time).abs() < PONG_MATCH_TOLERANCE_MS)
+                else {
+                    continue; // Stale, duplicate or forged
+                };
+                let sent = outstanding_pings[index];
+                // Pongs come back in order: anything sent earlier was lost
+                outstanding_pings.drain(..=index);
+                rtt.add_sample(state.server_time_ms() - sent);
+                if let Some(rtt_ms) = rtt.rtt_ms() {
+                    let latency = Latency { rtt_ms, jitter_ms: rtt.jitter_ms() };
+                    state.connections.set_latency(player_id, latency);
+                    let _ = state.commands.try_send(GameCommand::Latency { player_id, rtt_ms });
+                }
+            }
+            ClientMessage::Chat { text } => {
+                if !chat_limiter.try_send(Instant::now()) {
+                    continue; // Sending too fast: dropped
+                }
+                let Some(text) = sanitize_chat(&text) else {
+                    continue;
+                };
+                let chat = ServerMessage::Chat {
+                    from: player_id,
+                    name: name.clone(),
+                    text: state.chat_filter.censor(&text),
+                };
+                if let Ok(payload) = serde_json::to_string(&chat) {
+                    state.connections.broadcast(OutboundKind::Control, payload);
+                }
+            }
+            // A second Hello is ignored
+            ClientMessage::Hello { .. } => {}
         }
     }
 
-    // Cleanup on disconnect (single lock)
-    {
-        let mut snapshot = state.game.lock().await;
+    // Stop the writer: closing the queue lets it exit, abort covers a stuck send
+    state.connections.remove(player_id);
+    writer.abort();
+
+    // Remove player's ship and buffered input from the game
+    let _ = state
+        .commands
+        .send(GameCommand::Disconnect { player_id })
+        .await;
+
+    println!("Player {} disconnected", player_id);
+}
 
-        // Remove from connected players
-        snapshot.connected_players.remove(&player_id);
+/// Outcome of waiting for the client's `Hello`
+enum Handshake {
+    /// Play under this name
+    Accepted(String),
 
-        // Remove player's ship from game
-        snapshot.game.remove_player(player_id);
+    /// Client built with another protocol version or other game constants,
+    /// or one that didn't open with a `Hello`
+    Incompatible,
 
-        // Clean up rate limit tracking
-        snapshot.last_input_time.remove(&player_id);
+    /// Socket closed before the handshake finished
+    Closed,
+}
 
-        // Clean up input buffer
-        snapshot.player_inputs.remove(&player_id);
+/// Wait for the client's `Hello`, check it is compatible and pick its name
+///
+/// Refused names play under a default name. A client that doesn't say
+/// hello in time, or sends anything else first, can't be checked and is
+/// treated as incompatible.
+async fn receive_hello(
+    stream: &mut Inbound,
+    state: &AppState,
+    player_id: u32,
+) -> Handshake {
+    let deadline = tokio::time::Instant::now() + HELLO_TIMEOUT;
+    loop {
+        let msg = match tokio::time::timeout_at(deadline, stream.next()).await {
+            Ok(Some(Ok(msg))) => msg,
+            Ok(_) => return Handshake::Closed,
+            Err(_) => return Handshake::Incompatible, // No hello in time
+        };
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Binary(_) => return Handshake::Incompatible,
+            _ => continue, // Pings and the like
+        };
+
+        match ClientMessage::parse(&text) {
+            Ok(ClientMessage::Hello {
+                name,
+                client_version,
+                protocol_version,
+                constants_hash,
+            }) => {
+                println!(
+                    "Player {} hello from client {} (protocol {}, constants {:016x})",
+                    player_id, client_version, protocol_version, constants_hash
+                );
+                if protocol_version != PROTOCOL_VERSION || constants_hash != CONSTANTS_HASH {
+                    return Handshake::Incompatible;
+                }
+                return Handshake::Accepted(state.names.validate(&name).unwrap_or_else(|violation| {
+                    println!("Player {} name '{}' refused: {:?}", player_id, name, violation);
+                    default_name(player_id)
+                }));
+            }
+            // No handshake: an old client, or something that isn't one of ours
+            Ok(_) | Err(_) => return Handshake::Incompatible,
+        }
     }
+}
 
-    println!("Player {} disconnected", player_id);
+/// Tell an incompatible client what the server runs, then close the socket
+async fn reject_incompatible(mut sink: SplitSink<WebSocket, Message>) {
+    let message = ServerMessage::IncompatibleClient {
+        protocol_version: PROTOCOL_VERSION,
+        constants_hash: CONSTANTS_HASH,
+    };
+    if let Ok(json) = serde_json::to_string(&message) {
+        let _ = sink.send(Message::Text(json.into())).await;
+    }
+    let _ = sink.close().await;
+}
+
+/// Queue a message for this connection only (errors, pings, pongs)
+fn reply(state: &AppState, player_id: u32, message: &ServerMessage) {
+    if let Ok(json) = serde_json::to_string(message) {
+        state.connections.send_to(player_id, OutboundKind::Control, json);
+    }
+}
+
+/// Answer a message that couldn't be used, within the error rate limit
+///
+/// A client that sends nothing but bad messages is disconnected.
+fn reject_message(
+    state: &AppState,
+    player_id: u32,
+    errors: &mut ErrorLimiter,
+    kind: ClientErrorKind,
+    detail: String,
+) {
+    match errors.bad_message(Instant::now()) {
+        ErrorResponse::Reply => reply(state, player_id, &ServerMessage::Error { kind, detail }),
+        ErrorResponse::Ignore => {}
+        ErrorResponse::Disconnect => {
+            println!("Player {} kicked: {} bad messages in a row", player_id, MAX_CONSECUTIVE_ERRORS);
+            // Writer sends the close frame, then the reader loop ends
+            state.connections.kick(player_id);
+        }
+    }
+}
+
+/// Writer task: drain the outbound queue into the socket
+///
+/// Exits when the queue is closed or the socket rejects a write. With a
+/// simulated network, messages wait in a delay line until the conditioner
+/// releases them. A message only enters the link once the previous one is
+/// through the bandwidth cap, so a backlog builds up in the outbound queue,
+/// where it is coalesced, not in the delay line.
+async fn write_outbound(mut sink: SplitSink<WebSocket, Message>, queue: Arc<OutboundQueue>, link: LinkConditions) {
+    if link.is_perfect() {
+        while let Some(message) = queue.pop().await {
+            if sink.send(message).await.is_err() {
+                break; // Send failed, connection likely closed
+            }
+        }
+        let _ = sink.close().await;
+        return;
+    }
+
+    let mut conditioner = LinkConditioner::new(link);
+    let mut delayed = DelayLine::new();
+    let mut open = true;
+    while open || !delayed.is_empty() {
+        let next_release = delayed.next_release().unwrap_or_else(Instant::now);
+        let link_free_at = conditioner.free_at().unwrap_or_else(Instant::now);
+        let link_free = link_free_at <= Instant::now();
+        tokio::select! {
+            message = queue.pop(), if open && link_free => match message {
+                Some(message) => delayed.push(conditioner.schedule(Instant::now(), message_len(&message)), message),
+                None => open = false, // Deliver what is still in flight, then close
+            },
+            // Link busy: leave the queue alone until it is free
+            _ = tokio::time::sleep_until(link_free_at.into()), if open && !link_free => {}
+            _ = tokio::time::sleep_until(next_release.into()), if !delayed.is_empty() => {
+                while let Some(message) = delayed.pop_ready(Instant::now()) {
+                    if sink.send(message).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+    let _ = sink.close().await;
+}
+
+/// Messages from the socket, delayed by the link conditioner if one is set
+///
+/// A forwarding task holds each message until its release time, so the
+/// reader loop sees the client's traffic as it would arrive over that link.
+fn conditioned_inbound(mut stream: SplitStream<WebSocket>, link: LinkConditions) -> Inbound {
+    if link.is_perfect() {
+        return stream.boxed();
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut conditioner = LinkConditioner::new(link);
+        let mut delayed = DelayLine::new();
+        let mut open = true;
+        while open || !delayed.is_empty() {
+            let next_release = delayed.next_release().unwrap_or_else(Instant::now);
+            tokio::select! {
+                message = stream.next(), if open => match message {
+                    Some(Ok(message)) => {
+                        delayed.push(conditioner.schedule(Instant::now(), message_len(&message)), Ok(message));
+                    }
+                    // Errors and the end of the stream aren't delayed
+                    Some(Err(e)) => {
+                        let _ = tx.send(Err(e));
+                        open = false;
+                    }
+                    None => open = false,
+                },
+                _ = tokio::time::sleep_until(next_release.into()), if !delayed.is_empty() => {
+                    while let Some(message) = delayed.pop_ready(Instant::now()) {
+                        if tx.send(message).is_err() {
+                            return; // Reader loop is gone
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    futures_util::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|message| (message, rx)) }).boxed()
+}
+
+/// Size of a message on the wire (for the bandwidth cap)
+fn message_len(message: &Message) -> usize {
+    match message {
+        Message::Text(text) => text.len(),
+        Message::Binary(bytes) => bytes.len(),
+        _ => 0,
+    }
 }
@@ -0,0 +1,5 @@
+pub mod constants;
+pub mod app;
+pub mod domain;
+pub mod simulation;
+pub mod infrastructure;
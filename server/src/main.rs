@@ -1,12 +1,9 @@
-mod constants;
-mod app;
-mod domain;
-mod simulation;
-mod infrastructure;
-
-use infrastructure::run_server;
+use battlestar_server::app::ServerConfig;
+use battlestar_server::infrastructure::serve;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    run_server("0.0.0.0:3000").await
+    let mut config = ServerConfig::from_env();
+    config.apply_args(std::env::args().skip(1));
+    serve("0.0.0.0:3000", config).await
 }
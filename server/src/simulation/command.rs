@@ -0,0 +1,27 @@
+use battlestar_shared::ClientInput;
+
+/// Commands sent from connection tasks to the game loop
+///
+/// Connection tasks never touch `Game` directly: they push commands into an
+/// mpsc channel and the game loop drains it at the start of every tick.
+#[derive(Debug, Clone)]
+pub enum GameCommand {
+    /// A client connected and said hello (registered before any of its inputs)
+    Connect { player_id: u32, name: String },
+
+    /// One input from a client (player_id already overridden by the server)
+    Input(ClientInput),
+
+    /// New smoothed round trip measured by a connection task
+    Latency { player_id: u32, rtt_ms: f64 },
+
+    /// A client disconnected; its ship and buffered input are removed
+    Disconnect { player_id: u32 },
+}
+
+/// Capacity of the command channel
+///
+/// ~8 ticks worth of 60Hz input for 500 players. Inputs are dropped when the
+/// channel is full (the game loop is hopelessly behind at that point);
+/// connect and disconnect commands wait for room instead.
+pub const COMMAND_CHANNEL_CAPACITY: usize = 4096;
@@ -1,75 +1,678 @@
+use battlestar_shared::network::{IdleAction, SERVER_TICK_MS};
 use battlestar_shared::ServerMessage;
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
 
-use crate::app::AppState;
+use super::command::GameCommand;
+use super::idle::{Activity, IdleConfig};
+use super::input_queue::InputQueue;
+use super::lifecycle::MatchLifecycle;
+use crate::app::{AppState, OutboundKind};
+use crate::domain::Game;
+
+/// Time between two game ticks (20 Hz)
+pub const TICK_INTERVAL: Duration = Duration::from_millis(SERVER_TICK_MS);
+
+/// Message for a single client produced by the simulation
+#[derive(Debug, Clone)]
+pub struct ClientNotice {
+    pub player_id: u32,
+    pub message: ServerMessage,
+
+    /// Close the connection after delivering the message
+    pub disconnect: bool,
+}
+
+/// Game loop state, owned exclusively by the game loop task
+///
+/// Previous architecture shared a `Mutex<GameSnapshot>` between the game
+/// loop and every connection task, so each incoming input contended with
+/// the simulation for the same lock.
+///
+/// Now connection tasks only send `GameCommand`s through an mpsc channel.
+/// The loop drains the channel at the start of each tick and owns `Game`
+/// without any locking.
+pub struct Simulation {
+    /// Core game logic and state
+    pub game: Game,
+
+    /// Inputs received since the last tick, per player
+    pub player_inputs: HashMap<u32, InputQueue>,
+
+    /// Set of currently connected player IDs
+    pub connected_players: HashSet<u32>,
+
+    /// Last non-neutral input per player (idle detection)
+    pub activity: HashMap<u32, Activity>,
+
+    /// Input expiry and idle detection settings
+    pub idle: IdleConfig,
+
+    /// Lobby, countdown, round and results
+    pub lifecycle: MatchLifecycle,
+
+    /// Smoothed round trip per player (lag compensation)
+    pub rtt_ms: HashMap<u32, f64>,
+
+    /// Incoming commands from connection tasks
+    commands: mpsc::Receiver<GameCommand>,
+
+    /// Per-client messages produced this tick
+    notices: Vec<ClientNotice>,
+
+    /// Events for every client produced this tick (deaths, round results)
+    events: Vec<ServerMessage>,
+}
+
+impl Simulation {
+    pub fn new(mut game: Game, commands: mpsc::Receiver<GameCommand>) -> Self {
+        // The lifecycle starts the first round once enough players joined
+        game.round_active = false;
+
+        Self {
+            game,
+            player_inputs: HashMap::new(),
+            connected_players: HashSet::new(),
+            activity: HashMap::new(),
+            idle: IdleConfig::default(),
+            lifecycle: MatchLifecycle::default(),
+            rtt_ms: HashMap::new(),
+            commands,
+            notices: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Apply every command queued since the last tick (never blocks)
+    pub fn drain_commands(&mut self) {
+        while let Ok(command) = self.commands.try_recv() {
+            self.handle_command(command);
+        }
+    }
+
+    /// Apply a single command to the simulation
+    pub fn handle_command(&mut self, command: GameCommand) {
+        match command {
+            GameCommand::Connect { player_id, name } => {
+                self.connected_players.insert(player_id);
+                self.game.names.insert(player_id, name);
+                self.activity.insert(player_id, Activity::new(self.game.tick));
+
+                // Tell the newcomer where the match is (lobby, countdown, ...)
+                self.notices.push(ClientNotice {
+                    player_id,
+                    message: self.lifecycle.status(&self.game, self.active_players()),
+                    disconnect: false,
+                });
+            }
+            GameCommand::Input(input) => {
+                // Ignore inputs that race with a disconnect
+                if !self.connected_players.contains(&input.player_id) {
+                    return;
+                }
+
+                let tick = self.game.tick;
+                if let Some(activity) = self.activity.get_mut(&input.player_id) {
+                    activity.record_input(tick, input.thrust, input.rotate);
+                }
+                self.player_inputs
+                    .entry(input.player_id)
+                    .or_default()
+                    .push(input, tick);
+            }
+            GameCommand::Latency { player_id, rtt_ms } => {
+                if self.connected_players.contains(&player_id) {
+                    self.rtt_ms.insert(player_id, rtt_ms);
+                }
+            }
+            GameCommand::Disconnect { player_id } => {
+                self.connected_players.remove(&player_id);
+                self.rtt_ms.remove(&player_id);
+                self.game.remove_player(player_id);
+                self.game.names.remove(&player_id);
+                self.player_inputs.remove(&player_id);
+                self.activity.remove(&player_id);
+            }
+        }
+    }
+
+    /// Run one tick and build the message to broadcast
+    ///
+    /// 1. Drains queued commands
+    /// 2. Replays each player's buffered inputs in order (stale input expires)
+    /// 3. Simulates one tick of physics
+    /// 4. Queues death events and marks players idle after the configured timeout
+    /// 5. Advances the match lifecycle and queues its messages
+    /// 6. Returns a full state every `full_state_interval` ticks, a delta otherwise
+    pub fn step(&mut self, dt: f32) -> ServerMessage {
+        self.drain_commands();
+
+        // Apply every buffered input, each for its slice of the tick
+        let tick = self.game.tick;
+        let expiry_ticks = self.idle.input_expiry_ticks();
+        for (player_id, queue) in self.player_inputs.iter_mut() {
+            let batch = queue.take_tick(dt, tick, expiry_ticks);
+
+            // Idle players' input is discarded until they become active again
+            // (otherwise a neutral input would respawn a parked ship).
+            // Outside a round nobody flies.
+            if self.activity.get(player_id).is_some_and(|a| a.idle) || !self.lifecycle.is_playing() {
+                continue;
+            }
+
+            for (input, input_dt) in batch {
+                self.game.apply_input(input, input_dt);
+            }
+        }
+
+        // Tick simulation
+        self.game.tick(dt);
+
+        for death in &self.game.deaths {
+            self.events.push(ServerMessage::ShipDestroyed {
+                ship_id: death.ship_id,
+                source: death.source,
+            });
+        }
+
+        self.detect_idle_players();
+
+        let players = self.active_players();
+        let messages = self.lifecycle.update(&mut self.game, players);
+        self.events.extend(messages);
+
+        // Determine if this is a full state broadcast
+        let is_full_state = self.game.tick % self.game.full_state_interval == 0;
+
+        // Send full state or delta based on tick
+        if is_full_state {
+            // Full state: includes asteroids, all ship colors
+            ServerMessage::GameState(self.game.to_network_state())
+        } else {
+            // Delta state: only ship positions/velocities (90% smaller)
+            ServerMessage::DeltaState(self.game.to_delta_state())
+        }
+    }
+}
+
+impl Simulation {
+    /// Connected players that aren't idle (they count towards starting a match)
+    fn active_players(&self) -> u32 {
+        self.connected_players
+            .iter()
+            .filter(|id| self.activity.get(id).is_none_or(|a| !a.idle))
+            .count() as u32
+    }
+
+    /// Mark players idle once they exceed the idle timeout
+    fn detect_idle_players(&mut self) {
+        let Some(timeout_ticks) = self.idle.idle_timeout_ticks() else {
+            return;
+        };
+        let timeout_secs = self.idle.idle_timeout.map_or(0, |t| t.as_secs() as u32);
+
+        // Clients only send input during a round, so waiting in the lobby,
+        // countdown or results doesn't count towards the timeout
+        let tick = self.game.tick;
+        if !self.lifecycle.is_playing() {
+            for activity in self.activity.values_mut() {
+                *activity = Activity::new(tick);
+            }
+            return;
+        }
+
+        for (player_id, activity) in self.activity.iter_mut() {
+            if !activity.check_idle(tick, timeout_ticks) {
+                continue;
+            }
+
+            // Parked and kicked players both leave the world; a parked
+            // player's next non-neutral input spawns a new ship
+            self.game.remove_player(*player_id);
+
+            self.notices.push(ClientNotice {
+                player_id: *player_id,
+                message: ServerMessage::Idle {
+                    action: self.idle.action,
+                    timeout_secs,
+                },
+                disconnect: self.idle.action == IdleAction::Kicked,
+            });
+        }
+    }
+
+    /// Per-client messages produced since the last call
+    pub fn take_notices(&mut self) -> Vec<ClientNotice> {
+        std::mem::take(&mut self.notices)
+    }
+
+    /// Messages for every client produced since the last call
+    pub fn take_events(&mut self) -> Vec<ServerMessage> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// What paces the game loop
+pub enum TickSource {
+    /// Real time: one tick every `TICK_INTERVAL`
+    Interval,
+
+    /// One tick per request from a `TickDriver` (integration tests)
+    Manual(mpsc::Receiver<oneshot::Sender<u64>>),
+}
+
+/// Steps a game loop started with `TickSource::Manual`
+///
+/// Each tick still simulates `TICK_INTERVAL` of game time, however long
+/// the caller waits between steps.
+#[derive(Clone)]
+pub struct TickDriver {
+    requests: mpsc::Sender<oneshot::Sender<u64>>,
+}
+
+impl TickDriver {
+    /// Driver and the tick source to hand to `spawn_game_loop`
+    pub fn new() -> (Self, TickSource) {
+        let (tx, rx) = mpsc::channel(1);
+        (Self { requests: tx }, TickSource::Manual(rx))
+    }
+
+    /// Run one tick; returns its number once its messages are queued for
+    /// every client (None if the game loop is gone)
+    pub async fn step(&self) -> Option<u64> {
+        let (done, tick) = oneshot::channel();
+        self.requests.send(done).await.ok()?;
+        tick.await.ok()
+    }
+}
 
 /// Spawn the game loop as a background task
 ///
-/// Runs at 20 Hz (50ms per tick) and:
-/// 1. Collects all player inputs
-/// 2. Applies inputs to game state
+/// Runs at 20 Hz (50ms per tick), or one tick per `TickDriver::step` with
+/// `TickSource::Manual`, and:
+/// 1. Drains commands (connects, inputs, disconnects) from the channel
+/// 2. Replays each player's buffered inputs in order
 /// 3. Simulates one tick of physics
-/// 4. Broadcasts delta updates to all clients
+/// 4. Broadcasts delta updates and events (deaths, match lifecycle) to all clients
+/// 5. Delivers per-client notices (idle warnings, kicks)
 ///
 /// Key optimizations:
-/// - Single lock acquisition per tick instead of 4+
+/// - The loop owns `Game`: no lock is taken per input or per tick
 /// - Delta encoding: 90% bandwidth reduction
 /// - Full state fallback every N ticks (100 = 5 seconds)
-///
-/// Delta pattern:
-/// ```ignore
-/// let message = {
-///     let mut snapshot = state.game.lock().await;  // Single lock
-///     // ... tick simulation ...
-///     if is_full_state_tick {
-///         ServerMessage::GameState(snapshot.game.to_network_state())
-///     } else {
-///         ServerMessage::DeltaState(snapshot.game.to_delta_state())
-///     }
-/// }; // Lock released here
-/// broadcast(message); // Outside lock
-/// ```
-pub fn spawn_game_loop(state: Arc<AppState>) {
+pub fn spawn_game_loop(state: Arc<AppState>, mut simulation: Simulation, source: TickSource) {
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_millis(50));
+        match source {
+            TickSource::Interval => {
+                let mut interval = tokio::time::interval(TICK_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    run_tick(&state, &mut simulation);
+                }
+            }
+            TickSource::Manual(mut requests) => {
+                while let Some(done) = requests.recv().await {
+                    let tick = run_tick(&state, &mut simulation);
+                    let _ = done.send(tick);
+                }
+            }
+        }
+    });
+}
+
+/// Step the simulation and queue what it produced for the clients
+fn run_tick(state: &AppState, simulation: &mut Simulation) -> u64 {
+    let dt = TICK_INTERVAL.as_secs_f32(); // 50ms = 1/20 second
+    let message = simulation.step(dt);
+    state.tick.store(simulation.game.tick, Ordering::Relaxed); // Stamped on pongs
+
+    // Pushes into each client's bounded queue; slow clients skip deltas until resynced
+    let kind = match message {
+        ServerMessage::GameState(_) => OutboundKind::FullState,
+        _ => OutboundKind::Delta,
+    };
+    if let Ok(payload) = serde_json::to_string(&message) {
+        state.connections.broadcast(kind, payload);
+    }
 
-        loop {
-            interval.tick().await;
+    // Clients whose deltas were dropped catch up with the whole state
+    let behind = state.connections.needing_full_state();
+    if !behind.is_empty() {
+        let full_state = ServerMessage::GameState(simulation.game.to_network_state());
+        if let Ok(payload) = serde_json::to_string(&full_state) {
+            for player_id in behind {
+                state.connections.send_to(player_id, OutboundKind::FullState, payload.clone());
+            }
+        }
+    }
 
-            // Single lock acquisition for entire tick
-            let message = {
-                let mut snapshot = state.game.lock().await;
+    for event in simulation.take_events() {
+        if let Ok(payload) = serde_json::to_string(&event) {
+            state.connections.broadcast(OutboundKind::Control, payload);
+        }
+    }
 
-                // Collect all inputs (already in snapshot, no extra lock)
-                let inputs: Vec<_> = snapshot.player_inputs.values().cloned().collect();
+    for notice in simulation.take_notices() {
+        if let Ok(payload) = serde_json::to_string(&notice.message) {
+            state.connections.send_to(notice.player_id, OutboundKind::Control, payload);
+        }
+        if notice.disconnect {
+            state.connections.kick(notice.player_id);
+        }
+    }
 
-                // Apply all inputs
-                let dt = 1.0 / 20.0; // 50ms = 1/20 second
-                for input in inputs {
-                    snapshot.game.apply_input(input, dt);
-                }
+    simulation.game.tick
+}
 
-                // Tick simulation
-                snapshot.game.tick(dt);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RoundClock;
+    use crate::simulation::MatchConfig;
+    use battlestar_shared::{network::Winner, ClientInput, Vec2};
 
-                // Determine if this is a full state broadcast
-                let is_full_state = snapshot.game.tick % snapshot.game.full_state_interval == 0;
+    /// Simulation with a round already in progress (no lobby or countdown)
+    fn simulation() -> (mpsc::Sender<GameCommand>, Simulation) {
+        let (tx, rx) = mpsc::channel(16);
+        let mut sim = Simulation::new(Game::new(), rx);
+        sim.lifecycle = MatchLifecycle::new(MatchConfig {
+            min_players: 0,
+            countdown: Duration::ZERO,
+            results: Duration::ZERO,
+        });
+        sim.step(0.05);
+        sim.take_events();
+        (tx, sim)
+    }
 
-                // Send full state or delta based on tick
-                if is_full_state {
-                    // Full state: includes asteroids, all ship colors
-                    ServerMessage::GameState(snapshot.game.to_network_state())
-                } else {
-                    // Delta state: only ship positions/velocities (90% smaller)
-                    ServerMessage::DeltaState(snapshot.game.to_delta_state())
-                }
-            }; // Lock is released here
+    /// Idle warnings and kicks (skips the match status sent on connect)
+    fn idle_notices(sim: &mut Simulation) -> Vec<ClientNotice> {
+        sim.take_notices()
+            .into_iter()
+            .filter(|notice| matches!(notice.message, ServerMessage::Idle { .. }))
+            .collect()
+    }
 
-            // Broadcast outside the lock (reduces lock duration)
-            if let Ok(payload) = serde_json::to_string(&message) {
-                // Ignore send errors (no subscribers is OK)
-                let _ = state.broadcaster.send(payload);
-            }
+    fn connect(player_id: u32) -> GameCommand {
+        GameCommand::Connect {
+            player_id,
+            name: format!("Player {}", player_id),
         }
-    });
+    }
+
+    fn input(player_id: u32, thrust: f32) -> ClientInput {
+        ClientInput {
+            player_id,
+            thrust,
+            rotate: 0.0,
+        }
+    }
+
+    fn rotate_input(player_id: u32, rotate: f32) -> ClientInput {
+        ClientInput {
+            player_id,
+            thrust: 0.0,
+            rotate,
+        }
+    }
+
+    #[test]
+    fn test_commands_are_applied_on_step() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+
+        sim.step(0.05);
+
+        assert!(sim.connected_players.contains(&1));
+        assert!(sim.game.ships.contains_key(&1));
+    }
+
+    #[test]
+    fn test_input_without_connect_is_ignored() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(GameCommand::Input(input(7, 1.0))).unwrap();
+
+        sim.step(0.05);
+
+        assert!(sim.player_inputs.is_empty());
+        assert!(!sim.game.ships.contains_key(&7));
+    }
+
+    #[test]
+    fn test_disconnect_removes_ship_and_input() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+        sim.step(0.05);
+
+        tx.try_send(GameCommand::Disconnect { player_id: 1 }).unwrap();
+        sim.step(0.05);
+
+        assert!(!sim.connected_players.contains(&1));
+        assert!(!sim.player_inputs.contains_key(&1));
+        assert!(!sim.game.ships.contains_key(&1));
+    }
+
+    #[test]
+    fn test_latency_tracked_while_connected() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(GameCommand::Latency { player_id: 1, rtt_ms: 80.0 }).unwrap();
+        tx.try_send(connect(2)).unwrap();
+        tx.try_send(GameCommand::Latency { player_id: 2, rtt_ms: 120.0 }).unwrap();
+        sim.step(0.05);
+
+        assert!(!sim.rtt_ms.contains_key(&1), "Not connected");
+        assert_eq!(sim.rtt_ms.get(&2), Some(&120.0));
+
+        tx.try_send(GameCommand::Disconnect { player_id: 2 }).unwrap();
+        sim.step(0.05);
+        assert!(sim.rtt_ms.is_empty());
+    }
+
+    #[test]
+    fn test_short_tap_between_ticks_is_applied() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+
+        // Tap rotate for one 60Hz frame, then release, all before the tick
+        tx.try_send(GameCommand::Input(rotate_input(1, 1.0))).unwrap();
+        tx.try_send(GameCommand::Input(rotate_input(1, 0.0))).unwrap();
+        tx.try_send(GameCommand::Input(rotate_input(1, 0.0))).unwrap();
+
+        sim.step(0.05);
+
+        // Only a third of the tick was spent rotating
+        let rotation = sim.game.ships[&1].rotation;
+        let expected = -sim.game.constants.rotation_speed * 0.05 / 3.0;
+        assert!(
+            (rotation - expected).abs() < 1e-5,
+            "Tap should rotate for its slice of the tick (got {}, expected {})",
+            rotation,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_release_is_not_lost_behind_later_input() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(rotate_input(1, 1.0))).unwrap();
+        sim.step(0.05);
+        let after_first = sim.game.ships[&1].rotation;
+
+        // Release then tap the other way within one tick: net rotation cancels
+        tx.try_send(GameCommand::Input(rotate_input(1, 1.0))).unwrap();
+        tx.try_send(GameCommand::Input(rotate_input(1, -1.0))).unwrap();
+        sim.step(0.05);
+
+        let after_second = sim.game.ships[&1].rotation;
+        assert!((after_second - after_first).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_stale_input_stops_thrusting() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+        sim.step(0.05);
+
+        // Client goes quiet while holding thrust: the input expires
+        let expiry = sim.idle.input_expiry_ticks();
+        for _ in 0..=expiry {
+            sim.step(0.05);
+        }
+        let speed_before = sim.game.ships[&1].velocity.length();
+        sim.step(0.05);
+        let speed_after = sim.game.ships[&1].velocity.length();
+
+        assert!(speed_after < speed_before, "Ship should coast (drag only) once input expires");
+    }
+
+    #[test]
+    fn test_idle_player_is_parked_and_notified() {
+        let (tx, mut sim) = simulation();
+        sim.idle.idle_timeout = Some(TICK_INTERVAL * 10);
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+        sim.step(0.05);
+        assert!(sim.game.ships.contains_key(&1));
+
+        for _ in 0..10 {
+            // Neutral input keeps arriving but does not count as activity
+            tx.try_send(GameCommand::Input(input(1, 0.0))).unwrap();
+            sim.step(0.05);
+        }
+
+        assert!(!sim.game.ships.contains_key(&1), "Idle ship should be parked");
+        let notices = idle_notices(&mut sim);
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].player_id, 1);
+        assert!(!notices[0].disconnect);
+        assert!(matches!(
+            notices[0].message,
+            ServerMessage::Idle { action: IdleAction::Parked, .. }
+        ));
+
+        // Neutral input does not bring the ship back, thrust does
+        tx.try_send(GameCommand::Input(input(1, 0.0))).unwrap();
+        sim.step(0.05);
+        assert!(!sim.game.ships.contains_key(&1));
+
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+        sim.step(0.05);
+        assert!(sim.game.ships.contains_key(&1));
+    }
+
+    #[test]
+    fn test_idle_kick_requests_disconnect() {
+        let (tx, mut sim) = simulation();
+        sim.idle.idle_timeout = Some(TICK_INTERVAL * 5);
+        sim.idle.action = IdleAction::Kicked;
+        tx.try_send(connect(1)).unwrap();
+
+        for _ in 0..5 {
+            sim.step(0.05);
+        }
+
+        let notices = idle_notices(&mut sim);
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].disconnect);
+    }
+
+    #[test]
+    fn test_deaths_are_broadcast_as_events() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 0.0))).unwrap();
+        sim.step(0.05);
+        assert!(sim.take_events().is_empty());
+
+        // Ram an asteroid with no shield and almost no hull left
+        let asteroid = sim.game.asteroids[0].position;
+        let ship = sim.game.ships.get_mut(&1).unwrap();
+        ship.position = asteroid;
+        ship.velocity = Vec2::new(300.0, 0.0);
+        ship.shield = 0.0;
+        ship.health = 1.0;
+        sim.step(0.05);
+
+        let events = sim.take_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], ServerMessage::ShipDestroyed { ship_id: 1, .. }));
+    }
+
+    #[test]
+    fn test_round_end_is_broadcast_as_event() {
+        let (tx, mut sim) = simulation();
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 0.0))).unwrap();
+        sim.game.round = RoundClock::new(0.1);
+        sim.step(0.05);
+        assert!(sim.take_events().is_empty());
+
+        sim.step(0.05);
+
+        let events = sim.take_events();
+        let ServerMessage::RoundEnded(results) = &events[0] else {
+            panic!("Expected RoundEnded, got {:?}", events[0]);
+        };
+        assert_eq!(results.winner, Winner::Player { id: 1 });
+    }
+
+    #[test]
+    fn test_lobby_holds_players_until_enough_join() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut sim = Simulation::new(Game::new(), rx);
+        sim.lifecycle.config.min_players = 2;
+        sim.lifecycle.config.countdown = Duration::ZERO;
+
+        tx.try_send(connect(1)).unwrap();
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+        sim.step(0.05);
+
+        assert!(sim.game.ships.is_empty(), "Nobody flies in the lobby");
+        let notices = sim.take_notices();
+        assert!(matches!(
+            notices[0].message,
+            ServerMessage::MatchWaiting { players: 1, min_players: 2 }
+        ));
+        assert!(matches!(
+            sim.take_events()[..],
+            [ServerMessage::MatchWaiting { players: 1, min_players: 2 }]
+        ));
+
+        tx.try_send(connect(2)).unwrap();
+        sim.step(0.05);
+        assert!(sim.lifecycle.is_playing());
+        assert!(matches!(sim.take_events().last(), Some(ServerMessage::MatchStarted { .. })));
+
+        tx.try_send(GameCommand::Input(input(1, 1.0))).unwrap();
+        sim.step(0.05);
+        assert!(sim.game.ships.contains_key(&1));
+    }
+
+    #[test]
+    fn test_waiting_in_lobby_does_not_count_as_idle() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut sim = Simulation::new(Game::new(), rx);
+        sim.lifecycle.config.min_players = 2;
+        sim.lifecycle.config.countdown = Duration::ZERO;
+        sim.idle.idle_timeout = Some(TICK_INTERVAL * 5);
+
+        tx.try_send(connect(1)).unwrap();
+        for _ in 0..20 {
+            sim.step(0.05);
+        }
+        assert!(idle_notices(&mut sim).is_empty());
+
+        tx.try_send(connect(2)).unwrap();
+        sim.step(0.05);
+        assert!(sim.lifecycle.is_playing(), "Both players should count towards the start");
+        assert!(idle_notices(&mut sim).is_empty());
+    }
 }
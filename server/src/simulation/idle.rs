@@ -0,0 +1,121 @@
+use battlestar_shared::network::IdleAction;
+use std::time::Duration;
+
+use super::game_loop::TICK_INTERVAL;
+
+/// Input expiry and idle (AFK) detection settings
+#[derive(Debug, Clone, Copy)]
+pub struct IdleConfig {
+    /// Held input older than this is treated as neutral (the ship coasts)
+    pub input_expiry: Duration,
+
+    /// Time without any non-neutral input before a player is marked idle
+    /// (None disables idle detection)
+    pub idle_timeout: Option<Duration>,
+
+    /// What happens to an idle player
+    pub action: IdleAction,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            input_expiry: Duration::from_millis(250),
+            idle_timeout: Some(Duration::from_secs(120)),
+            action: IdleAction::Parked,
+        }
+    }
+}
+
+impl IdleConfig {
+    /// Input expiry in game ticks
+    pub fn input_expiry_ticks(&self) -> u64 {
+        duration_to_ticks(self.input_expiry)
+    }
+
+    /// Idle timeout in game ticks
+    pub fn idle_timeout_ticks(&self) -> Option<u64> {
+        self.idle_timeout.map(duration_to_ticks)
+    }
+}
+
+/// Convert a wall-clock duration to a number of ticks (rounded up)
+pub fn duration_to_ticks(duration: Duration) -> u64 {
+    let tick_ms = TICK_INTERVAL.as_millis();
+    duration.as_millis().div_ceil(tick_ms) as u64
+}
+
+/// Per-player activity tracking for idle detection
+#[derive(Debug, Clone, Copy)]
+pub struct Activity {
+    /// Tick of the last non-neutral input (or of the connection)
+    pub last_active_tick: u64,
+
+    /// Whether the player has been marked idle
+    pub idle: bool,
+}
+
+impl Activity {
+    pub fn new(tick: u64) -> Self {
+        Self {
+            last_active_tick: tick,
+            idle: false,
+        }
+    }
+
+    /// Record an input; only non-neutral input counts as activity
+    ///
+    /// Returns true if this input brought the player back from idle.
+    pub fn record_input(&mut self, tick: u64, thrust: f32, rotate: f32) -> bool {
+        if thrust == 0.0 && rotate == 0.0 {
+            return false;
+        }
+
+        self.last_active_tick = tick;
+        std::mem::replace(&mut self.idle, false)
+    }
+
+    /// Whether the player just crossed the idle timeout
+    pub fn check_idle(&mut self, tick: u64, timeout_ticks: u64) -> bool {
+        if self.idle || tick.saturating_sub(self.last_active_tick) < timeout_ticks {
+            return false;
+        }
+
+        self.idle = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_to_ticks_rounds_up() {
+        assert_eq!(duration_to_ticks(Duration::from_millis(50)), 1);
+        assert_eq!(duration_to_ticks(Duration::from_millis(51)), 2);
+        assert_eq!(duration_to_ticks(Duration::from_secs(1)), 20);
+    }
+
+    #[test]
+    fn test_neutral_input_is_not_activity() {
+        let mut activity = Activity::new(0);
+        activity.record_input(10, 0.0, 0.0);
+        assert_eq!(activity.last_active_tick, 0);
+
+        activity.record_input(12, 1.0, 0.0);
+        assert_eq!(activity.last_active_tick, 12);
+    }
+
+    #[test]
+    fn test_idle_fires_once_then_resets_on_activity() {
+        let mut activity = Activity::new(0);
+
+        assert!(!activity.check_idle(19, 20));
+        assert!(activity.check_idle(20, 20));
+        assert!(!activity.check_idle(21, 20), "Idle should only fire once");
+
+        assert!(activity.record_input(25, 0.0, 1.0), "Activity should clear idle");
+        assert!(!activity.idle);
+    }
+}
@@ -0,0 +1,147 @@
+use battlestar_shared::ClientInput;
+use std::collections::VecDeque;
+
+/// Maximum inputs buffered per player between two ticks
+///
+/// The client sends at ~60Hz and the server ticks at 20Hz, so ~3 inputs
+/// arrive per tick. The cap bounds memory for a client that floods us;
+/// the oldest inputs are dropped first.
+pub const MAX_QUEUED_INPUTS: usize = 8;
+
+/// Per-player queue of inputs received since the last tick
+///
+/// Previously only the latest `ClientInput` was kept, so two out of three
+/// 60Hz inputs were overwritten and a quick tap could vanish entirely.
+/// Now every input is replayed in order, each for an equal slice of the
+/// tick, which matches what the client predicted frame by frame.
+///
+/// A held input expires: once nothing has arrived for `expiry_ticks`, the
+/// player is treated as neutral instead of replaying the last input forever
+/// (e.g. a backgrounded tab that was holding W).
+#[derive(Debug, Clone, Default)]
+pub struct InputQueue {
+    pending: VecDeque<ClientInput>,
+    last: Option<ClientInput>,
+    last_received_tick: u64,
+}
+
+impl InputQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffer an input until the next tick (drops the oldest when full)
+    pub fn push(&mut self, input: ClientInput, tick: u64) {
+        if self.pending.len() >= MAX_QUEUED_INPUTS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(input);
+        self.last_received_tick = tick;
+    }
+
+    /// Number of inputs waiting for the next tick
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Inputs to apply this tick, each paired with its share of `dt`
+    ///
+    /// Queued inputs split the tick evenly, in arrival order. With nothing
+    /// queued, the last input is held for the whole tick until it is older
+    /// than `expiry_ticks`, after which nothing is applied (the ship coasts).
+    pub fn take_tick(&mut self, dt: f32, current_tick: u64, expiry_ticks: u64) -> Vec<(ClientInput, f32)> {
+        if self.pending.is_empty() {
+            if current_tick.saturating_sub(self.last_received_tick) > expiry_ticks {
+                self.last = None;
+            }
+            return self.last.clone().map(|input| vec![(input, dt)]).unwrap_or_default();
+        }
+
+        let slice = dt / self.pending.len() as f32;
+        let batch: Vec<_> = self.pending.drain(..).map(|input| (input, slice)).collect();
+        self.last = batch.last().map(|(input, _)| input.clone());
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(thrust: f32, rotate: f32) -> ClientInput {
+        ClientInput {
+            player_id: 1,
+            thrust,
+            rotate,
+        }
+    }
+
+    #[test]
+    fn test_inputs_split_tick_in_order() {
+        let mut queue = InputQueue::new();
+        queue.push(input(0.0, 1.0), 0);
+        queue.push(input(0.0, 0.0), 0);
+        queue.push(input(1.0, 0.0), 0);
+
+        let batch = queue.take_tick(0.06, 0, 5);
+
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch[0].0.rotate, 1.0);
+        assert_eq!(batch[2].0.thrust, 1.0);
+        for (_, dt) in &batch {
+            assert!((dt - 0.02).abs() < 1e-6);
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_empty_queue_holds_last_input() {
+        let mut queue = InputQueue::new();
+        queue.push(input(1.0, 0.0), 0);
+        queue.take_tick(0.05, 0, 5);
+
+        let batch = queue.take_tick(0.05, 1, 5);
+
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].0.thrust, 1.0);
+        assert_eq!(batch[0].1, 0.05);
+    }
+
+    #[test]
+    fn test_no_input_yet_yields_nothing() {
+        let mut queue = InputQueue::new();
+        assert!(queue.take_tick(0.05, 0, 5).is_empty());
+    }
+
+    #[test]
+    fn test_queue_is_capped() {
+        let mut queue = InputQueue::new();
+        for i in 0..(MAX_QUEUED_INPUTS + 2) {
+            queue.push(input(i as f32, 0.0), 0);
+        }
+
+        assert_eq!(queue.len(), MAX_QUEUED_INPUTS);
+
+        // Oldest inputs were dropped
+        let batch = queue.take_tick(0.05, 0, 5);
+        assert_eq!(batch[0].0.thrust, 2.0);
+    }
+
+    #[test]
+    fn test_held_input_expires() {
+        let mut queue = InputQueue::new();
+        queue.push(input(1.0, 0.0), 10);
+        queue.take_tick(0.05, 10, 5);
+
+        // Still held within the expiry window
+        assert_eq!(queue.take_tick(0.05, 15, 5).len(), 1);
+
+        // Client went quiet: the held thrust is no longer replayed
+        assert!(queue.take_tick(0.05, 16, 5).is_empty());
+        assert!(queue.take_tick(0.05, 17, 5).is_empty());
+    }
+}
@@ -0,0 +1,287 @@
+use battlestar_shared::network::RoundResults;
+use battlestar_shared::ServerMessage;
+use std::time::Duration;
+
+use super::idle::duration_to_ticks;
+use crate::constants::*;
+use crate::domain::Game;
+
+/// Player threshold and phase durations of a match
+#[derive(Debug, Clone, Copy)]
+pub struct MatchConfig {
+    /// Players needed before the countdown starts
+    pub min_players: u32,
+
+    /// Countdown between enough players joining and the round starting
+    pub countdown: Duration,
+
+    /// How long the results are shown before the match resets
+    pub results: Duration,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            min_players: MIN_PLAYERS,
+            countdown: Duration::from_secs_f32(COUNTDOWN_DURATION),
+            results: Duration::from_secs_f32(RESULTS_DURATION),
+        }
+    }
+}
+
+/// Where the match is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchPhase {
+    /// Not enough players: connected players wait in the lobby
+    Waiting,
+
+    /// Enough players: the round starts at tick `ends_at`
+    Countdown { ends_at: u64 },
+
+    /// A round is being played
+    InProgress,
+
+    /// Round over: results are shown until tick `ends_at`, then the match resets
+    Results { ends_at: u64 },
+}
+
+/// Match state machine: waiting → countdown → in progress → results → reset
+///
+/// Ships only fly while a round is in progress. Every phase change (and
+/// every second of the countdown, and every player joining or leaving the
+/// lobby) produces a message for all clients.
+#[derive(Debug, Clone)]
+pub struct MatchLifecycle {
+    pub config: MatchConfig,
+    phase: MatchPhase,
+
+    /// Last value sent to clients (players waiting, or countdown seconds),
+    /// so only changes are broadcast
+    announced: Option<u32>,
+
+    /// Results of the last round, for clients connecting during the results
+    results: Option<RoundResults>,
+}
+
+impl MatchLifecycle {
+    pub fn new(config: MatchConfig) -> Self {
+        Self {
+            config,
+            phase: MatchPhase::Waiting,
+            announced: None,
+            results: None,
+        }
+    }
+
+    pub fn phase(&self) -> MatchPhase {
+        self.phase
+    }
+
+    /// Whether player input moves ships
+    pub fn is_playing(&self) -> bool {
+        self.phase == MatchPhase::InProgress
+    }
+
+    /// Advance the state machine after a game tick
+    ///
+    /// `players` is the number of active (connected, non-idle) players.
+    /// Starts the round on `game` when the countdown ends and picks up its
+    /// results when it ends. Returns the messages to broadcast.
+    pub fn update(&mut self, game: &mut Game, players: u32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        while let Some(message) = self.advance(game, players) {
+            messages.push(message);
+        }
+        messages
+    }
+
+    /// Message describing the current phase, for a client that just connected
+    pub fn status(&self, game: &Game, players: u32) -> ServerMessage {
+        match self.phase {
+            MatchPhase::Waiting => self.waiting_message(players),
+            MatchPhase::Countdown { ends_at } => countdown_message(game.tick, ends_at),
+            MatchPhase::InProgress => started_message(game),
+            MatchPhase::Results { .. } => match &self.results {
+                Some(results) => ServerMessage::RoundEnded(results.clone()),
+                None => self.waiting_message(players),
+            },
+        }
+    }
+
+    /// Take at most one step; None once nothing changes this tick
+    fn advance(&mut self, game: &mut Game, players: u32) -> Option<ServerMessage> {
+        let tick = game.tick;
+        let enough_players = players >= self.config.min_players;
+
+        match self.phase {
+            MatchPhase::Waiting if enough_players => Some(self.start_countdown(tick)),
+            MatchPhase::Waiting => self
+                .announce(players)
+                .then(|| self.waiting_message(players)),
+            MatchPhase::Countdown { .. } if !enough_players => {
+                self.phase = MatchPhase::Waiting;
+                self.announced = Some(players);
+                Some(self.waiting_message(players))
+            }
+            MatchPhase::Countdown { ends_at } if tick >= ends_at => {
+                self.phase = MatchPhase::InProgress;
+                self.announced = None;
+                self.results = None;
+                game.start_round();
+                Some(started_message(game))
+            }
+            MatchPhase::Countdown { ends_at } => {
+                let secs = countdown_secs(tick, ends_at);
+                self.announce(secs).then(|| countdown_message(tick, ends_at))
+            }
+            MatchPhase::InProgress => {
+                let results = game.round_results.take()?;
+                self.phase = MatchPhase::Results {
+                    ends_at: tick + duration_to_ticks(self.config.results),
+                };
+                self.results = Some(results.clone());
+                Some(ServerMessage::RoundEnded(results))
+            }
+            MatchPhase::Results { ends_at } if tick >= ends_at => {
+                // Reset: back to the lobby (or straight into the next countdown)
+                self.phase = MatchPhase::Waiting;
+                self.announced = Some(players);
+                Some(self.waiting_message(players))
+            }
+            MatchPhase::Results { .. } => None,
+        }
+    }
+
+    fn start_countdown(&mut self, tick: u64) -> ServerMessage {
+        let ends_at = tick + duration_to_ticks(self.config.countdown);
+        self.phase = MatchPhase::Countdown { ends_at };
+        self.announced = Some(countdown_secs(tick, ends_at));
+        countdown_message(tick, ends_at)
+    }
+
+    /// Record `value` as sent; true if it differs from the last one
+    fn announce(&mut self, value: u32) -> bool {
+        self.announced.replace(value) != Some(value)
+    }
+
+    fn waiting_message(&self, players: u32) -> ServerMessage {
+        ServerMessage::MatchWaiting {
+            players,
+            min_players: self.config.min_players,
+        }
+    }
+}
+
+impl Default for MatchLifecycle {
+    fn default() -> Self {
+        Self::new(MatchConfig::default())
+    }
+}
+
+/// Whole seconds left until `ends_at` (rounded up)
+fn countdown_secs(tick: u64, ends_at: u64) -> u32 {
+    let ticks_per_sec = duration_to_ticks(Duration::from_secs(1));
+    ends_at.saturating_sub(tick).div_ceil(ticks_per_sec) as u32
+}
+
+fn countdown_message(tick: u64, ends_at: u64) -> ServerMessage {
+    ServerMessage::MatchCountdown {
+        secs: countdown_secs(tick, ends_at),
+    }
+}
+
+fn started_message(game: &Game) -> ServerMessage {
+    ServerMessage::MatchStarted {
+        mode: game.mode.kind(),
+        remaining_secs: game.round.remaining_secs().ceil() as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RoundClock;
+
+    fn lifecycle() -> MatchLifecycle {
+        MatchLifecycle::new(MatchConfig {
+            min_players: 2,
+            countdown: Duration::from_secs(2),
+            results: Duration::from_secs(1),
+        })
+    }
+
+    /// Tick the game `ticks` times, collecting lifecycle messages
+    fn run(lifecycle: &mut MatchLifecycle, game: &mut Game, players: u32, ticks: u32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        for _ in 0..ticks {
+            game.tick(0.05);
+            messages.extend(lifecycle.update(game, players));
+        }
+        messages
+    }
+
+    #[test]
+    fn test_waits_for_enough_players() {
+        let mut lifecycle = lifecycle();
+        let mut game = Game::new();
+
+        let messages = run(&mut lifecycle, &mut game, 1, 10);
+
+        assert_eq!(lifecycle.phase(), MatchPhase::Waiting);
+        assert!(!lifecycle.is_playing());
+        assert_eq!(messages.len(), 1, "Lobby is announced once per change");
+        assert!(matches!(messages[0], ServerMessage::MatchWaiting { players: 1, min_players: 2 }));
+    }
+
+    #[test]
+    fn test_countdown_then_round_starts() {
+        let mut lifecycle = lifecycle();
+        let mut game = Game::new();
+        game.round_active = false;
+
+        let messages = run(&mut lifecycle, &mut game, 2, 1);
+        assert!(matches!(messages[..], [ServerMessage::MatchCountdown { secs: 2 }]));
+
+        let messages = run(&mut lifecycle, &mut game, 2, 40);
+        assert!(matches!(
+            messages[..],
+            [ServerMessage::MatchCountdown { secs: 1 }, ServerMessage::MatchStarted { .. }]
+        ));
+        assert!(lifecycle.is_playing());
+        assert!(game.round_active);
+    }
+
+    #[test]
+    fn test_countdown_aborts_when_a_player_leaves() {
+        let mut lifecycle = lifecycle();
+        let mut game = Game::new();
+
+        run(&mut lifecycle, &mut game, 2, 5);
+        let messages = run(&mut lifecycle, &mut game, 1, 1);
+
+        assert_eq!(lifecycle.phase(), MatchPhase::Waiting);
+        assert!(matches!(messages[..], [ServerMessage::MatchWaiting { players: 1, .. }]));
+    }
+
+    #[test]
+    fn test_results_then_reset() {
+        let mut lifecycle = lifecycle();
+        let mut game = Game::new();
+        game.spawn_player(1);
+        game.round = RoundClock::new(1.0);
+        run(&mut lifecycle, &mut game, 2, 41);
+        assert!(lifecycle.is_playing());
+
+        let messages = run(&mut lifecycle, &mut game, 2, 20);
+        assert!(matches!(messages[..], [ServerMessage::RoundEnded(_)]));
+        assert!(matches!(lifecycle.phase(), MatchPhase::Results { .. }));
+        assert!(matches!(lifecycle.status(&game, 2), ServerMessage::RoundEnded(_)));
+
+        // Results shown for a second, then straight into the next countdown
+        let messages = run(&mut lifecycle, &mut game, 2, 20);
+        assert!(matches!(
+            messages[..],
+            [ServerMessage::MatchWaiting { .. }, ServerMessage::MatchCountdown { secs: 2 }]
+        ));
+    }
+}
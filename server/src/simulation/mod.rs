@@ -1,3 +1,11 @@
+pub mod command;
 pub mod game_loop;
+pub mod idle;
+pub mod input_queue;
+pub mod lifecycle;
 
+pub use command::*;
 pub use game_loop::*;
+pub use idle::*;
+pub use input_queue::*;
+pub use lifecycle::*;
@@ -0,0 +1,235 @@
+//! In-process server and scripted WebSocket clients for integration tests
+//!
+//! The server runs the real router on an ephemeral port, but its game loop
+//! only ticks when the test calls `TestServer::step`, so snapshots can be
+//! checked tick by tick.
+
+#![allow(dead_code)] // Each test binary uses a different part of the harness
+
+use battlestar_server::app::{AppState, ServerConfig};
+use battlestar_server::constants::CONSTANTS_HASH;
+use battlestar_server::infrastructure::create_app;
+use battlestar_server::simulation::{MatchConfig, TickDriver};
+use battlestar_shared::network::PROTOCOL_VERSION;
+use battlestar_shared::{ClientInput, ClientMessage, ServerMessage};
+use futures_util::{SinkExt, StreamExt};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Longest wait for a message before a test fails
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The server answers at most one client ping per this interval
+const PING_SPACING: Duration = Duration::from_millis(200);
+
+/// Config of `TestServer::start`: seeded field, rounds start right away
+pub fn test_config() -> ServerConfig {
+    ServerConfig {
+        asteroid_seed: Some(1),
+        match_config: MatchConfig {
+            min_players: 1,
+            countdown: Duration::ZERO,
+            results: Duration::ZERO,
+        },
+        ..ServerConfig::default()
+    }
+}
+
+/// Server listening on 127.0.0.1 with a manually stepped game loop
+pub struct TestServer {
+    pub state: Arc<AppState>,
+    ticks: TickDriver,
+    addr: SocketAddr,
+}
+
+impl TestServer {
+    /// Server whose rounds start as soon as one player is connected
+    pub async fn start() -> Self {
+        Self::with_config(test_config()).await
+    }
+
+    pub async fn with_config(config: ServerConfig) -> Self {
+        let (state, ticks) = AppState::with_manual_ticks(config);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = create_app(state.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        Self { state, ticks, addr }
+    }
+
+    /// Run one game tick; returns once its messages are queued for every client
+    pub async fn step(&self) -> u64 {
+        self.ticks.step().await.expect("game loop stopped")
+    }
+
+    /// Wait until the server dropped `player_id`'s connection
+    ///
+    /// The connection task tells the game loop right after, without
+    /// yielding, so the next step sees the player gone.
+    pub async fn disconnected(&self, player_id: u32) {
+        let gone = async {
+            while self.state.connections.queue_stats().iter().any(|(id, _)| *id == player_id) {
+                tokio::task::yield_now().await;
+            }
+        };
+        tokio::time::timeout(RECV_TIMEOUT, gone)
+            .await
+            .unwrap_or_else(|_| panic!("player {} still connected", player_id));
+    }
+
+    /// Driver for ticking from another task
+    pub fn ticks(&self) -> TickDriver {
+        self.ticks.clone()
+    }
+
+    /// Open a socket without saying hello
+    pub async fn open(&self) -> TestClient {
+        let url = format!("ws://{}/ws", self.addr);
+        // No Nagle: messages sent back to back arrive back to back, like a browser's
+        let (socket, _) = tokio_tungstenite::connect_async_with_config(url, None, true).await.unwrap();
+        TestClient { socket, player_id: 0, last_sync: None }
+    }
+
+    /// Connect and complete the handshake (the first message must be Welcome)
+    pub async fn connect(&self, name: &str) -> TestClient {
+        let mut client = self.open().await;
+        client
+            .send(&ClientMessage::Hello {
+                name: name.to_string(),
+                client_version: "test".to_string(),
+                protocol_version: PROTOCOL_VERSION,
+                constants_hash: CONSTANTS_HASH,
+            })
+            .await;
+        match client.recv().await {
+            ServerMessage::Welcome { assigned_id, .. } => client.player_id = assigned_id,
+            other => panic!("expected Welcome first, got {:?}", other),
+        }
+        client
+    }
+}
+
+/// Scripted client speaking the JSON protocol
+pub struct TestClient {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    pub player_id: u32,
+    /// When `sync` last pinged (pings closer than `PING_SPACING` go unanswered)
+    last_sync: Option<tokio::time::Instant>,
+}
+
+impl TestClient {
+    pub async fn send(&mut self, message: &ClientMessage) {
+        let json = serde_json::to_string(message).unwrap();
+        self.socket.send(Message::text(json)).await.unwrap();
+    }
+
+    /// Send text as is (malformed messages)
+    pub async fn send_raw(&mut self, text: &str) {
+        self.socket.send(Message::text(text)).await.unwrap();
+    }
+
+    pub async fn send_input(&mut self, thrust: f32, rotate: f32) {
+        let input = ClientInput { player_id: self.player_id, thrust, rotate };
+        self.send(&ClientMessage::Input(input)).await;
+    }
+
+    /// Next message from the server, whatever it is
+    pub async fn recv(&mut self) -> ServerMessage {
+        self.try_recv()
+            .await
+            .unwrap_or_else(|| panic!("player {}: no message within {:?}", self.player_id, RECV_TIMEOUT))
+    }
+
+    /// Next message from the server, or None if the socket closed or stayed silent
+    pub async fn try_recv(&mut self) -> Option<ServerMessage> {
+        loop {
+            let message = tokio::time::timeout(RECV_TIMEOUT, self.socket.next()).await.ok()??.ok()?;
+            match message {
+                Message::Text(text) => return Some(serde_json::from_str(text.as_str()).unwrap()),
+                Message::Close(_) => return None,
+                _ => continue,
+            }
+        }
+    }
+
+    /// Skip messages until one matches `wanted`
+    pub async fn recv_until(&mut self, wanted: impl Fn(&ServerMessage) -> bool) -> ServerMessage {
+        loop {
+            let message = self.recv().await;
+            if wanted(&message) {
+                return message;
+            }
+        }
+    }
+
+    /// Wait until the server has handled everything sent so far
+    ///
+    /// Messages are handled in order, so once the pong for a fresh ping is
+    /// back, earlier inputs are with the game loop and count from the next
+    /// step. Messages received meanwhile are skipped.
+    pub async fn sync(&mut self) {
+        if let Some(last) = self.last_sync {
+            tokio::time::sleep_until(last + PING_SPACING).await;
+        }
+
+        let marker = -(self.player_id as f64) - 1.0; // Never a real client time
+        self.send(&ClientMessage::Ping { client_time: marker }).await;
+        let pong = self.recv_until(|message| {
+            matches!(message, ServerMessage::Pong { client_time, .. } if *client_time == marker)
+        });
+        // Server pings keep `recv_until` going, so bound the whole wait
+        tokio::time::timeout(RECV_TIMEOUT, pong)
+            .await
+            .unwrap_or_else(|_| panic!("no pong within {:?}", RECV_TIMEOUT));
+        // The server spaces pings from when it answered, which is before now
+        self.last_sync = Some(tokio::time::Instant::now());
+    }
+
+    /// Next game state (full or delta), skipping pings and events
+    pub async fn next_snapshot(&mut self) -> ServerMessage {
+        self.recv_until(|message| {
+            matches!(message, ServerMessage::GameState(_) | ServerMessage::DeltaState(_))
+        })
+        .await
+    }
+
+    pub async fn close(mut self) {
+        let _ = self.socket.close(None).await;
+    }
+}
+
+/// Tick number of a snapshot
+pub fn snapshot_tick(message: &ServerMessage) -> u64 {
+    match message {
+        ServerMessage::GameState(state) => state.tick,
+        ServerMessage::DeltaState(delta) => delta.tick,
+        other => panic!("expected a snapshot, got {:?}", other),
+    }
+}
+
+/// Step the server and read `client`'s snapshot of each tick until `check`
+/// passes, for at most `max_ticks` ticks
+///
+/// Messages from other clients only count once the server has them: call
+/// `TestClient::sync` (or `TestServer::disconnected`) first.
+pub async fn step_until(
+    server: &TestServer,
+    client: &mut TestClient,
+    max_ticks: u32,
+    check: impl Fn(&ServerMessage) -> bool,
+) -> ServerMessage {
+    for _ in 0..max_ticks {
+        server.step().await;
+        let snapshot = client.next_snapshot().await;
+        if check(&snapshot) {
+            return snapshot;
+        }
+    }
+    panic!("condition not met within {} ticks", max_ticks);
+}
@@ -0,0 +1,306 @@
+//! End-to-end tests: real router and sockets, game loop stepped by the test
+
+mod common;
+
+use battlestar_shared::{ClientMessage, ServerMessage};
+use battlestar_server::domain::rate_limit::MAX_CONSECUTIVE_ERRORS;
+use battlestar_server::app::LinkConditions;
+use common::{snapshot_tick, step_until, test_config, TestServer};
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_welcome_comes_first_while_game_runs() {
+    let server = TestServer::start().await;
+    let _host = server.connect("Host").await;
+
+    // Snapshots are broadcast while the others join
+    let ticks = server.ticks();
+    let ticker = tokio::spawn(async move {
+        while ticks.step().await.is_some() {
+            tokio::task::yield_now().await;
+        }
+    });
+
+    let mut ids = HashSet::new();
+    for i in 0..50 {
+        // `connect` fails unless Welcome is the first message
+        let client = server.connect(&format!("Pilot{}", i)).await;
+        ids.insert(client.player_id);
+    }
+    ticker.abort();
+
+    assert_eq!(ids.len(), 50, "every connection gets its own ID");
+}
+
+#[tokio::test]
+async fn test_welcome_carries_name() {
+    let server = TestServer::start().await;
+    let mut client = server.open().await;
+    client
+        .send(&ClientMessage::Hello {
+            name: "Starbuck".to_string(),
+            client_version: "test".to_string(),
+            protocol_version: battlestar_shared::network::PROTOCOL_VERSION,
+            constants_hash: battlestar_server::constants::CONSTANTS_HASH,
+        })
+        .await;
+
+    match client.recv().await {
+        ServerMessage::Welcome { name, .. } => assert_eq!(name, "Starbuck"),
+        other => panic!("expected Welcome, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_incompatible_client_rejected() {
+    let server = TestServer::start().await;
+    let mut client = server.open().await;
+    client
+        .send(&ClientMessage::Hello {
+            name: "Old".to_string(),
+            client_version: "test".to_string(),
+            protocol_version: 0,
+            constants_hash: 0,
+        })
+        .await;
+
+    assert!(matches!(client.recv().await, ServerMessage::IncompatibleClient { .. }));
+    assert!(client.try_recv().await.is_none(), "socket closed after rejection");
+}
+
+#[tokio::test]
+async fn test_client_without_hello_rejected() {
+    let server = TestServer::start().await;
+
+    let mut client = server.open().await;
+    client.send_input(1.0, 0.0).await;
+    assert!(matches!(client.recv().await, ServerMessage::IncompatibleClient { .. }));
+    assert!(client.try_recv().await.is_none(), "socket closed after rejection");
+
+    let mut client = server.open().await;
+    client.send_raw("not json").await;
+    assert!(matches!(client.recv().await, ServerMessage::IncompatibleClient { .. }));
+    assert!(client.try_recv().await.is_none(), "socket closed after rejection");
+}
+
+#[tokio::test]
+async fn test_full_state_every_100_ticks() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Apollo").await;
+
+    for _ in 0..250 {
+        let tick = server.step().await;
+        let snapshot = client.next_snapshot().await;
+        assert_eq!(snapshot_tick(&snapshot), tick);
+
+        let full = matches!(snapshot, ServerMessage::GameState(_));
+        assert_eq!(full, tick % 100 == 0, "tick {}", tick);
+    }
+}
+
+#[tokio::test]
+async fn test_disconnect_removes_ship() {
+    let server = TestServer::start().await;
+    let mut leaver = server.connect("Leaver").await;
+    let mut watcher = server.connect("Watcher").await;
+    let leaver_id = leaver.player_id;
+
+    // First tick starts the round; input spawns the ship
+    server.step().await;
+    watcher.next_snapshot().await;
+    leaver.send_input(1.0, 0.0).await;
+    leaver.sync().await;
+    step_until(&server, &mut watcher, 20, |snapshot| match snapshot {
+        ServerMessage::DeltaState(delta) => delta.changed_ships.iter().any(|ship| ship.id == leaver_id),
+        ServerMessage::GameState(state) => state.ships.iter().any(|ship| ship.id == leaver_id),
+        _ => false,
+    })
+    .await;
+
+    leaver.close().await;
+    server.disconnected(leaver_id).await;
+    step_until(&server, &mut watcher, 20, |snapshot| match snapshot {
+        ServerMessage::DeltaState(delta) => delta.removed_ship_ids.contains(&leaver_id),
+        _ => false,
+    })
+    .await;
+
+    // Gone from the next full state too
+    let full = step_until(&server, &mut watcher, 100, |snapshot| {
+        matches!(snapshot, ServerMessage::GameState(_))
+    })
+    .await;
+    let ServerMessage::GameState(state) = full else { unreachable!() };
+    assert!(state.ships.iter().all(|ship| ship.id != leaver_id));
+    assert!(server.state.connections.queue_stats().iter().all(|(id, _)| *id != leaver_id));
+}
+
+#[tokio::test]
+async fn test_input_rate_limited() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Twitchy").await;
+    let own_ship = |snapshot: &ServerMessage, id: u32| match snapshot {
+        ServerMessage::DeltaState(delta) => {
+            delta.changed_ships.iter().find(|ship| ship.id == id).map(|ship| (ship.rotation, ship.velocity))
+        }
+        _ => None,
+    };
+
+    // First tick starts the round; a neutral input spawns the ship at rest
+    server.step().await;
+    client.next_snapshot().await;
+    client.send_input(0.0, 0.0).await;
+    client.sync().await;
+    server.step().await;
+    let (start_rotation, _) = own_ship(&client.next_snapshot().await, client.player_id).expect("ship spawned");
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    // Second input within 15ms of the first: dropped
+    client.send_input(0.0, 1.0).await;
+    client.send_input(1.0, 0.0).await;
+    client.sync().await;
+    server.step().await;
+    let (rotation, velocity) = own_ship(&client.next_snapshot().await, client.player_id).expect("ship turned");
+    assert_ne!(rotation, start_rotation, "first input applied");
+    assert_eq!(velocity.length(), 0.0, "second input dropped");
+
+    // Once the interval has passed, the same input goes through
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    client.send_input(1.0, 0.0).await;
+    client.sync().await;
+    server.step().await;
+    let (_, velocity) = own_ship(&client.next_snapshot().await, client.player_id).expect("ship thrusting");
+    assert!(velocity.length() > 0.0);
+}
+
+#[tokio::test]
+async fn test_chat_rate_limited() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Chatty").await;
+
+    for i in 0..8 {
+        client.send(&ClientMessage::Chat { text: format!("message {}", i) }).await;
+    }
+    // Replies come in order: every relayed chat is queued before this pong
+    client.send(&ClientMessage::Ping { client_time: 42.0 }).await;
+
+    let mut relayed = 0;
+    loop {
+        match client.recv().await {
+            ServerMessage::Chat { from, .. } => {
+                assert_eq!(from, client.player_id);
+                relayed += 1;
+            }
+            ServerMessage::Pong { client_time: 42.0, .. } => break,
+            _ => {}
+        }
+    }
+    assert_eq!(relayed, 5, "burst of 5, the rest dropped");
+}
+
+#[tokio::test]
+async fn test_ping_rate_limited() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Pinger").await;
+
+    for i in 0..3 {
+        client.send(&ClientMessage::Ping { client_time: i as f64 }).await;
+    }
+    client.send(&ClientMessage::Chat { text: "done".to_string() }).await;
+
+    let mut pongs = Vec::new();
+    loop {
+        match client.recv().await {
+            ServerMessage::Pong { client_time, .. } => pongs.push(client_time),
+            ServerMessage::Chat { .. } => break,
+            _ => {}
+        }
+    }
+    assert_eq!(pongs, vec![0.0], "pings closer than 200ms are ignored");
+}
+
+#[tokio::test]
+async fn test_round_trip_measured_over_ping_interval() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Faraway").await;
+
+    // Answer the first ping only once the next one is out (RTT over a second)
+    let first = match client.recv_until(|message| matches!(message, ServerMessage::Ping { .. })).await {
+        ServerMessage::Ping { server_time } => server_time,
+        _ => unreachable!(),
+    };
+    client.recv_until(|message| matches!(message, ServerMessage::Ping { .. })).await;
+    // The first ping can go out a little late, leaving the gap to the next under a second
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    client.send(&ClientMessage::Pong { server_time: first }).await;
+
+    // The pong for this ping is queued after the first one is recorded
+    client.send(&ClientMessage::Ping { client_time: 42.0 }).await;
+    client
+        .recv_until(|message| matches!(message, ServerMessage::Pong { client_time: 42.0, .. }))
+        .await;
+
+    let latencies = server.state.connections.latencies();
+    let (_, latency) = latencies
+        .iter()
+        .find(|(id, _)| *id == client.player_id)
+        .expect("round trip recorded");
+    assert!(latency.rtt_ms >= 1000.0, "rtt {}", latency.rtt_ms);
+}
+
+#[tokio::test]
+async fn test_malformed_message_gets_error() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Garbled").await;
+    client.send_raw("{\"type\":\"Teleport\"}").await;
+
+    let error = client
+        .recv_until(|message| matches!(message, ServerMessage::Error { .. }))
+        .await;
+    assert!(matches!(
+        error,
+        ServerMessage::Error { kind: battlestar_shared::network::ClientErrorKind::UnknownType, .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_error_replies_limited_then_disconnected() {
+    let server = TestServer::start().await;
+    let mut client = server.connect("Spammer").await;
+    for _ in 0..MAX_CONSECUTIVE_ERRORS {
+        client.send_raw("garbage").await;
+    }
+
+    // Read until the server closes the socket (pings would keep it busy otherwise)
+    let mut errors = 0;
+    let closed = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(message) = client.try_recv().await {
+            if matches!(message, ServerMessage::Error { .. }) {
+                errors += 1;
+            }
+        }
+    })
+    .await;
+    assert!(closed.is_ok(), "socket closed after {} bad messages", MAX_CONSECUTIVE_ERRORS);
+    assert!(errors < MAX_CONSECUTIVE_ERRORS, "error replies are rate limited ({} sent)", errors);
+    assert!(errors > 0);
+}
+
+#[tokio::test]
+async fn test_bandwidth_cap_backs_up_outbound_queue() {
+    // 4 kB/s: a full state takes longer to send than the test takes to run
+    let link = LinkConditions { bandwidth: Some(4_000), ..LinkConditions::PERFECT };
+    let server = TestServer::with_config(battlestar_server::app::ServerConfig { link, ..test_config() }).await;
+    let client = server.connect("Dialup").await;
+
+    for _ in 0..50 {
+        server.step().await;
+    }
+
+    // Snapshots piled up in the outbound queue (and were coalesced there)
+    // instead of all going into the link
+    let stats = server.state.connections.queue_stats();
+    let (_, stats) = stats.iter().find(|(id, _)| *id == client.player_id).unwrap();
+    assert!(stats.coalesced > 0, "{:?}", stats);
+}
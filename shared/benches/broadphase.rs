@@ -0,0 +1,97 @@
+// Broad phase vs brute force collision checks
+//
+// Entities are spread uniformly over the 4000x4000 world with radii in the
+// asteroid/ship range. Each iteration rebuilds the grid (as the server does
+// every tick) and collects all overlapping pairs.
+//
+// Run with: cargo bench -p battlestar-shared --bench broadphase
+
+use battlestar_shared::physics::{check_collision, SpatialGrid};
+use battlestar_shared::Vec2;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+const WORLD_LIMIT: f32 = 2000.0;
+const CELL_SIZE: f32 = 64.0;
+
+/// Deterministic pseudo-random entities (LCG, no extra dependency)
+fn entities(count: usize) -> Vec<(u32, Vec2, f32)> {
+    let mut seed = 0x2545_f491u32;
+    let mut next = move || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (seed >> 8) as f32 / (1u32 << 24) as f32
+    };
+
+    (0..count as u32)
+        .map(|id| {
+            let position = Vec2::new(
+                next() * WORLD_LIMIT * 2.0 - WORLD_LIMIT,
+                next() * WORLD_LIMIT * 2.0 - WORLD_LIMIT,
+            );
+            (id, position, 15.0 + next() * 10.0)
+        })
+        .collect()
+}
+
+fn bench_pairs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collision_pairs");
+
+    for count in [100, 1_000, 10_000] {
+        let data = entities(count);
+
+        group.bench_with_input(BenchmarkId::new("grid", count), &data, |b, data| {
+            let mut grid = SpatialGrid::new(WORLD_LIMIT, CELL_SIZE);
+            b.iter(|| {
+                grid.clear();
+                for &(id, position, radius) in data {
+                    grid.insert(id, position, radius);
+                }
+                black_box(grid.query_pairs())
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("brute_force", count), &data, |b, data| {
+            b.iter(|| {
+                let mut pairs = Vec::new();
+                for (i, &(a, pa, ra)) in data.iter().enumerate() {
+                    for &(b, pb, rb) in &data[i + 1..] {
+                        if check_collision(pa, ra, pb, rb) {
+                            pairs.push((a, b));
+                        }
+                    }
+                }
+                black_box(pairs)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_radius_queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("query_radius");
+
+    for count in [100, 1_000, 10_000] {
+        let data = entities(count);
+        let mut grid = SpatialGrid::new(WORLD_LIMIT, CELL_SIZE);
+        for &(id, position, radius) in &data {
+            grid.insert(id, position, radius);
+        }
+
+        // One query per entity, like ships checking their surroundings
+        group.bench_with_input(BenchmarkId::new("grid", count), &data, |b, data| {
+            b.iter(|| {
+                let mut hits = 0;
+                for &(_, position, radius) in data {
+                    hits += grid.query_radius(position, radius).len();
+                }
+                black_box(hits)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pairs, bench_radius_queries);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn get_float(section: &toml::Value, key: &str) -> f32 {
+    let value = section.get(key).unwrap_or_else(|| panic!("Missing {}", key));
+    value.as_float().map(|f| f as f32)
+        .or_else(|| value.as_integer().map(|i| i as f32))
+        .unwrap_or_else(|| panic!("Missing or invalid {}", key))
+}
+
+/// Generates the defaults of the tuning structs (`DamageConstants`,
+/// `PickupConstants`) from game-constants.toml, like the server and client
+/// generate their own constants
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+
+    // Try parent directory first (normal workspace), then current directory (Docker build)
+    let constants_path = Path::new(&manifest_dir).parent().unwrap().join("game-constants.toml");
+    let constants_path = if constants_path.exists() {
+        constants_path
+    } else {
+        Path::new(&manifest_dir).join("game-constants.toml")
+    };
+    println!("cargo:rerun-if-changed={}", constants_path.display());
+
+    let contents = fs::read_to_string(&constants_path)
+        .expect("Failed to read game-constants.toml");
+    let config: toml::Value = toml::from_str(&contents)
+        .expect("Failed to parse game-constants.toml");
+
+    let damage = config.get("damage").expect("Missing [damage] section");
+    let pickups = config.get("pickups").expect("Missing [pickups] section");
+
+    let generated_code = format!(
+        r#"// This file is auto-generated by build.rs from game-constants.toml
+// DO NOT EDIT MANUALLY - your changes will be overwritten
+
+pub const MAX_HEALTH: f32 = {:?};  // hull points
+pub const MAX_SHIELD: f32 = {:?};  // shield points
+pub const SHIELD_REGEN_RATE: f32 = {:?};  // shield points per second
+pub const SHIELD_REGEN_DELAY: f32 = {:?};  // seconds without damage before regenerating
+pub const IMPACT_DAMAGE_FACTOR: f32 = {:?};  // damage per pixel/sec of closing speed
+pub const MIN_IMPACT_SPEED: f32 = {:?};  // closing speed below which impacts do no damage
+pub const PICKUP_RADIUS: f32 = {:?};  // collection radius (pixels)
+pub const PICKUP_DURATION: f32 = {:?};  // seconds timed effects last
+pub const SPEED_BOOST: f32 = {:?};  // thrust and top speed multiplier while boosted
+pub const RAPID_FIRE: f32 = {:?};  // fire rate multiplier with rapid fire
+"#,
+        get_float(damage, "max_health"),
+        get_float(damage, "max_shield"),
+        get_float(damage, "shield_regen_rate"),
+        get_float(damage, "shield_regen_delay"),
+        get_float(damage, "impact_damage_factor"),
+        get_float(damage, "min_impact_speed"),
+        get_float(pickups, "radius"),
+        get_float(pickups, "duration"),
+        get_float(pickups, "speed_boost"),
+        get_float(pickups, "rapid_fire"),
+    );
+
+    let dest_path = Path::new(&env::var("OUT_DIR").unwrap()).join("generated_constants.rs");
+    fs::write(&dest_path, generated_code).expect("Failed to write generated constants");
+}
@@ -0,0 +1,5 @@
+// Damage and pickup tuning, the defaults of DamageConstants / PickupConstants
+// These are auto-generated from game-constants.toml at build time
+// DO NOT EDIT THIS FILE - edit game-constants.toml instead
+
+include!(concat!(env!("OUT_DIR"), "/generated_constants.rs"));
@@ -1,14 +1,38 @@
 use serde::{Deserialize, Serialize};
-use crate::math::Vec2;
+use std::f32::consts::{PI, TAU};
+use crate::math::{SeededRng, Vec2};
 use crate::physics::wrap_position;
 
+/// Outline vertex count range (inclusive)
+const OUTLINE_MIN_VERTICES: u64 = 7;
+const OUTLINE_MAX_VERTICES: u64 = 12;
+
+/// Outline vertices sit between this fraction of the radius and the radius
+///
+/// The outline never leaves the radius, so the circle stays a valid bound
+/// for the broad phase and swept tests.
+const OUTLINE_MIN_SCALE: f32 = 0.7;
+
+/// Mixed into the asteroid id so outlines don't correlate with other id-seeded values
+const OUTLINE_SALT: u64 = 0xA57E_401D;
+
 /// Asteroid entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Asteroid {
     pub id: u32,
     pub position: Vec2,
     pub velocity: Vec2,
+
+    /// Bounding radius (the outline lies inside it)
     pub radius: f32,
+
+    /// Orientation of the outline (radians)
+    #[serde(default)]
+    pub rotation: f32,
+
+    /// Spin (radians/sec)
+    #[serde(default)]
+    pub angular_velocity: f32,
 }
 
 impl Asteroid {
@@ -18,15 +42,48 @@ impl Asteroid {
             position,
             velocity,
             radius,
+            rotation: 0.0,
+            angular_velocity: 0.0,
         }
     }
 
-    /// Update asteroid position (simple velocity integration with wrapping)
+    /// Update asteroid position and spin (simple integration with wrapping)
     pub fn update(&mut self, dt: f32, world_limit: f32) {
         self.position.x += self.velocity.x * dt;
         self.position.y += self.velocity.y * dt;
         wrap_position(&mut self.position, world_limit);
+
+        // Keep rotation in [-PI, PI) so it doesn't lose precision over time
+        self.rotation = (self.rotation + self.angular_velocity * dt + PI).rem_euclid(TAU) - PI;
     }
+
+    /// Current outline: `asteroid_outline` rotated, relative to `position`
+    pub fn outline(&self) -> Vec<Vec2> {
+        asteroid_outline(self.id, self.radius)
+            .into_iter()
+            .map(|vertex| vertex.rotated(self.rotation))
+            .collect()
+    }
+}
+
+/// Irregular outline of an asteroid, unrotated and relative to its center
+///
+/// Derived only from the id and radius, so the server and every client
+/// build the same shape without sending it. Vertices go counter-clockwise
+/// and the shape is star-shaped around the center (safe to triangle-fan).
+pub fn asteroid_outline(id: u32, radius: f32) -> Vec<Vec2> {
+    let mut rng = SeededRng::new(u64::from(id) ^ OUTLINE_SALT);
+    let span = OUTLINE_MAX_VERTICES - OUTLINE_MIN_VERTICES + 1;
+    let count = (OUTLINE_MIN_VERTICES + rng.next_u64() % span) as usize;
+
+    (0..count)
+        .map(|i| {
+            // Jitter under half a slot keeps the vertices in order
+            let angle = (i as f32 + rng.range(-0.35, 0.35)) * TAU / count as f32;
+            let distance = radius * rng.range(OUTLINE_MIN_SCALE, 1.0);
+            Vec2::new(angle.cos() * distance, angle.sin() * distance)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -50,6 +107,52 @@ mod tests {
         assert_eq!(asteroid.position.y, 5.0);
     }
 
+    #[test]
+    fn test_asteroid_spins() {
+        let mut asteroid = Asteroid::new(1, Vec2::ZERO, Vec2::ZERO, 20.0);
+        asteroid.angular_velocity = 1.0;
+        asteroid.update(0.5, 2000.0);
+        assert!((asteroid.rotation - 0.5).abs() < 1e-6);
+
+        // Stays within one turn
+        asteroid.update(10.0, 2000.0);
+        assert!((-PI..PI).contains(&asteroid.rotation));
+    }
+
+    #[test]
+    fn test_outline_is_deterministic_per_id() {
+        assert_eq!(asteroid_outline(7, 30.0), asteroid_outline(7, 30.0));
+        assert_ne!(asteroid_outline(7, 30.0), asteroid_outline(8, 30.0));
+    }
+
+    #[test]
+    fn test_outline_stays_inside_radius() {
+        for id in 0..200 {
+            let outline = asteroid_outline(id, 30.0);
+            assert!((7..=12).contains(&outline.len()));
+
+            for (i, vertex) in outline.iter().enumerate() {
+                let distance = vertex.length();
+                assert!((30.0 * OUTLINE_MIN_SCALE - 1e-3..=30.0 + 1e-3).contains(&distance));
+
+                // Counter-clockwise around the center
+                let next = outline[(i + 1) % outline.len()];
+                assert!(vertex.x * next.y - vertex.y * next.x > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_outline_follows_rotation() {
+        let mut asteroid = Asteroid::new(3, Vec2::ZERO, Vec2::ZERO, 20.0);
+        let unrotated = asteroid.outline();
+        asteroid.rotation = PI / 2.0;
+
+        let rotated = asteroid.outline();
+        let expected = unrotated[0].rotated(PI / 2.0);
+        assert!((rotated[0] - expected).length() < 1e-5);
+    }
+
     #[test]
     fn test_asteroid_wrapping() {
         let mut asteroid = Asteroid::new(1, Vec2::new(1999.0, 0.0), Vec2::new(100.0, 0.0), 20.0);
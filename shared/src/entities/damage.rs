@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use crate::constants::*;
+
+/// Health, shield and damage tuning (from game-constants.toml `[damage]`)
+#[derive(Debug, Clone, Copy)]
+pub struct DamageConstants {
+    pub max_health: f32,
+    pub max_shield: f32,
+
+    /// Shield points regenerated per second
+    pub shield_regen_rate: f32,
+
+    /// Seconds without taking damage before the shield starts regenerating
+    pub shield_regen_delay: f32,
+
+    /// Damage per pixel/sec of closing speed above `min_impact_speed`
+    pub impact_damage_factor: f32,
+
+    /// Closing speed (pixels/sec) below which impacts do no damage
+    pub min_impact_speed: f32,
+}
+
+impl DamageConstants {
+    pub fn from_game_constants(
+        max_health: f32,
+        max_shield: f32,
+        shield_regen_rate: f32,
+        shield_regen_delay: f32,
+        impact_damage_factor: f32,
+        min_impact_speed: f32,
+    ) -> Self {
+        Self {
+            max_health,
+            max_shield,
+            shield_regen_rate,
+            shield_regen_delay,
+            impact_damage_factor,
+            min_impact_speed,
+        }
+    }
+
+    /// Damage dealt by a source
+    pub fn damage_for(&self, source: &DamageSource) -> f32 {
+        match *source {
+            DamageSource::Asteroid { speed } | DamageSource::Ship { speed, .. } => {
+                (speed - self.min_impact_speed).max(0.0) * self.impact_damage_factor
+            }
+            DamageSource::Projectile { damage, .. } => damage,
+        }
+    }
+}
+
+impl Default for DamageConstants {
+    /// `[damage]` from game-constants.toml
+    fn default() -> Self {
+        Self {
+            max_health: MAX_HEALTH,
+            max_shield: MAX_SHIELD,
+            shield_regen_rate: SHIELD_REGEN_RATE,
+            shield_regen_delay: SHIELD_REGEN_DELAY,
+            impact_damage_factor: IMPACT_DAMAGE_FACTOR,
+            min_impact_speed: MIN_IMPACT_SPEED,
+        }
+    }
+}
+
+/// What damaged a ship
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DamageSource {
+    /// Hit an asteroid with the given closing speed (pixels/sec)
+    Asteroid { speed: f32 },
+
+    /// Collided with another ship
+    Ship { other_id: u32, speed: f32 },
+
+    /// Hit by a projectile fired by `owner_id`
+    Projectile { owner_id: u32, damage: f32 },
+}
+
+impl DamageSource {
+    /// Player credited with the damage, if any
+    pub fn attacker(&self) -> Option<u32> {
+        match *self {
+            DamageSource::Asteroid { .. } => None,
+            DamageSource::Ship { other_id, .. } => Some(other_id),
+            DamageSource::Projectile { owner_id, .. } => Some(owner_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impact_damage_scales_with_speed() {
+        let constants = DamageConstants::default();
+
+        let slow = constants.damage_for(&DamageSource::Asteroid { speed: 30.0 });
+        let medium = constants.damage_for(&DamageSource::Asteroid { speed: 150.0 });
+        let fast = constants.damage_for(&DamageSource::Ship { other_id: 2, speed: 350.0 });
+
+        assert_eq!(slow, 0.0, "Gentle bumps do no damage");
+        assert_eq!(medium, 50.0);
+        assert_eq!(fast, 150.0);
+    }
+
+    #[test]
+    fn test_projectile_damage_is_fixed() {
+        let constants = DamageConstants::default();
+        let source = DamageSource::Projectile { owner_id: 3, damage: 25.0 };
+
+        assert_eq!(constants.damage_for(&source), 25.0);
+        assert_eq!(source.attacker(), Some(3));
+    }
+}
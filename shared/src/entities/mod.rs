@@ -1,8 +1,14 @@
 pub mod ship;
 pub mod asteroid;
+pub mod damage;
+pub mod pickup;
+pub mod team;
 
 pub use ship::Ship;
 pub use asteroid::Asteroid;
+pub use damage::{DamageConstants, DamageSource};
+pub use pickup::{Modifier, Pickup, PickupConstants, PickupKind};
+pub use team::Team;
 
 use serde::{Deserialize, Serialize
 
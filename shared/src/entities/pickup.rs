@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use crate::math::Vec2;
+use crate::physics::PhysicsConstants;
+use crate::constants::*;
+
+/// What a pickup does when collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PickupKind {
+    /// Refills the shield at once
+    ShieldRecharge,
+
+    /// Fires `rapid_fire` times faster for a while
+    RapidFire,
+
+    /// Thrust and top speed multiplied by `speed_boost` for a while
+    SpeedBoost,
+
+    /// Ignores collisions and damage for a while
+    Invincibility,
+}
+
+impl PickupKind {
+    pub const ALL: [PickupKind; 4] = [
+        PickupKind::ShieldRecharge,
+        PickupKind::RapidFire,
+        PickupKind::SpeedBoost,
+        PickupKind::Invincibility,
+    ];
+
+    /// Whether the effect lasts `duration` seconds (otherwise it applies once)
+    pub fn is_timed(self) -> bool {
+        self != PickupKind::ShieldRecharge
+    }
+}
+
+/// Collectible lying in the world, picked up by flying over it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pickup {
+    pub id: u32,
+    pub kind: PickupKind,
+    pub position: Vec2,
+}
+
+impl Pickup {
+    pub fn new(id: u32, kind: PickupKind, position: Vec2) -> Self {
+        Self { id, kind, position }
+    }
+}
+
+/// Pickup tuning (from game-constants.toml `[pickups]`)
+#[derive(Debug, Clone, Copy)]
+pub struct PickupConstants {
+    /// Collection radius (pixels)
+    pub radius: f32,
+
+    /// How long timed effects last (seconds)
+    pub duration: f32,
+
+    /// Thrust and top speed multiplier while boosted
+    pub speed_boost: f32,
+
+    /// Fire rate multiplier with rapid fire
+    pub rapid_fire: f32,
+}
+
+impl PickupConstants {
+    pub fn from_game_constants(radius: f32, duration: f32, speed_boost: f32, rapid_fire: f32) -> Self {
+        Self {
+            radius,
+            duration,
+            speed_boost,
+            rapid_fire,
+        }
+    }
+}
+
+impl Default for PickupConstants {
+    /// `[pickups]` from game-constants.toml
+    fn default() -> Self {
+        Self::from_game_constants(PICKUP_RADIUS, PICKUP_DURATION, SPEED_BOOST, RAPID_FIRE)
+    }
+}
+
+/// Timed pickup effect on a ship
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Modifier {
+    pub kind: PickupKind,
+
+    /// Seconds left
+    pub remaining: f32,
+}
+
+/// Count modifiers down, dropping the ones that ran out
+pub fn tick_modifiers(modifiers: &mut Vec<Modifier>, dt: f32) {
+    for modifier in modifiers.iter_mut() {
+        modifier.remaining -= dt;
+    }
+    modifiers.retain(|modifier| modifier.remaining > 0.0);
+}
+
+/// Physics constants with active modifiers applied
+///
+/// Free function so the client can predict with the modifiers it was sent.
+pub fn effective_constants(
+    base: &PhysicsConstants,
+    modifiers: &[Modifier],
+    pickups: &PickupConstants,
+) -> PhysicsConstants {
+    let mut constants = *base;
+    if modifiers.iter().any(|m| m.kind == PickupKind::SpeedBoost) {
+        constants.thrust_accel *= pickups.speed_boost;
+        constants.max_speed *= pickups.speed_boost;
+    }
+    constants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> PhysicsConstants {
+        PhysicsConstants::from_game_constants(300.0, 4.0, 400.0, 0.95, 2000.0, 25.0)
+    }
+
+    #[test]
+    fn test_speed_boost_scales_thrust_and_top_speed() {
+        let boost = [Modifier { kind: PickupKind::SpeedBoost, remaining: 1.0 }];
+        let constants = effective_constants(&base(), &boost, &PickupConstants::default());
+
+        assert_eq!(constants.thrust_accel, 450.0);
+        assert_eq!(constants.max_speed, 600.0);
+        assert_eq!(constants.rotation_speed, 4.0);
+    }
+
+    #[test]
+    fn test_other_modifiers_leave_physics_alone() {
+        let modifiers = [Modifier { kind: PickupKind::RapidFire, remaining: 1.0 }];
+        let constants = effective_constants(&base(), &modifiers, &PickupConstants::default());
+
+        assert_eq!(constants.thrust_accel, 300.0);
+        assert_eq!(constants.max_speed, 400.0);
+    }
+
+    #[test]
+    fn test_modifiers_expire() {
+        let mut modifiers = vec![
+            Modifier { kind: PickupKind::SpeedBoost, remaining: 0.5 },
+            Modifier { kind: PickupKind::RapidFire, remaining: 2.0 },
+        ];
+
+        tick_modifiers(&mut modifiers, 1.0);
+
+        assert_eq!(modifiers, vec![Modifier { kind: PickupKind::RapidFire, remaining: 1.0 }]);
+    }
+
+    #[test]
+    fn test_pickup_kind_serialization() {
+        let json = serde_json::to_string(&PickupKind::SpeedBoost).unwrap();
+        assert_eq!(json, "\"speed_boost\"");
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use crate::math::Vec2;
 use crate::physics::{PhysicsConstants, Input, apply_ship_physics, wrap_position};
-use super::Color;
+use super::pickup::{self, Modifier, PickupConstants, PickupKind};
+use super::{Color, DamageConstants, Team};
 
 /// Ship entity with behavior methods
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,10 +13,32 @@ pub struct Ship {
     pub rotation: f32,
     pub color: Color,
 
+    /// Player name shown above the ship
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+
+    /// Side in team modes (None in free-for-all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<Team>,
+
     /// Tick when ship last respawned (for invincibility tracking)
     /// None = never respawned or invincibility expired
     #[serde(skip_serializing_if = "Option::is_none")]
     pub respawn_tick: Option<u64>,
+
+    /// Hull points; the ship is destroyed when this reaches zero
+    pub health: f32,
+
+    /// Shield points, absorbed before the hull and regenerated over time
+    pub shield: f32,
+
+    /// Seconds left before the shield starts regenerating (reset on damage)
+    #[serde(skip)]
+    pub shield_cooldown: f32,
+
+    /// Timed pickup effects (speed boost, rapid fire, invincibility)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<Modifier>,
 }
 
 impl Ship {
@@ -26,7 +49,13 @@ impl Ship {
             velocity: Vec2::ZERO,
             rotation: 0.0,
             color,
+            name: String::new(),
+            team: None,
             respawn_tick: None,
+            health: DamageConstants::default().max_health,
+            shield: DamageConstants::default().max_shield,
+            shield_cooldown: 0.0,
+            modifiers: Vec::new(),
         }
     }
 
@@ -65,14 +94,99 @@ impl Ship {
 
     /// Respawn ship at origin (used after collision/death)
     pub fn respawn(&mut self, current_tick: u64) {
-        self.position = Vec2::ZERO;
+        self.respawn_at(Vec2::ZERO, current_tick);
+    }
+
+    /// Respawn ship at a given position (see the server's spawn selector)
+    pub fn respawn_at(&mut self, position: Vec2, current_tick: u64) {
+        self.position = position;
         self.velocity = Vec2::ZERO;
         self.rotation = 0.0;
         self.respawn_tick = Some(current_tick);
+        self.modifiers.clear();
+    }
+
+    /// Refill hull and shield (spawn/respawn)
+    pub fn restore(&mut self, damage: &DamageConstants) {
+        self.health = damage.max_health;
+        self.shield = damage.max_shield;
+        self.shield_cooldown = 0.0;
+    }
+
+    /// Apply damage, shield first
+    ///
+    /// Returns true only when this damage brings the hull to zero,
+    /// so a death is reported once.
+    pub fn take_damage(&mut self, amount: f32, damage: &DamageConstants) -> bool {
+        if amount <= 0.0 || self.health <= 0.0 {
+            return false;
+        }
+
+        let absorbed = amount.min(self.shield);
+        self.shield -= absorbed;
+        self.health = (self.health - (amount - absorbed)).max(0.0);
+        self.shield_cooldown = damage.shield_regen_delay;
+
+        self.health == 0.0
     }
 
-    /// Check if ship is invincible (recently respawned)
+    /// Regenerate the shield once no damage was taken for `shield_regen_delay`
+    pub fn regenerate_shield(&mut self, dt: f32, damage: &DamageConstants) {
+        if self.shield_cooldown > 0.0 {
+            self.shield_cooldown = (self.shield_cooldown - dt).max(0.0);
+            return;
+        }
+        self.shield = (self.shield + damage.shield_regen_rate * dt).min(damage.max_shield);
+    }
+
+    /// Apply a collected pickup
+    ///
+    /// Timed effects start (or restart) at the full duration.
+    pub fn collect(&mut self, kind: PickupKind, pickups: &PickupConstants, damage: &DamageConstants) {
+        if !kind.is_timed() {
+            self.shield = damage.max_shield;
+            self.shield_cooldown = 0.0;
+            return;
+        }
+
+        match self.modifiers.iter_mut().find(|m| m.kind == kind) {
+            Some(modifier) => modifier.remaining = pickups.duration,
+            None => self.modifiers.push(Modifier {
+                kind,
+                remaining: pickups.duration,
+            }),
+        }
+    }
+
+    /// Whether a timed effect is active
+    pub fn has_modifier(&self, kind: PickupKind) -> bool {
+        self.modifiers.iter().any(|m| m.kind == kind)
+    }
+
+    /// Count timed effects down
+    pub fn tick_modifiers(&mut self, dt: f32) {
+        pickup::tick_modifiers(&mut self.modifiers, dt);
+    }
+
+    /// Physics constants with this ship's timed effects applied
+    pub fn effective_constants(&self, base: &PhysicsConstants, pickups: &PickupConstants) -> PhysicsConstants {
+        pickup::effective_constants(base, &self.modifiers, pickups)
+    }
+
+    /// Fire rate multiplier (rapid fire)
+    pub fn fire_rate_multiplier(&self, pickups: &PickupConstants) -> f32 {
+        if self.has_modifier(PickupKind::RapidFire) {
+            pickups.rapid_fire
+        } else {
+            1.0
+        }
+    }
+
+    /// Check if ship is invincible (recently respawned or from a pickup)
     pub fn is_invincible(&self, current_tick: u64, invincibility_ticks: u64) -> bool {
+        if self.has_modifier(PickupKind::Invincibility) {
+            return true;
+        }
         if let Some(respawn_tick) = self.respawn_tick {
             current_tick - respawn_tick < invincibility_ticks
         } else {
@@ -144,6 +258,68 @@ mod tests {
         assert_eq!(ship.respawn_tick, Some(10));
     }
 
+    #[test]
+    fn test_ship_respawn_at_position() {
+        let mut ship = Ship::new(1, Color::RED);
+        ship.velocity = Vec2::new(50.0, 50.0);
+
+        ship.respawn_at(Vec2::new(300.0, -200.0), 4);
+
+        assert_eq!(ship.position, Vec2::new(300.0, -200.0));
+        assert_eq!(ship.velocity, Vec2::ZERO);
+        assert_eq!(ship.respawn_tick, Some(4));
+    }
+
+    #[test]
+    fn test_collect_shield_recharge() {
+        let damage = DamageConstants::default();
+        let mut ship = Ship::new(1, Color::RED);
+        ship.shield = 5.0;
+        ship.shield_cooldown = 2.0;
+
+        ship.collect(PickupKind::ShieldRecharge, &PickupConstants::default(), &damage);
+
+        assert_eq!(ship.shield, damage.max_shield);
+        assert_eq!(ship.shield_cooldown, 0.0);
+        assert!(ship.modifiers.is_empty(), "Instant effect, nothing to time");
+    }
+
+    #[test]
+    fn test_collect_timed_effect_refreshes_duration() {
+        let pickups = PickupConstants::default();
+        let mut ship = Ship::new(1, Color::RED);
+
+        ship.collect(PickupKind::SpeedBoost, &pickups, &DamageConstants::default());
+        ship.tick_modifiers(3.0);
+        ship.collect(PickupKind::SpeedBoost, &pickups, &DamageConstants::default());
+
+        assert_eq!(ship.modifiers.len(), 1);
+        assert_eq!(ship.modifiers[0].remaining, pickups.duration);
+
+        ship.tick_modifiers(pickups.duration);
+        assert!(!ship.has_modifier(PickupKind::SpeedBoost));
+    }
+
+    #[test]
+    fn test_invincibility_pickup() {
+        let mut ship = Ship::new(1, Color::RED);
+        ship.collect(PickupKind::Invincibility, &PickupConstants::default(), &DamageConstants::default());
+
+        assert!(ship.is_invincible(1000, 20));
+    }
+
+    #[test]
+    fn test_respawn_clears_modifiers() {
+        let mut ship = Ship::new(1, Color::RED);
+        ship.collect(PickupKind::RapidFire, &PickupConstants::default(), &DamageConstants::default());
+        assert_eq!(ship.fire_rate_multiplier(&PickupConstants::default()), 2.0);
+
+        ship.respawn_at(Vec2::ZERO, 5);
+
+        assert!(ship.modifiers.is_empty());
+        assert_eq!(ship.fire_rate_multiplier(&PickupConstants::default()), 1.0);
+    }
+
     #[test]
     fn test_ship_invincibility_tracking() {
         let mut ship = Ship::new(1, Color::RED);
@@ -160,4 +336,53 @@ mod tests {
         assert!(!ship.is_invincible(30, 20), "Should NOT be invincible at tick 30");
         assert!(!ship.is_invincible(50, 20), "Should NOT be invincible at tick 50");
     }
+
+    #[test]
+    fn test_shield_absorbs_damage_first() {
+        let damage = DamageConstants::default();
+        let mut ship = Ship::new(1, Color::RED);
+
+        assert!(!ship.take_damage(30.0, &damage));
+        assert_eq!(ship.shield, 20.0);
+        assert_eq!(ship.health, 100.0);
+
+        assert!(!ship.take_damage(50.0, &damage));
+        assert_eq!(ship.shield, 0.0);
+        assert_eq!(ship.health, 70.0);
+    }
+
+    #[test]
+    fn test_death_only_when_health_reaches_zero() {
+        let damage = DamageConstants::default();
+        let mut ship = Ship::new(1, Color::RED);
+
+        assert!(!ship.take_damage(149.0, &damage));
+        assert_eq!(ship.health, 1.0);
+        assert!(ship.take_damage(5.0, &damage), "Hull reaching zero is a death");
+        assert_eq!(ship.health, 0.0);
+        assert!(!ship.take_damage(5.0, &damage), "Death is reported once");
+
+        ship.restore(&damage);
+        assert_eq!(ship.health, damage.max_health);
+        assert_eq!(ship.shield, damage.max_shield);
+    }
+
+    #[test]
+    fn test_shield_regenerates_after_delay() {
+        let damage = DamageConstants::default();
+        let mut ship = Ship::new(1, Color::RED);
+        ship.take_damage(40.0, &damage);
+
+        // Still within the delay: no regeneration
+        ship.regenerate_shield(damage.shield_regen_delay - 0.5, &damage);
+        assert_eq!(ship.shield, 10.0);
+
+        ship.regenerate_shield(0.5, &damage);
+        ship.regenerate_shield(1.0, &damage);
+        assert_eq!(ship.shield, 10.0 + damage.shield_regen_rate);
+
+        // Capped at max
+        ship.regenerate_shield(100.0, &damage);
+        assert_eq!(ship.shield, damage.max_shield);
+    }
 }
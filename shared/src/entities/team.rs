@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use super::Color;
+
+/// Side a ship plays for in team modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Team {
+    Red,
+    Blue,
+}
+
+impl Team {
+    pub const ALL: [Team; 2] = [Team::Red, Team::Blue];
+
+    /// Ship color for the team (replaces the random color)
+    pub fn color(self) -> Color {
+        match self {
+            Team::Red => Color::new(1.0, 0.35, 0.3),
+            Team::Blue => Color::new(0.3, 0.55, 1.0),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 use crate::physics::Input;
 
 /// Validate and clamp input to prevent cheating
@@ -5,6 +7,232 @@ pub fn validate_input(input: &mut Input) {
     input.clamp();
 }
 
+/// Input rejected outright (never applied to a ship)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputViolation {
+    /// NaN or infinite axis value (`clamp` lets NaN through)
+    NonFinite,
+}
+
+/// Reject non-finite values, then clamp to the valid range
+///
+/// A NaN thrust would otherwise poison the ship's velocity and position.
+pub fn sanitize_input(input: &mut Input) -> Result<(), InputViolation> {
+    if !input.thrust.is_finite() || !input.rotate.is_finite() {
+        return Err(InputViolation::NonFinite);
+    }
+    input.clamp();
+    Ok(())
+}
+
+/// Suspicious input behavior detected by `InputMonitor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAnomaly {
+    /// NaN or infinite values
+    NonFinite,
+
+    /// Inputs arriving at a near-perfect interval (no human or network jitter)
+    PerfectFrequency,
+
+    /// Rotation direction flipping faster than a human can press keys
+    RotationToggling,
+}
+
+impl InputAnomaly {
+    /// Suspicion points added when this anomaly is observed
+    pub fn weight(&self) -> f32 {
+        match self {
+            InputAnomaly::NonFinite => 50.0,
+            InputAnomaly::PerfectFrequency => 20.0,
+            InputAnomaly::RotationToggling => 15.0,
+        }
+    }
+}
+
+/// What to do with a player given their suspicion score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SuspicionAction {
+    None,
+    Log,
+    Throttle,
+    Kick,
+}
+
+/// Score thresholds for each action, and how fast the score decays
+#[derive(Debug, Clone, Copy)]
+pub struct SuspicionPolicy {
+    pub log_at: f32,
+    pub throttle_at: f32,
+    pub kick_at: f32,
+
+    /// Points removed per second, so old anomalies are forgiven
+    pub decay_per_sec: f32,
+}
+
+impl Default for SuspicionPolicy {
+    fn default() -> Self {
+        Self {
+            log_at: 10.0,
+            throttle_at: 40.0,
+            kick_at: 100.0,
+            decay_per_sec: 1.0,
+        }
+    }
+}
+
+impl SuspicionPolicy {
+    pub fn action_for(&self, score: f32) -> SuspicionAction {
+        if score >= self.kick_at {
+            SuspicionAction::Kick
+        } else if score >= self.throttle_at {
+            SuspicionAction::Throttle
+        } else if score >= self.log_at {
+            SuspicionAction::Log
+        } else {
+            SuspicionAction::None
+        }
+    }
+}
+
+/// Number of input intervals inspected for perfect-frequency detection
+pub const FREQUENCY_SAMPLES: usize = 30;
+
+/// Interval standard deviation below which input timing looks scripted (ms)
+///
+/// Browsers schedule frames with a few ms of jitter and the network adds
+/// more, so real clients are far above this.
+pub const MIN_INTERVAL_STDDEV_MS: f64 = 0.25;
+
+/// Rotation direction changes per second above which input looks scripted
+pub const MAX_ROTATION_TOGGLES_PER_SEC: usize = 20;
+
+/// Result of observing one input
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputObservation {
+    pub anomalies: Vec<InputAnomaly>,
+    pub score: f32,
+    pub action: SuspicionAction,
+}
+
+impl InputObservation {
+    /// Whether this input must be dropped instead of applied
+    pub fn rejected(&self) -> bool {
+        self.anomalies.contains(&InputAnomaly::NonFinite)
+    }
+}
+
+/// Per-player statistical anti-cheat tracking
+///
+/// Complements `sanitize_input` (which only looks at one input) by looking
+/// at input behavior over time and accumulating a suspicion score. Times are
+/// passed in by the caller (milliseconds), so this works without a clock.
+#[derive(Debug, Clone)]
+pub struct InputMonitor {
+    policy: SuspicionPolicy,
+    score: f32,
+    last_time_ms: Option<f64>,
+    intervals: VecDeque<f64>,
+    last_rotate_sign: f32,
+    toggle_times: VecDeque<f64>,
+}
+
+impl InputMonitor {
+    pub fn new(policy: SuspicionPolicy) -> Self {
+        Self {
+            policy,
+            score: 0.0,
+            last_time_ms: None,
+            intervals: VecDeque::with_capacity(FREQUENCY_SAMPLES),
+            last_rotate_sign: 0.0,
+            toggle_times: VecDeque::new(),
+        }
+    }
+
+    /// Current suspicion score
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// Record one received input and return what was detected
+    pub fn observe(&mut self, input: &Input, time_ms: f64) -> InputObservation {
+        let mut anomalies = Vec::new();
+
+        // Decay score since the previous input
+        if let Some(last) = self.last_time_ms {
+            let elapsed_secs = ((time_ms - last) / 1000.0).max(0.0) as f32;
+            self.score = (self.score - self.policy.decay_per_sec * elapsed_secs).max(0.0);
+            self.record_interval(time_ms - last, &mut anomalies);
+        }
+        self.last_time_ms = Some(time_ms);
+
+        if !input.thrust.is_finite() || !input.rotate.is_finite() {
+            anomalies.push(InputAnomaly::NonFinite);
+        } else {
+            self.record_rotation(input.rotate, time_ms, &mut anomalies);
+        }
+
+        self.score += anomalies.iter().map(InputAnomaly::weight).sum::<f32>();
+
+        InputObservation {
+            anomalies,
+            score: self.score,
+            action: self.policy.action_for(self.score),
+        }
+    }
+
+    fn record_interval(&mut self, interval_ms: f64, anomalies: &mut Vec<InputAnomaly>) {
+        if self.intervals.len() == FREQUENCY_SAMPLES {
+            self.intervals.pop_front();
+        }
+        self.intervals.push_back(interval_ms);
+
+        if self.intervals.len() < FREQUENCY_SAMPLES {
+            return;
+        }
+
+        let n = self.intervals.len() as f64;
+        let mean = self.intervals.iter().sum::<f64>() / n;
+        let variance = self.intervals.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / n;
+
+        if variance.sqrt() < MIN_INTERVAL_STDDEV_MS {
+            anomalies.push(InputAnomaly::PerfectFrequency);
+            // Require a fresh window before flagging again
+            self.intervals.clear();
+        }
+    }
+
+    fn record_rotation(&mut self, rotate: f32, time_ms: f64, anomalies: &mut Vec<InputAnomaly>) {
+        // Releasing the key (0) isn't a toggle; a direct left/right flip is
+        let sign = if rotate > 0.0 {
+            1.0
+        } else if rotate < 0.0 {
+            -1.0
+        } else {
+            return;
+        };
+
+        if self.last_rotate_sign != 0.0 && sign != self.last_rotate_sign {
+            self.toggle_times.push_back(time_ms);
+        }
+        self.last_rotate_sign = sign;
+
+        while self.toggle_times.front().is_some_and(|t| time_ms - t > 1000.0) {
+            self.toggle_times.pop_front();
+        }
+
+        if self.toggle_times.len() > MAX_ROTATION_TOGGLES_PER_SEC {
+            anomalies.push(InputAnomaly::RotationToggling);
+            self.toggle_times.clear();
+        }
+    }
+}
+
+impl Default for InputMonitor {
+    fn default() -> Self {
+        Self::new(SuspicionPolicy::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,4 +260,120 @@ mod tests {
         assert_eq!(input.thrust, -1.0);
         assert_eq!(input.rotate, -1.0);
     }
+
+    #[test]
+    fn test_sanitize_rejects_nan_and_infinity() {
+        assert_eq!(
+            sanitize_input(&mut Input::new(f32::NAN, 0.0)),
+            Err(InputViolation::NonFinite)
+        );
+        assert_eq!(
+            sanitize_input(&mut Input::new(0.0, f32::INFINITY)),
+            Err(InputViolation::NonFinite)
+        );
+        assert_eq!(
+            sanitize_input(&mut Input::new(f32::NEG_INFINITY, 0.0)),
+            Err(InputViolation::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_sanitize_clamps_finite_input() {
+        let mut input = Input::new(3.0, -3.0);
+        assert!(sanitize_input(&mut input).is_ok());
+        assert_eq!(input.thrust, 1.0);
+        assert_eq!(input.rotate, -1.0);
+    }
+
+    #[test]
+    fn test_monitor_flags_non_finite_input() {
+        let mut monitor = InputMonitor::default();
+        let observation = monitor.observe(&Input::new(f32::NAN, 0.0), 0.0);
+
+        assert!(observation.rejected());
+        assert_eq!(observation.anomalies, vec![InputAnomaly::NonFinite]);
+        assert_eq!(observation.action, SuspicionAction::Throttle);
+    }
+
+    #[test]
+    fn test_monitor_flags_perfect_frequency() {
+        let mut monitor = InputMonitor::default();
+        let mut flagged = false;
+
+        for i in 0..=FREQUENCY_SAMPLES {
+            let observation = monitor.observe(&Input::new(1.0, 0.0), i as f64 * 16.0);
+            flagged |= observation.anomalies.contains(&InputAnomaly::PerfectFrequency);
+        }
+
+        assert!(flagged, "Metronome-perfect input should be flagged");
+    }
+
+    #[test]
+    fn test_monitor_accepts_jittery_frequency() {
+        let mut monitor = InputMonitor::default();
+        let mut time = 0.0;
+
+        for i in 0..(FREQUENCY_SAMPLES * 3) {
+            // Typical browser frame jitter of a few ms
+            time += 16.0 + ((i * 7) % 5) as f64 - 2.0;
+            let observation = monitor.observe(&Input::new(1.0, 0.0), time);
+            assert!(observation.anomalies.is_empty());
+        }
+        assert_eq!(monitor.score(), 0.0);
+    }
+
+    #[test]
+    fn test_monitor_flags_rotation_toggling() {
+        let mut monitor = InputMonitor::default();
+        let mut flagged = false;
+
+        // Flip left/right every input at ~60Hz for one second
+        for i in 0..60 {
+            let rotate = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let time = i as f64 * 16.0 + (i % 3) as f64;
+            let observation = monitor.observe(&Input::new(0.0, rotate), time);
+            flagged |= observation.anomalies.contains(&InputAnomaly::RotationToggling);
+        }
+
+        assert!(flagged, "Left/right flipping at 60Hz should be flagged");
+    }
+
+    #[test]
+    fn test_monitor_accepts_human_rotation() {
+        let mut monitor = InputMonitor::default();
+
+        // Hold left, release, hold right: a few toggles per second at most
+        for i in 0..120 {
+            let rotate = match (i / 10) % 3 {
+                0 => -1.0,
+                1 => 0.0,
+                _ => 1.0,
+            };
+            let time = i as f64 * 16.0 + (i % 4) as f64;
+            let observation = monitor.observe(&Input::new(0.0, rotate), time);
+            assert!(!observation.anomalies.contains(&InputAnomaly::RotationToggling));
+        }
+    }
+
+    #[test]
+    fn test_score_decays_over_time() {
+        let mut monitor = InputMonitor::new(SuspicionPolicy {
+            decay_per_sec: 10.0,
+            ..SuspicionPolicy::default()
+        });
+        monitor.observe(&Input::new(f32::NAN, 0.0), 0.0);
+        assert_eq!(monitor.score(), 50.0);
+
+        monitor.observe(&Input::new(0.0, 0.0), 2000.0);
+        assert_eq!(monitor.score(), 30.0);
+    }
+
+    #[test]
+    fn test_policy_actions_escalate() {
+        let policy = SuspicionPolicy::default();
+        assert_eq!(policy.action_for(0.0), SuspicionAction::None);
+        assert_eq!(policy.action_for(policy.log_at), SuspicionAction::Log);
+        assert_eq!(policy.action_for(policy.throttle_at), SuspicionAction::Throttle);
+        assert_eq!(policy.action_for(policy.kick_at), SuspicionAction::Kick);
+    }
 }
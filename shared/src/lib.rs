@@ -1,4 +1,5 @@
 // Core modules
+pub mod constants;
 pub mod math;
 pub mod physics;
 pub mod entities;
@@ -9,4 +10,4 @@ pub mod network;
 pub use math::Vec2;
 pub use physics::{PhysicsConstants, Input};
 pub use entities::{Ship, Asteroid, Color};
-pub use network::{ServerMessage, ClientInput, GameState, DeltaState, ShipUpdate};
+pub use network::{ServerMessage, ClientMessage, ClientInput, GameState, DeltaState, ShipUpdate};
@@ -52,6 +52,35 @@ impl Vec2 {
         let dy = self.y - other.y;
         dx * dx + dy * dy
     }
+
+    /// Rotate counter-clockwise by `angle` radians
+    pub fn rotated(&self, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    /// Shortest vector from `self` to `other` in the toroidal world
+    ///
+    /// The world spans [-world_limit, world_limit] and wraps on both axes,
+    /// so x=1990 and x=-1990 are 20 px apart, not 3980.
+    pub fn wrapped_delta_to(&self, other: Vec2, world_limit: f32) -> Vec2 {
+        let size = world_limit * 2.0;
+        let wrap = |d: f32| d - size * (d / size).round();
+        Vec2::new(wrap(other.x - self.x), wrap(other.y - self.y))
+    }
+
+    /// Shortest distance to `other` in the toroidal world
+    pub fn wrapped_distance_to(&self, other: Vec2, world_limit: f32) -> f32 {
+        self.wrapped_delta_to(other, world_limit).length()
+    }
+
+    /// Squared shortest distance to `other` in the toroidal world
+    pub fn wrapped_distance_squared_to(&self, other: Vec2, world_limit: f32) -> f32 {
+        self.wrapped_delta_to(other, world_limit).length_squared()
+    }
 }
 
 impl std::ops::Add for Vec2 {
@@ -145,4 +174,55 @@ mod tests {
         let v5 = v1 * 2.0;
         assert_eq!(v5, Vec2::new(2.0, 4.0));
     }
+
+    #[test]
+    fn test_wrapped_delta_inside_world_is_plain_delta() {
+        let v1 = Vec2::new(100.0, -50.0);
+        let v2 = Vec2::new(130.0, -10.0);
+        assert_eq!(v1.wrapped_delta_to(v2, 2000.0), Vec2::new(30.0, 40.0));
+    }
+
+    #[test]
+    fn test_wrapped_delta_right_edge() {
+        let v1 = Vec2::new(1990.0, 0.0);
+        let v2 = Vec2::new(-1990.0, 0.0);
+        assert_eq!(v1.wrapped_delta_to(v2, 2000.0), Vec2::new(20.0, 0.0));
+        assert_eq!(v1.wrapped_distance_to(v2, 2000.0), 20.0);
+    }
+
+    #[test]
+    fn test_wrapped_delta_left_edge() {
+        let v1 = Vec2::new(-1990.0, 0.0);
+        let v2 = Vec2::new(1990.0, 0.0);
+        assert_eq!(v1.wrapped_delta_to(v2, 2000.0), Vec2::new(-20.0, 0.0));
+    }
+
+    #[test]
+    fn test_wrapped_delta_top_edge() {
+        let v1 = Vec2::new(0.0, 1995.0);
+        let v2 = Vec2::new(0.0, -1995.0);
+        assert_eq!(v1.wrapped_delta_to(v2, 2000.0), Vec2::new(0.0, 10.0));
+    }
+
+    #[test]
+    fn test_wrapped_delta_bottom_edge() {
+        let v1 = Vec2::new(0.0, -1995.0);
+        let v2 = Vec2::new(0.0, 1995.0);
+        assert_eq!(v1.wrapped_delta_to(v2, 2000.0), Vec2::new(0.0, -10.0));
+    }
+
+    #[test]
+    fn test_wrapped_delta_corners() {
+        let corners = [
+            (Vec2::new(1995.0, 1995.0), Vec2::new(-1995.0, -1995.0), Vec2::new(10.0, 10.0)),
+            (Vec2::new(-1995.0, 1995.0), Vec2::new(1995.0, -1995.0), Vec2::new(-10.0, 10.0)),
+            (Vec2::new(1995.0, -1995.0), Vec2::new(-1995.0, 1995.0), Vec2::new(10.0, -10.0)),
+            (Vec2::new(-1995.0, -1995.0), Vec2::new(1995.0, 1995.0), Vec2::new(-10.0, -10.0)),
+        ];
+
+        for (from, to, expected) in corners {
+            assert_eq!(from.wrapped_delta_to(to, 2000.0), expected, "from {:?} to {:?}", from, to);
+            assert_eq!(from.wrapped_distance_squared_to(to, 2000.0), 200.0);
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::{entities::Ship, Vec2};
+use crate::{entities::{Asteroid, Modifier, Pickup, Ship}, Vec2};
 
 /// Delta update containing only changed entities
 ///
@@ -21,6 +21,21 @@ pub struct DeltaState {
     /// Ships that were removed this tick (player disconnected)
     pub removed_ship_ids: Vec<u32>,
 
+    /// Asteroids whose motion changed this tick (bounced off something)
+    ///
+    /// Asteroids otherwise move predictably and are only corrected by the
+    /// full state, so only the ones knocked off course are sent.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_asteroids: Vec<Asteroid>,
+
+    /// Pickups that appeared this tick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_pickups: Vec<Pickup>,
+
+    /// Pickups collected this tick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_pickup_ids: Vec<u32>,
+
     /// Whether this is a full state update (every N ticks)
     pub is_full_state: bool,
 }
@@ -38,17 +53,29 @@ pub struct ShipUpdate {
     pub velocity: Vec2,
     pub rotation: f32,
 
+    /// Hull and shield points (drawn as bars over the ship)
+    pub health: f32,
+    pub shield: f32,
+
     /// Color only included when ship spawns/respawns
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<crate::entities::Color>,
 
+    /// Player name, sent along with the color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
     /// Invincibility state (only sent when true for bandwidth optimization)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_invincible: Option<bool>,
+
+    /// Active timed pickup effects (only sent when there are any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<Modifier>,
 }
 
 impl ShipUpdate {
-    /// Create update from ship (include color for spawns, check invincibility)
+    /// Create update from ship (include color and name for spawns, check invincibility)
     pub fn from_ship(ship: &Ship, include_color: bool, current_tick: u64, invincibility_ticks: u64) -> Self {
         let is_invincible = ship.is_invincible(current_tick, invincibility_ticks);
 
@@ -57,12 +84,16 @@ impl ShipUpdate {
             position: ship.position,
             velocity: ship.velocity,
             rotation: ship.rotation,
+            health: ship.health,
+            shield: ship.shield,
             color: if include_color {
                 Some(ship.color)
             } else {
                 None
             },
+            name: include_color.then(|| ship.name.clone()),
             is_invincible: if is_invincible { Some(true) } else { None },
+            modifiers: ship.modifiers.clone(),
         }
     }
 
@@ -84,6 +115,9 @@ impl DeltaState {
             tick,
             changed_ships: Vec::new(),
             removed_ship_ids: Vec::new(),
+            changed_asteroids: Vec::new(),
+            added_pickups: Vec::new(),
+            removed_pickup_ids: Vec::new(),
             is_full_state,
         }
     }
@@ -98,9 +132,28 @@ impl DeltaState {
         self.removed_ship_ids.push(id);
     }
 
+    /// Add an asteroid whose motion changed
+    pub fn add_asteroid_update(&mut self, asteroid: Asteroid) {
+        self.changed_asteroids.push(asteroid);
+    }
+
+    /// Add a pickup that just appeared
+    pub fn add_pickup(&mut self, pickup: Pickup) {
+        self.added_pickups.push(pickup);
+    }
+
+    /// Add a collected pickup ID
+    pub fn add_removed_pickup(&mut self, id: u32) {
+        self.removed_pickup_ids.push(id);
+    }
+
     /// Check if delta contains any changes
     pub fn has_changes(&self) -> bool {
-        !self.changed_ships.is_empty() || !self.removed_ship_ids.is_empty()
+        !self.changed_ships.is_empty()
+            || !self.removed_ship_ids.is_empty()
+            || !self.changed_asteroids.is_empty()
+            || !self.added_pickups.is_empty()
+            || !self.removed_pickup_ids.is_empty()
     }
 }
 
@@ -131,6 +184,18 @@ mod tests {
         assert_eq!(update.color.unwrap(), Color::RED);
     }
 
+    #[test]
+    fn test_ship_update_name_sent_with_color() {
+        let mut ship = create_test_ship(1);
+        ship.name = "Starbuck".to_string();
+
+        let spawn = ShipUpdate::with_color(&ship, 0, 20);
+        assert_eq!(spawn.name.as_deref(), Some("Starbuck"));
+
+        let json = serde_json::to_string(&ShipUpdate::without_color(&ship, 0, 20)).unwrap();
+        assert!(!json.contains("name"), "Name only sent with spawn data");
+    }
+
     #[test]
     fn test_ship_update_without_color() {
         let ship = create_test_ship(1);
@@ -140,6 +205,18 @@ mod tests {
         assert!(update.color.is_none());
     }
 
+    #[test]
+    fn test_ship_update_carries_health_and_shield() {
+        let mut ship = create_test_ship(1);
+        ship.health = 42.0;
+        ship.shield = 7.5;
+
+        let update = ShipUpdate::without_color(&ship, 0, 20);
+
+        assert_eq!(update.health, 42.0);
+        assert_eq!(update.shield, 7.5);
+    }
+
     #[test]
     fn test_delta_add_ship_update() {
         let mut delta = DeltaState::new(1, false);
@@ -160,4 +237,47 @@ mod tests {
         assert_eq!(delta.removed_ship_ids.len(), 1);
         assert_eq!(delta.removed_ship_ids[0], 42);
     }
+
+    #[test]
+    fn test_delta_asteroids_only_serialized_when_changed() {
+        let mut delta = DeltaState::new(1, false);
+        let json = serde_json::to_string(&delta).unwrap();
+        assert!(!json.contains("changed_asteroids"));
+
+        delta.add_asteroid_update(Asteroid::new(7, Vec2::ZERO, Vec2::new(5.0, 0.0), 20.0));
+        assert!(delta.has_changes());
+
+        let json = serde_json::to_string(&delta).unwrap();
+        let parsed: DeltaState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.changed_asteroids.len(), 1);
+        assert_eq!(parsed.changed_asteroids[0].id, 7);
+    }
+
+    #[test]
+    fn test_delta_pickups() {
+        use crate::entities::PickupKind;
+
+        let mut delta = DeltaState::new(1, false);
+        delta.add_pickup(Pickup::new(3, PickupKind::SpeedBoost, Vec2::new(10.0, 20.0)));
+        delta.add_removed_pickup(2);
+        assert!(delta.has_changes());
+
+        let json = serde_json::to_string(&delta).unwrap();
+        let parsed: DeltaState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.added_pickups[0].kind, PickupKind::SpeedBoost);
+        assert_eq!(parsed.removed_pickup_ids, vec![2]);
+    }
+
+    #[test]
+    fn test_ship_update_carries_modifiers() {
+        use crate::entities::{DamageConstants, PickupConstants, PickupKind};
+
+        let mut ship = create_test_ship(1);
+        let update = ShipUpdate::without_color(&ship, 0, 20);
+        assert!(update.modifiers.is_empty());
+
+        ship.collect(PickupKind::RapidFire, &PickupConstants::default(), &DamageConstants::default());
+        let update = ShipUpdate::without_color(&ship, 0, 20);
+        assert_eq!(update.modifiers.len(), 1);
+    }
 }
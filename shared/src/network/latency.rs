@@ -0,0 +1,174 @@
+/// Length of a server tick in milliseconds (the game loop runs at 20 Hz)
+pub const SERVER_TICK_MS: u64 = 50;
+
+/// Rolling round-trip time and jitter estimate
+///
+/// Smoothed like TCP's retransmission timer (RFC 6298): each sample moves
+/// the RTT 1/8 of the way and the jitter (mean deviation) 1/4 of the way.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RttEstimator {
+    rtt_ms: Option<f64>,
+    jitter_ms: f64,
+}
+
+impl RttEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one measured round trip; negative or non-finite samples are ignored
+    pub fn add_sample(&mut self, rtt_ms: f64) {
+        if !rtt_ms.is_finite() || rtt_ms < 0.0 {
+            return;
+        }
+        match self.rtt_ms {
+            None => {
+                self.rtt_ms = Some(rtt_ms);
+                self.jitter_ms = rtt_ms / 2.0;
+            }
+            Some(rtt) => {
+                self.jitter_ms += ((rtt - rtt_ms).abs() - self.jitter_ms) / 4.0;
+                self.rtt_ms = Some(rtt + (rtt_ms - rtt) / 8.0);
+            }
+        }
+    }
+
+    /// Smoothed round trip, None before the first sample
+    pub fn rtt_ms(&self) -> Option<f64> {
+        self.rtt_ms
+    }
+
+    /// Mean deviation of the round trip
+    pub fn jitter_ms(&self) -> f64 {
+        self.jitter_ms
+    }
+}
+
+/// Ping/pong exchanges kept to pick the clock offset from
+const CLOCK_SAMPLES: usize = 8;
+
+/// One ping/pong exchange, in client milliseconds except `server_time`
+#[derive(Debug, Clone, Copy)]
+struct ClockSample {
+    rtt_ms: f64,
+    offset_ms: f64,
+    server_time: f64,
+    tick: u64,
+}
+
+/// Client estimate of the server clock and tick (NTP-style)
+///
+/// Each exchange gives `offset = server_time - (sent + received) / 2`,
+/// which is exact when both legs take the same time. The error grows with
+/// the round trip, so the sample with the smallest RTT among the last few
+/// is trusted.
+#[derive(Debug, Clone, Default)]
+pub struct ClockSync {
+    samples: Vec<ClockSample>,
+}
+
+impl ClockSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a pong: when the ping left, the server's time and tick when
+    /// it answered, and when the pong arrived
+    pub fn add_sample(&mut self, sent_at: f64, server_time: f64, tick: u64, received_at: f64) {
+        let rtt_ms = received_at - sent_at;
+        if !rtt_ms.is_finite() || rtt_ms < 0.0 {
+            return;
+        }
+        if self.samples.len() == CLOCK_SAMPLES {
+            self.samples.remove(0);
+        }
+        self.samples.push(ClockSample {
+            rtt_ms,
+            offset_ms: server_time - (sent_at + received_at) / 2.0,
+            server_time,
+            tick,
+        });
+    }
+
+    fn best(&self) -> Option<&ClockSample> {
+        self.samples
+            .iter()
+            .min_by(|a, b| a.rtt_ms.total_cmp(&b.rtt_ms))
+    }
+
+    /// Server clock minus client clock, None before the first pong
+    pub fn offset_ms(&self) -> Option<f64> {
+        self.best().map(|sample| sample.offset_ms)
+    }
+
+    /// Server clock at client time `now`
+    pub fn server_time(&self, now: f64) -> Option<f64> {
+        self.offset_ms().map(|offset| now + offset)
+    }
+
+    /// Fractional server tick at client time `now`
+    pub fn server_tick(&self, now: f64) -> Option<f64> {
+        let sample = self.best()?;
+        let elapsed = now + sample.offset_ms - sample.server_time;
+        Some(sample.tick as f64 + elapsed / SERVER_TICK_MS as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtt_smooths_and_tracks_jitter() {
+        let mut rtt = RttEstimator::new();
+        assert_eq!(rtt.rtt_ms(), None);
+
+        rtt.add_sample(100.0);
+        assert_eq!(rtt.rtt_ms(), Some(100.0));
+
+        rtt.add_sample(180.0);
+        assert_eq!(rtt.rtt_ms(), Some(110.0));
+        assert_eq!(rtt.jitter_ms(), 57.5);
+
+        rtt.add_sample(f64::NAN);
+        rtt.add_sample(-5.0);
+        assert_eq!(rtt.rtt_ms(), Some(110.0));
+    }
+
+    #[test]
+    fn test_rtt_settles_on_steady_link() {
+        let mut rtt = RttEstimator::new();
+        for _ in 0..100 {
+            rtt.add_sample(60.0);
+        }
+        assert!((rtt.rtt_ms().unwrap() - 60.0).abs() < 1e-6);
+        assert!(rtt.jitter_ms() < 1e-6);
+    }
+
+    #[test]
+    fn test_clock_offset_from_symmetric_exchange() {
+        // Server clock runs 5000ms ahead, 40ms each way
+        let mut clock = ClockSync::new();
+        clock.add_sample(1000.0, 6040.0, 120, 1080.0);
+
+        assert_eq!(clock.offset_ms(), Some(5000.0));
+        assert_eq!(clock.server_time(1100.0), Some(6100.0));
+        assert_eq!(clock.server_tick(1100.0), Some(121.2));
+    }
+
+    #[test]
+    fn test_clock_trusts_fastest_exchange() {
+        let mut clock = ClockSync::new();
+        // Slow exchange: the return leg took 300ms more, offset off by 150ms
+        clock.add_sample(0.0, 5040.0, 0, 380.0);
+        clock.add_sample(1000.0, 6040.0, 20, 1080.0);
+        assert_eq!(clock.offset_ms(), Some(5000.0));
+
+        // Old samples roll out
+        for i in 0..CLOCK_SAMPLES {
+            let sent = 2000.0 + i as f64 * 1000.0;
+            clock.add_sample(sent, sent + 5100.0, 0, sent + 100.0);
+        }
+        assert_eq!(clock.offset_ms(), Some(5050.0));
+    }
+}
@@ -1,17 +1,123 @@
 use serde::{Deserialize, Serialize};
-use crate::entities::{Ship, Asteroid};
+use crate::entities::{Ship, Asteroid, DamageSource, Pickup};
 use super::delta::DeltaState;
+use super::round::{ModeKind, RoundResults};
+
+/// Version of the client/server message format
+///
+/// Bump whenever `ServerMessage`, `ClientMessage` or anything they carry
+/// changes shape. Exchanged in `Hello` along with the game constants hash.
+pub const PROTOCOL_VERSION: u32 = 4;
 
 /// Messages sent from server to client
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ServerMessage {
-    Welcome { assigned_id: u32 },
+    /// Reply to `Hello`: the player's ID and the name they play under
+    Welcome { assigned_id: u32, name: String },
+    /// Reply to the client's `Ping`: its timestamp echoed, with the server's
+    /// clock (ms since start) and tick when it answered
+    Pong { client_time: f64, server_time: f64, tick: u64 },
+    /// Sent every second to measure the round trip; the client echoes
+    /// `server_time` in a `Pong`
+    Ping { server_time: f64 },
+    /// Chat line relayed to every client (muting is up to each client)
+    Chat { from: u32, name: String, text: String },
+    /// Reply to a client message the server could not understand (the
+    /// message is dropped, the connection stays open)
+    Error { kind: ClientErrorKind, detail: String },
+    /// Reply to a `Hello` from a client built with a different protocol
+    /// version or game constants; the connection is closed right after
+    IncompatibleClient { protocol_version: u32, constants_hash: u64 },
     GameState(GameState),
     DeltaState(DeltaState),
+    /// Sent to a single client after it stopped providing input for too long
+    Idle { action: IdleAction, timeout_secs: u32 },
+    /// Broadcast when a ship's hull reaches zero (it respawns right after)
+    ShipDestroyed { ship_id: u32, source: DamageSource },
+    /// Broadcast while the server waits for enough players to start a match
+    /// (and to each client that connects meanwhile)
+    MatchWaiting { players: u32, min_players: u32 },
+    /// Broadcast every second of the countdown before a round (and to each
+    /// client that connects meanwhile)
+    MatchCountdown { secs: u32 },
+    /// Broadcast when a round starts (every ship respawns and scores reset),
+    /// and sent to clients joining mid-round
+    MatchStarted { mode: ModeKind, remaining_secs: u32 },
+    /// Broadcast when a round is won or runs out of time; the results stay up
+    /// until the next `MatchWaiting` or `MatchCountdown`
+    RoundEnded(RoundResults),
+}
+
+/// What the server did with a client that went idle
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleAction {
+    /// Ship removed from the world; the next non-neutral input respawns it
+    Parked,
+    /// Connection closed by the server
+    Kicked,
+}
+
+/// Why a client message was rejected
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientErrorKind {
+    /// Not JSON, not an object with a `type`, or fields don't match the type
+    Malformed,
+    /// `type` names no `ClientMessage` variant
+    UnknownType,
 }
 
 /// Messages sent from client to server
+///
+/// Tagged like `ServerMessage`: `{"type": "Input", "player_id": 1, ...}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ClientMessage {
+    /// First message after connecting, before any input
+    ///
+    /// `protocol_version` and `constants_hash` must match the server's
+    /// (missing from clients that predate them).
+    Hello {
+        name: String,
+        client_version: String,
+        #[serde(default)]
+        protocol_version: u32,
+        #[serde(default)]
+        constants_hash: u64,
+    },
+    Input(ClientInput),
+    /// Asks for a `Pong` to measure the round trip and the server clock
+    /// (`client_time` in the client's milliseconds, echoed back)
+    Ping { client_time: f64 },
+    /// Answer to the server's `Ping`, `server_time` echoed unchanged
+    Pong { server_time: f64 },
+    /// Chat line for every player (rate limited, cut and filtered by the server)
+    Chat { text: String },
+}
+
+impl ClientMessage {
+    /// Every `type` tag a client may send
+    pub const TYPES: [&'static str; 5] = ["Hello", "Input", "Ping", "Pong", "Chat"];
+
+    /// Parse a client message, telling unknown types apart from malformed ones
+    pub fn parse(text: &str) -> Result<Self, (ClientErrorKind, String)> {
+        serde_json::from_str(text).map_err(|error| {
+            let tag = serde_json::from_str::<serde_json::Value>(text)
+                .ok()
+                .and_then(|value| value.get("type")?.as_str().map(str::to_string));
+            match tag {
+                Some(tag) if !Self::TYPES.contains(&tag.as_str()) => {
+                    (ClientErrorKind::UnknownType, format!("unknown message type '{}'", tag))
+                }
+                _ => (ClientErrorKind::Malformed, error.to_string()),
+            }
+        })
+    }
+}
+
+/// Player input (thrust and rotation), sent many times per second
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientInput {
     pub player_id: u32,
@@ -24,6 +130,11 @@ pub struct ClientInput {
 pub struct GameState {
     pub ships: Vec<Ship>,
     pub asteroids: Vec<Asteroid>,
+
+    /// Pickups waiting to be collected
+    #[serde(default)]
+    pub pickups: Vec<Pickup>,
+
     pub tick: u64,
 }
 
@@ -32,6 +143,7 @@ impl GameState {
         Self {
             ships: Vec::new(),
             asteroids: Vec::new(),
+            pickups: Vec::new(),
             tick: 0,
         }
     }
@@ -42,3 +154,84 @@ impl Default for GameState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: &ClientMessage) -> ClientMessage {
+        ClientMessage::parse(&serde_json::to_string(message).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_client_messages_round_trip() {
+        let hello = ClientMessage::Hello {
+            name: "Starbuck".to_string(),
+            client_version: "0.1.0".to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            constants_hash: 42,
+        };
+        assert!(matches!(
+            round_trip(&hello),
+            ClientMessage::Hello { name, protocol_version: PROTOCOL_VERSION, constants_hash: 42, .. } if name == "Starbuck"
+        ));
+
+        let input = ClientMessage::Input(ClientInput { player_id: 3, thrust: 1.0, rotate: -0.5 });
+        assert!(matches!(
+            round_trip(&input),
+            ClientMessage::Input(ClientInput { player_id: 3, thrust, rotate }) if thrust == 1.0 && rotate == -0.5
+        ));
+
+        let ping = ClientMessage::Ping { client_time: 1234.5 };
+        assert!(matches!(round_trip(&ping), ClientMessage::Ping { client_time } if client_time == 1234.5));
+
+        let pong = ClientMessage::Pong { server_time: 99.25 };
+        assert!(matches!(round_trip(&pong), ClientMessage::Pong { server_time } if server_time == 99.25));
+
+        let chat = ClientMessage::Chat { text: "So say we all".to_string() };
+        assert!(matches!(round_trip(&chat), ClientMessage::Chat { text } if text == "So say we all"));
+    }
+
+    #[test]
+    fn test_every_client_type_is_known() {
+        let messages = [
+            ClientMessage::Hello {
+                name: String::new(),
+                client_version: String::new(),
+                protocol_version: 0,
+                constants_hash: 0,
+            },
+            ClientMessage::Input(ClientInput { player_id: 0, thrust: 0.0, rotate: 0.0 }),
+            ClientMessage::Ping { client_time: 0.0 },
+            ClientMessage::Pong { server_time: 0.0 },
+            ClientMessage::Chat { text: String::new() },
+        ];
+        for message in &messages {
+            let value = serde_json::to_value(message).unwrap();
+            assert!(ClientMessage::TYPES.contains(&value["type"].as_str().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let kind = |text: &str| ClientMessage::parse(text).unwrap_err().0;
+        assert_eq!(kind("not json"), ClientErrorKind::Malformed);
+        assert_eq!(kind(r#"{"player_id":1,"thrust":0.0,"rotate":0.0}"#), ClientErrorKind::Malformed);
+        assert_eq!(kind(r#"{"type":"Input","thrust":"fast"}"#), ClientErrorKind::Malformed);
+        assert_eq!(kind(r#"{"type":"Teleport","x":0}"#), ClientErrorKind::UnknownType);
+    }
+
+    #[test]
+    fn test_server_error_round_trip() {
+        let error = ServerMessage::Error {
+            kind: ClientErrorKind::UnknownType,
+            detail: "unknown message type 'Teleport'".to_string(),
+        };
+        let json = serde_json::to_string(&error).unwrap();
+        assert!(json.contains(r#""type":"Error""#) && json.contains(r#""kind":"unknown_type""#));
+        assert!(matches!(
+            serde_json::from_str::<ServerMessage>(&json).unwrap(),
+            ServerMessage::Error { kind: ClientErrorKind::UnknownType, .. }
+        ));
+    }
+}
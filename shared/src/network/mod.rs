@@ -1,5 +1,9 @@
 pub mod messages;
 pub mod delta;
+pub mod round;
+pub mod latency;
 
 pub use messages::*;
 pub use delta::*;
+pub use round::*;
+pub use latency::*;
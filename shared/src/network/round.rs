@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use crate::entities::Team;
+
+/// Rules in play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeKind {
+    #[default]
+    FreeForAll,
+    TeamDeathmatch,
+}
+
+/// Who won a round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Winner {
+    Player { id: u32 },
+    Team { team: Team },
+    /// Time ran out with the lead tied (or nobody playing)
+    Draw,
+}
+
+/// One player's tally for a round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerScore {
+    pub player_id: u32,
+    pub kills: u32,
+    pub deaths: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<Team>,
+}
+
+/// Team total for a round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamScore {
+    pub team: Team,
+    pub kills: u32,
+}
+
+/// End-of-round summary (broadcast in `ServerMessage::RoundEnded`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundResults {
+    pub mode: ModeKind,
+    pub winner: Winner,
+
+    /// Best first
+    pub scores: Vec<PlayerScore>,
+
+    /// Only in team modes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub team_scores: Vec<TeamScore>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_results_serialization() {
+        let results = RoundResults {
+            mode: ModeKind::TeamDeathmatch,
+            winner: Winner::Team { team: Team::Blue },
+            scores: vec![PlayerScore { player_id: 1, kills: 3, deaths: 1, team: Some(Team::Blue) }],
+            team_scores: vec![TeamScore { team: Team::Blue, kills: 3 }],
+        };
+
+        let json = serde_json::to_string(&results).unwrap();
+        assert!(json.contains(r#""winner":{"kind":"team","team":"blue"}"#));
+        assert!(json.contains(r#""mode":"team_deathmatch""#));
+
+        let parsed: RoundResults = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, results);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::math::Vec2;
+
+/// Entity stored in a `SpatialGrid`
+#[derive(Debug, Clone, Copy)]
+pub struct GridEntry<K> {
+    pub key: K,
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+/// Uniform grid broad phase over the toroidal world
+///
+/// Replaces O(n²) all-pairs checks: each entity is bucketed by the cell
+/// containing its center, and queries only visit the cells within reach.
+/// Cell indices wrap around like `wrap_position`, so an entity at x=1990
+/// is a neighbor of one at x=-1990.
+///
+/// Build it once per tick (`clear` + `insert`), then query.
+/// `K` is whatever the caller uses to identify entities (ID, index...).
+#[derive(Debug, Clone)]
+pub struct SpatialGrid<K> {
+    world_limit: f32,
+    cell_size: f32,
+    cells_per_axis: usize,
+    cells: Vec<Vec<usize>>,
+    entries: Vec<GridEntry<K>>,
+    max_radius: f32,
+}
+
+impl<K: Copy> SpatialGrid<K> {
+    /// Create a grid covering [-world_limit, world_limit] on both axes
+    ///
+    /// `cell_size` is rounded so cells tile the world exactly. A good value
+    /// is about twice the largest entity radius.
+    pub fn new(world_limit: f32, cell_size: f32) -> Self {
+        let world_size = world_limit * 2.0;
+        let cells_per_axis = ((world_size / cell_size).floor() as usize).max(1);
+
+        Self {
+            world_limit,
+            cell_size: world_size / cells_per_axis as f32,
+            cells_per_axis,
+            cells: vec![Vec::new(); cells_per_axis * cells_per_axis],
+            entries: Vec::new(),
+            max_radius: 0.0,
+        }
+    }
+
+    /// Remove all entities (keeps allocations for the next tick)
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.entries.clear();
+        self.max_radius = 0.0;
+    }
+
+    pub fn insert(&mut self, key: K, position: Vec2, radius: f32) {
+        let (cx, cy) = self.cell_coords(position);
+        let index = self.entries.len();
+        self.entries.push(GridEntry { key, position, radius });
+        self.cells[cy * self.cells_per_axis + cx].push(index);
+        self.max_radius = self.max_radius.max(radius);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entities that may overlap a circle (broad phase only, no distance test)
+    pub fn candidates(&self, center: Vec2, radius: f32) -> Vec<&GridEntry<K>> {
+        let mut out = Vec::new();
+        self.for_each_near(center, radius + self.max_radius, |index| {
+            out.push(&self.entries[index]);
+        });
+        out
+    }
+
+    /// Keys of entities whose circle overlaps the given circle (wrap-aware)
+    pub fn query_radius(&self, center: Vec2, radius: f32) -> Vec<K> {
+        let mut out = Vec::new();
+        self.for_each_near(center, radius + self.max_radius, |index| {
+            let entry = &self.entries[index];
+            let reach = radius + entry.radius;
+            if self.distance_squared(center, entry.position) < reach * reach {
+                out.push(entry.key);
+            }
+        });
+        out
+    }
+
+    /// Every pair of overlapping entities (wrap-aware), each pair once
+    pub fn query_pairs(&self) -> Vec<(K, K)> {
+        let mut out = Vec::new();
+        for (i, a) in self.entries.iter().enumerate() {
+            self.for_each_near(a.position, a.radius + self.max_radius, |j| {
+                if j <= i {
+                    return;
+                }
+                let b = &self.entries[j];
+                let reach = a.radius + b.radius;
+                if self.distance_squared(a.position, b.position) < reach * reach {
+                    out.push((a.key, b.key));
+                }
+            });
+        }
+        out
+    }
+
+    /// Visit every entry index in cells within `reach` of `center`
+    fn for_each_near(&self, center: Vec2, reach: f32, mut visit: impl FnMut(usize)) {
+        let n = self.cells_per_axis as isize;
+        let (cx, cy) = self.cell_coords(center);
+        let span = (reach / self.cell_size).ceil() as isize;
+
+        // When the reach covers the whole axis, visit each cell exactly once
+        let offsets = if 2 * span + 1 >= n { 0..=(n - 1) } else { -span..=span };
+
+        for dy in offsets.clone() {
+            let y = if 2 * span + 1 >= n { dy } else { (cy as isize + dy).rem_euclid(n) };
+            for dx in offsets.clone() {
+                let x = if 2 * span + 1 >= n { dx } else { (cx as isize + dx).rem_euclid(n) };
+                for &index in &self.cells[(y * n + x) as usize] {
+                    visit(index);
+                }
+            }
+        }
+    }
+
+    fn cell_coords(&self, position: Vec2) -> (usize, usize) {
+        let n = self.cells_per_axis as isize;
+        let x = ((position.x + self.world_limit) / self.cell_size).floor() as isize;
+        let y = ((position.y + self.world_limit) / self.cell_size).floor() as isize;
+        (x.rem_euclid(n) as usize, y.rem_euclid(n) as usize)
+    }
+
+    /// Squared shortest distance on the torus
+    fn distance_squared(&self, a: Vec2, b: Vec2) -> f32 {
+        a.wrapped_distance_squared_to(b, self.world_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut keys: Vec<u32>) -> Vec<u32> {
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_query_radius_finds_overlapping() {
+        let mut grid = SpatialGrid::new(2000.0, 100.0);
+        grid.insert(1, Vec2::new(0.0, 0.0), 20.0);
+        grid.insert(2, Vec2::new(30.0, 0.0), 20.0);
+        grid.insert(3, Vec2::new(500.0, 500.0), 20.0);
+
+        assert_eq!(sorted(grid.query_radius(Vec2::new(10.0, 0.0), 5.0)), vec![1, 2]);
+        assert!(grid.query_radius(Vec2::new(-500.0, -500.0), 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_query_radius_across_wrap_seam() {
+        let mut grid = SpatialGrid::new(2000.0, 100.0);
+        grid.insert(1, Vec2::new(1990.0, 0.0), 20.0);
+
+        // 20 px apart across the seam
+        assert_eq!(grid.query_radius(Vec2::new(-1990.0, 0.0), 5.0), vec![1]);
+    }
+
+    #[test]
+    fn test_query_pairs_reports_each_pair_once() {
+        let mut grid = SpatialGrid::new(2000.0, 100.0);
+        grid.insert(1, Vec2::new(0.0, 0.0), 20.0);
+        grid.insert(2, Vec2::new(30.0, 0.0), 20.0);
+        grid.insert(3, Vec2::new(60.0, 0.0), 20.0);
+        grid.insert(4, Vec2::new(1000.0, 0.0), 20.0);
+
+        let mut pairs: Vec<_> = grid
+            .query_pairs()
+            .into_iter()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        pairs.sort();
+
+        assert_eq!(pairs, vec![(1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn test_query_pairs_at_corner_seam() {
+        let mut grid = SpatialGrid::new(2000.0, 100.0);
+        grid.insert(1, Vec2::new(1995.0, 1995.0), 10.0);
+        grid.insert(2, Vec2::new(-1995.0, -1995.0), 10.0);
+
+        assert_eq!(grid.query_pairs().len(), 1);
+    }
+
+    #[test]
+    fn test_large_radius_on_small_grid_visits_cells_once() {
+        let mut grid = SpatialGrid::new(100.0, 50.0);
+        grid.insert(1, Vec2::new(0.0, 0.0), 10.0);
+        grid.insert(2, Vec2::new(5.0, 0.0), 10.0);
+
+        assert_eq!(grid.query_radius(Vec2::ZERO, 500.0).len(), 2);
+        assert_eq!(grid.query_pairs().len(), 1);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut grid = SpatialGrid::new(500.0, 40.0);
+        let mut points = Vec::new();
+        let mut seed = 12345u32;
+        for id in 0..200u32 {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            let x = (seed >> 8) as f32 / (1u32 << 24) as f32 * 1000.0 - 500.0;
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            let y = (seed >> 8) as f32 / (1u32 << 24) as f32 * 1000.0 - 500.0;
+            let radius = 5.0 + (id % 4) as f32 * 5.0;
+            grid.insert(id, Vec2::new(x, y), radius);
+            points.push((id, Vec2::new(x, y), radius));
+        }
+
+        let mut expected = Vec::new();
+        for (i, (a, pa, ra)) in points.iter().enumerate() {
+            for (b, pb, rb) in &points[i + 1..] {
+                let reach = ra + rb;
+                if grid.distance_squared(*pa, *pb) < reach * reach {
+                    expected.push((*a, *b));
+                }
+            }
+        }
+
+        let mut actual: Vec<_> = grid
+            .query_pairs()
+            .into_iter()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        actual.sort();
+        expected.sort();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -11,6 +11,189 @@ pub fn check_collision(pos1: Vec2, radius1: f32, pos2: Vec2, radius2: f32) -> bo
     distance(pos1, pos2) < collision_distance
 }
 
+/// Shortest distance between two points in the toroidal world
+pub fn wrapped_distance(p1: Vec2, p2: Vec2, world_limit: f32) -> f32 {
+    p1.wrapped_distance_to(p2, world_limit)
+}
+
+/// Check collision between two circular objects in the toroidal world
+///
+/// Same as `check_collision`, but objects on opposite sides of the wrap
+/// seam are measured the short way round.
+pub fn check_collision_wrapped(
+    pos1: Vec2,
+    radius1: f32,
+    pos2: Vec2,
+    radius2: f32,
+    world_limit: f32,
+) -> bool {
+    let collision_distance = radius1 + radius2;
+    pos1.wrapped_distance_squared_to(pos2, world_limit) < collision_distance * collision_distance
+}
+
+/// Time of impact between two moving circles (swept-circle test)
+///
+/// Each circle moves in a straight line from `pos` by `motion` over one step.
+/// Returns the fraction of the step (0.0..=1.0) at which they first touch,
+/// or None if they stay apart for the whole step. Circles that already
+/// overlap at the start return `Some(0.0)`.
+///
+/// Unlike `check_collision` at the end of the step, this can't tunnel: a
+/// fast object passing through a small one is caught at any speed.
+pub fn swept_circle_toi(
+    pos1: Vec2,
+    motion1: Vec2,
+    radius1: f32,
+    pos2: Vec2,
+    motion2: Vec2,
+    radius2: f32,
+) -> Option<f32> {
+    relative_toi(pos2 - pos1, motion2 - motion1, radius1 + radius2)
+}
+
+/// `swept_circle_toi` in the toroidal world
+///
+/// The starting offset is measured the short way round, so objects moving
+/// across the wrap seam are handled like anywhere else.
+pub fn swept_circle_toi_wrapped(
+    pos1: Vec2,
+    motion1: Vec2,
+    radius1: f32,
+    pos2: Vec2,
+    motion2: Vec2,
+    radius2: f32,
+    world_limit: f32,
+) -> Option<f32> {
+    relative_toi(
+        pos1.wrapped_delta_to(pos2, world_limit),
+        motion2 - motion1,
+        radius1 + radius2,
+    )
+}
+
+/// Contact between a polygon and a circle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Unit vector from the polygon toward the circle
+    pub normal: Vec2,
+
+    /// How far the circle overlaps the polygon
+    pub depth: f32,
+}
+
+/// Narrow-phase test between a polygon and a circle
+///
+/// `polygon` lists the outline's vertices in order, in the same frame as
+/// `center` (usually relative to the polygon's own center). Works for
+/// concave outlines. Returns None if they don't overlap.
+pub fn polygon_circle_contact(polygon: &[Vec2], center: Vec2, radius: f32) -> Option<Contact> {
+    if polygon.len() < 3 {
+        return None;
+    }
+
+    // Closest point on the outline, and whether the center is inside (crossing test)
+    let mut closest = polygon[0];
+    let mut closest_squared = f32::INFINITY;
+    let mut inside = false;
+
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+
+        let point = closest_point_on_segment(a, b, center);
+        let distance_squared = point.distance_squared_to(center);
+        if distance_squared < closest_squared {
+            closest = point;
+            closest_squared = distance_squared;
+        }
+
+        if (a.y > center.y) != (b.y > center.y) {
+            let x = a.x + (center.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if center.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    let distance = closest_squared.sqrt();
+    if !inside && distance >= radius {
+        return None;
+    }
+
+    let mut normal = (center - closest).normalized();
+    if inside {
+        normal *= -1.0; // Push the circle out through the nearest edge
+    }
+    if normal == Vec2::ZERO {
+        normal = closest.normalized(); // Center exactly on the outline
+    }
+
+    let depth = if inside { radius + distance } else { radius - distance };
+    Some(Contact { normal, depth })
+}
+
+/// Time of impact between a moving circle and a polygon (sub-stepped)
+///
+/// Works in the polygon's frame: the circle starts at `start` and moves by
+/// `motion` relative to it. Checks from fraction `from` (usually the time the
+/// circle reached the polygon's bounding circle) to the end of the step, in
+/// steps of at most half the radius. Returns None if it never touches.
+pub fn swept_circle_polygon_toi(
+    polygon: &[Vec2],
+    start: Vec2,
+    motion: Vec2,
+    radius: f32,
+    from: f32,
+) -> Option<f32> {
+    let remaining = 1.0 - from;
+    let steps = ((motion.length() * remaining) / (radius * 0.5)).ceil().max(1.0) as u32;
+
+    (0..=steps)
+        .map(|step| from + remaining * step as f32 / steps as f32)
+        .find(|&t| polygon_circle_contact(polygon, start + motion * t, radius).is_some())
+}
+
+fn closest_point_on_segment(a: Vec2, b: Vec2, point: Vec2) -> Vec2 {
+    let edge = b - a;
+    let length_squared = edge.length_squared();
+    if length_squared == 0.0 {
+        return a;
+    }
+
+    let offset = point - a;
+    let t = ((offset.x * edge.x + offset.y * edge.y) / length_squared).clamp(0.0, 1.0);
+    a + edge * t
+}
+
+/// First t in [0, 1] where |offset + motion * t| = reach
+///
+/// Solved in f64: at high speeds the quadratic terms lose too much
+/// precision in f32 and grazing hits would be missed.
+fn relative_toi(offset: Vec2, motion: Vec2, reach: f32) -> Option<f32> {
+    let (dx, dy) = (offset.x as f64, offset.y as f64);
+    let (mx, my) = (motion.x as f64, motion.y as f64);
+    let reach = reach as f64;
+
+    let c = dx * dx + dy * dy - reach * reach;
+    if c < 0.0 {
+        return Some(0.0); // Already overlapping
+    }
+
+    let a = mx * mx + my * my;
+    let b = 2.0 * (dx * mx + dy * my);
+    if a == 0.0 || b >= 0.0 {
+        return None; // Not moving relative to each other, or moving apart
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None; // Closest approach is still out of reach
+    }
+
+    // Numerically stable smaller root (b < 0 here)
+    let t = (2.0 * c) / (-b + discriminant.sqrt());
+    (t <= 1.0).then_some(t as f32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +229,259 @@ mod tests {
         // Exactly touching (distance == sum of radii) should NOT collide (< not <=)
         assert!(!check_collision(pos1, 10.0, pos2, 10.0), "Should not collide when exactly touching");
     }
+
+    #[test]
+    fn test_wrapped_distance_across_seam() {
+        let p1 = Vec2::new(1990.0, 0.0);
+        let p2 = Vec2::new(-1990.0, 0.0);
+        assert_eq!(wrapped_distance(p1, p2, 2000.0), 20.0);
+    }
+
+    #[test]
+    fn test_wrapped_collision_at_all_edges() {
+        let cases = [
+            (Vec2::new(1990.0, 0.0), Vec2::new(-1990.0, 0.0)),
+            (Vec2::new(-1990.0, 0.0), Vec2::new(1990.0, 0.0)),
+            (Vec2::new(0.0, 1990.0), Vec2::new(0.0, -1990.0)),
+            (Vec2::new(0.0, -1990.0), Vec2::new(0.0, 1990.0)),
+        ];
+
+        for (p1, p2) in cases {
+            assert!(!check_collision(p1, 15.0, p2, 15.0), "Plain check misses the seam");
+            assert!(
+                check_collision_wrapped(p1, 15.0, p2, 15.0, 2000.0),
+                "Should collide across the seam: {:?} vs {:?}",
+                p1,
+                p2
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrapped_collision_at_corners() {
+        let p1 = Vec2::new(1995.0, 1995.0);
+        let p2 = Vec2::new(-1995.0, -1995.0);
+        assert!(check_collision_wrapped(p1, 10.0, p2, 10.0, 2000.0));
+        assert!(!check_collision_wrapped(p1, 5.0, p2, 2.0, 2000.0));
+    }
+
+    #[test]
+    fn test_wrapped_collision_matches_plain_inside_world() {
+        let p1 = Vec2::new(0.0, 0.0);
+        let p2 = Vec2::new(20.0, 0.0);
+        assert!(!check_collision_wrapped(p1, 10.0, p2, 10.0, 2000.0));
+        assert!(check_collision_wrapped(p1, 10.0, p2, 10.1, 2000.0));
+    }
+
+    #[test]
+    fn test_toi_head_on() {
+        // 100 px apart, closing at 200 px per step, touching at 20 px
+        let toi = swept_circle_toi(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(100.0, 0.0),
+            10.0,
+            Vec2::new(100.0, 0.0),
+            Vec2::new(-100.0, 0.0),
+            10.0,
+        );
+        assert_eq!(toi, Some(0.4));
+    }
+
+    #[test]
+    fn test_toi_catches_tunneling() {
+        // Ends the step 500 px past a small asteroid: no overlap at either end
+        let start = Vec2::new(-500.0, 0.0);
+        let motion = Vec2::new(1000.0, 0.0);
+        assert!(!check_collision(start + motion, 15.0, Vec2::ZERO, 5.0));
+
+        let toi = swept_circle_toi(start, motion, 15.0, Vec2::ZERO, Vec2::ZERO, 5.0);
+        assert_eq!(toi, Some(0.48));
+    }
+
+    #[test]
+    fn test_toi_misses() {
+        let toi = swept_circle_toi(
+            Vec2::new(-500.0, 50.0),
+            Vec2::new(1000.0, 0.0),
+            15.0,
+            Vec2::ZERO,
+            Vec2::ZERO,
+            5.0,
+        );
+        assert_eq!(toi, None);
+    }
+
+    #[test]
+    fn test_toi_moving_apart_or_too_short() {
+        let toi = swept_circle_toi(Vec2::ZERO, Vec2::new(-50.0, 0.0), 10.0, Vec2::new(30.0, 0.0), Vec2::ZERO, 10.0);
+        assert_eq!(toi, None);
+
+        let toi = swept_circle_toi(Vec2::ZERO, Vec2::new(5.0, 0.0), 10.0, Vec2::new(30.0, 0.0), Vec2::ZERO, 10.0);
+        assert_eq!(toi, None);
+    }
+
+    #[test]
+    fn test_toi_already_overlapping() {
+        let toi = swept_circle_toi(Vec2::ZERO, Vec2::ZERO, 10.0, Vec2::new(5.0, 0.0), Vec2::ZERO, 10.0);
+        assert_eq!(toi, Some(0.0));
+    }
+
+    #[test]
+    fn test_toi_wrapped_across_seam() {
+        // Moving right out of the world onto an asteroid just inside the left edge
+        let toi = swept_circle_toi_wrapped(
+            Vec2::new(1950.0, 0.0),
+            Vec2::new(100.0, 0.0),
+            10.0,
+            Vec2::new(-1970.0, 0.0),
+            Vec2::ZERO,
+            10.0,
+            2000.0,
+        );
+        assert_eq!(toi, Some(0.6));
+    }
+
+    /// 20×20 square centered on the origin
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(-10.0, -10.0),
+            Vec2::new(10.0, -10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(-10.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn test_polygon_circle_separate() {
+        assert_eq!(polygon_circle_contact(&square(), Vec2::new(20.0, 0.0), 5.0), None);
+    }
+
+    #[test]
+    fn test_polygon_circle_touching_edge() {
+        let contact = polygon_circle_contact(&square(), Vec2::new(13.0, 0.0), 5.0).unwrap();
+        assert_eq!(contact.normal, Vec2::new(1.0, 0.0));
+        assert!((contact.depth - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_polygon_corner_is_not_its_bounding_circle() {
+        // Overlaps the square's bounding circle (radius 14.1) but not the square
+        let center = Vec2::new(14.5, 8.0);
+        assert!(center.length() < 14.1 + 4.0);
+        assert_eq!(polygon_circle_contact(&square(), center, 4.0), None);
+    }
+
+    #[test]
+    fn test_circle_center_inside_polygon() {
+        let contact = polygon_circle_contact(&square(), Vec2::new(8.0, 0.0), 1.0).unwrap();
+        assert_eq!(contact.normal, Vec2::new(1.0, 0.0), "Pushed out through the nearest edge");
+        assert!((contact.depth - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_concave_polygon_notch() {
+        // U shape: the notch between the arms is outside the polygon
+        let u_shape = vec![
+            Vec2::new(-10.0, -10.0),
+            Vec2::new(10.0, -10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(5.0, 10.0),
+            Vec2::new(5.0, -5.0),
+            Vec2::new(-5.0, -5.0),
+            Vec2::new(-5.0, 10.0),
+            Vec2::new(-10.0, 10.0),
+        ];
+        assert_eq!(polygon_circle_contact(&u_shape, Vec2::new(0.0, 5.0), 2.0), None);
+        assert!(polygon_circle_contact(&u_shape, Vec2::new(0.0, -8.0), 1.0).is_some());
+    }
+
+    #[test]
+    fn test_swept_circle_polygon_hit() {
+        // Passes straight through the square within one step
+        let toi = swept_circle_polygon_toi(&square(), Vec2::new(-100.0, 0.0), Vec2::new(200.0, 0.0), 5.0, 0.0)
+            .unwrap();
+        // First touch at x = -15, found within one sub-step (half the radius)
+        let at_contact = -100.0 + 200.0 * toi;
+        assert!((-15.0..=-12.5).contains(&at_contact), "Got x = {}", at_contact);
+    }
+
+    #[test]
+    fn test_swept_circle_polygon_miss() {
+        // Clips the bounding circle but not the square
+        let toi = swept_circle_polygon_toi(&square(), Vec2::new(-100.0, 17.0), Vec2::new(200.0, 0.0), 5.0, 0.0);
+        assert_eq!(toi, None);
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn vec2(range: f32) -> impl Strategy<Value = Vec2> {
+            (-range..range, -range..range).prop_map(|(x, y)| Vec2::new(x, y))
+        }
+
+        proptest! {
+            /// Any overlap at a sub-step sample is reported, no matter the speed
+            #[test]
+            fn no_tunneling_at_any_speed(
+                pos1 in vec2(1000.0),
+                motion1 in vec2(100_000.0),
+                radius1 in 1.0f32..50.0,
+                pos2 in vec2(1000.0),
+                motion2 in vec2(1000.0),
+                radius2 in 1.0f32..50.0,
+            ) {
+                let toi = swept_circle_toi(pos1, motion1, radius1, pos2, motion2, radius2);
+
+                for step in 0..=1000 {
+                    let t = step as f32 / 1000.0;
+                    let a = pos1 + motion1 * t;
+                    let b = pos2 + motion2 * t;
+                    // Small margin: sampled positions carry f32 rounding error
+                    if check_collision(a, radius1 * 0.999, b, radius2 * 0.999) {
+                        let hit = toi.expect("overlap during the step must be detected");
+                        prop_assert!(hit <= t + 1e-3, "toi {} after overlap at {}", hit, t);
+                        break;
+                    }
+                }
+            }
+
+            /// A straight shot through a stationary target always hits
+            #[test]
+            fn shot_through_target_always_hits(
+                target in vec2(1000.0),
+                direction in 0.0f32..std::f32::consts::TAU,
+                speed in 1.0f32..1_000_000.0,
+                offset in -0.99f32..0.99,
+                radius in 1.0f32..30.0,
+            ) {
+                let dir = Vec2::new(direction.cos(), direction.sin());
+                let side = Vec2::new(-dir.y, dir.x) * (offset * radius * 2.0);
+                // Start and end equally far on either side of the target
+                let start = target + side - dir * (speed / 2.0 + radius * 2.0);
+                let motion = dir * (speed + radius * 4.0);
+
+                let toi = swept_circle_toi(start, motion, radius, target, Vec2::ZERO, radius);
+                prop_assert!(toi.is_some());
+            }
+
+            /// At the reported time of impact the circles are just touching
+            #[test]
+            fn toi_is_first_contact(
+                pos1 in vec2(1000.0),
+                motion1 in vec2(5000.0),
+                pos2 in vec2(1000.0),
+                radius in 1.0f32..50.0,
+            ) {
+                if let Some(t) = swept_circle_toi(pos1, motion1, radius, pos2, Vec2::ZERO, radius) {
+                    let gap = (pos1 + motion1 * t).distance_to(pos2) - radius * 2.0;
+                    let tolerance = 1e-3 * (1.0 + motion1.length());
+                    prop_assert!(gap <= tolerance, "gap {} at t {}", gap, t);
+                    if t > 0.0 {
+                        prop_assert!(gap >= -tolerance, "gap {} at t {}", gap, t);
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,8 +1,12 @@
 pub mod movement;
 pub mod collision;
+pub mod broadphase;
+pub mod response;
 
 pub use movement::*;
 pub use collision::*;
+pub use broadphase::*;
+pub use response::*;
 
 /// Physics constants for game simulation
 #[derive(Debug, Clone, Copy)]
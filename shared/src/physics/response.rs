@@ -0,0 +1,308 @@
+use crate::math::Vec2;
+use super::collision::polygon_circle_contact;
+use super::movement::wrap_position;
+
+/// Collision response settings (from game-constants.toml `[collision]`)
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseConstants {
+    /// Bounce on contact; when false, ships pass through (impact damage applies either way)
+    pub enabled: bool,
+
+    /// Fraction of the closing speed kept after a bounce (1.0 = perfectly elastic)
+    pub restitution: f32,
+}
+
+impl ResponseConstants {
+    pub fn new(enabled: bool, restitution: f32) -> Self {
+        Self {
+            enabled,
+            restitution,
+        }
+    }
+}
+
+/// Gap (pixels) within which touching circles still count as in contact
+///
+/// A swept hit places bodies exactly touching, and f32 rounding can leave
+/// them a hair apart; they must still bounce.
+pub const CONTACT_SLOP: f32 = 0.01;
+
+/// Mass of a circular body (proportional to its area)
+pub fn mass_from_radius(radius: f32) -> f32 {
+    radius * radius
+}
+
+/// Circle taking part in a collision response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Body {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub radius: f32,
+    pub mass: f32,
+}
+
+impl Body {
+    /// Body with mass derived from its radius
+    pub fn new(position: Vec2, velocity: Vec2, radius: f32) -> Self {
+        Self {
+            position,
+            velocity,
+            radius,
+            mass: mass_from_radius(radius),
+        }
+    }
+}
+
+/// Result of resolving one contact
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Impact {
+    /// Unit vector from the first body toward the second
+    pub normal: Vec2,
+
+    /// Closing speed along the normal before the bounce (0 if already separating)
+    pub speed: f32,
+}
+
+/// Resolve a contact between two circles in the toroidal world
+///
+/// Returns None if they don't touch (see `CONTACT_SLOP`). Otherwise pushes them apart along
+/// the contact normal (heavier bodies move less) so they no longer overlap,
+/// and if they were closing, exchanges momentum with the given restitution.
+pub fn resolve_collision(
+    a: &mut Body,
+    b: &mut Body,
+    restitution: f32,
+    world_limit: f32,
+) -> Option<Impact> {
+    let delta = a.position.wrapped_delta_to(b.position, world_limit);
+    let reach = a.radius + b.radius;
+    let distance_squared = delta.length_squared();
+    if distance_squared >= (reach + CONTACT_SLOP) * (reach + CONTACT_SLOP) {
+        return None;
+    }
+
+    let distance = distance_squared.sqrt();
+    let normal = if distance > f32::EPSILON {
+        delta * (1.0 / distance)
+    } else {
+        Vec2::new(1.0, 0.0) // Exactly on top of each other: pick any direction
+    };
+
+    let penetration = (reach - distance).max(0.0);
+    Some(resolve_contact(a, b, normal, penetration, restitution, world_limit))
+}
+
+/// Resolve a contact between a circle and a polygon in the toroidal world
+///
+/// `outline` is the polygon's (rotated) outline relative to `polygon.position`;
+/// its mass still comes from `polygon.radius`. Same as `resolve_collision`
+/// otherwise, with the normal taken from the nearest edge.
+pub fn resolve_polygon_collision(
+    circle: &mut Body,
+    polygon: &mut Body,
+    outline: &[Vec2],
+    restitution: f32,
+    world_limit: f32,
+) -> Option<Impact> {
+    let center = polygon.position.wrapped_delta_to(circle.position, world_limit);
+    let contact = polygon_circle_contact(outline, center, circle.radius + CONTACT_SLOP)?;
+
+    // Contact normal points at the circle; impacts point from the first body
+    let penetration = (contact.depth - CONTACT_SLOP).max(0.0);
+    Some(resolve_contact(
+        circle,
+        polygon,
+        contact.normal * -1.0,
+        penetration,
+        restitution,
+        world_limit,
+    ))
+}
+
+/// Push two touching bodies apart and bounce them
+///
+/// `normal` is the unit vector from `a` toward `b`. The overlap is split by
+/// inverse mass (heavier bodies move less) and, if the bodies were closing,
+/// momentum is exchanged with the given restitution.
+fn resolve_contact(
+    a: &mut Body,
+    b: &mut Body,
+    normal: Vec2,
+    penetration: f32,
+    restitution: f32,
+    world_limit: f32,
+) -> Impact {
+    let inv_a = 1.0 / a.mass;
+    let inv_b = 1.0 / b.mass;
+    let inv_total = inv_a + inv_b;
+
+    // Penetration resolution: split the overlap by inverse mass
+    a.position -= normal * (penetration * inv_a / inv_total);
+    b.position += normal * (penetration * inv_b / inv_total);
+    wrap_position(&mut a.position, world_limit);
+    wrap_position(&mut b.position, world_limit);
+
+    // Impulse along the normal, only if the bodies are moving toward each other
+    let relative = b.velocity - a.velocity;
+    let closing = relative.x * normal.x + relative.y * normal.y;
+    if closing >= 0.0 {
+        return Impact { normal, speed: 0.0 };
+    }
+
+    let impulse = -(1.0 + restitution) * closing / inv_total;
+    a.velocity -= normal * (impulse * inv_a);
+    b.velocity += normal * (impulse * inv_b);
+
+    Impact {
+        normal,
+        speed: -closing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn momentum(a: &Body, b: &Body) -> Vec2 {
+        a.velocity * a.mass + b.velocity * b.mass
+    }
+
+    #[test]
+    fn test_no_contact_is_untouched() {
+        let mut a = Body::new(Vec2::ZERO, Vec2::new(10.0, 0.0), 10.0);
+        let mut b = Body::new(Vec2::new(50.0, 0.0), Vec2::ZERO, 10.0);
+        let before = (a, b);
+
+        assert_eq!(resolve_collision(&mut a, &mut b, 1.0, 2000.0), None);
+        assert_eq!((a, b), before);
+    }
+
+    #[test]
+    fn test_exactly_touching_still_bounces() {
+        let mut a = Body::new(Vec2::ZERO, Vec2::new(100.0, 0.0), 10.0);
+        let mut b = Body::new(Vec2::new(20.0 + CONTACT_SLOP / 2.0, 0.0), Vec2::ZERO, 10.0);
+
+        let impact = resolve_collision(&mut a, &mut b, 1.0, 2000.0).unwrap();
+
+        assert_eq!(impact.speed, 100.0);
+        assert_eq!(a.position, Vec2::ZERO, "No penetration to resolve");
+    }
+
+    #[test]
+    fn test_equal_masses_swap_velocities_when_elastic() {
+        let mut a = Body::new(Vec2::ZERO, Vec2::new(100.0, 0.0), 10.0);
+        let mut b = Body::new(Vec2::new(19.0, 0.0), Vec2::ZERO, 10.0);
+
+        let impact = resolve_collision(&mut a, &mut b, 1.0, 2000.0).unwrap();
+
+        assert_eq!(impact.normal, Vec2::new(1.0, 0.0));
+        assert_eq!(impact.speed, 100.0);
+        assert_eq!(a.velocity, Vec2::ZERO);
+        assert_eq!(b.velocity, Vec2::new(100.0, 0.0));
+    }
+
+    #[test]
+    fn test_momentum_is_conserved() {
+        let mut a = Body::new(Vec2::ZERO, Vec2::new(120.0, 30.0), 15.0);
+        let mut b = Body::new(Vec2::new(20.0, 10.0), Vec2::new(-40.0, 0.0), 25.0);
+        let before = momentum(&a, &b);
+
+        resolve_collision(&mut a, &mut b, 0.6, 2000.0).unwrap();
+
+        let after = momentum(&a, &b);
+        assert!((before.x - after.x).abs() < 0.5);
+        assert!((before.y - after.y).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_restitution_scales_separation_speed() {
+        let mut a = Body::new(Vec2::ZERO, Vec2::new(100.0, 0.0), 10.0);
+        let mut b = Body::new(Vec2::new(19.0, 0.0), Vec2::ZERO, 10.0);
+
+        resolve_collision(&mut a, &mut b, 0.5, 2000.0).unwrap();
+
+        let separating = b.velocity.x - a.velocity.x;
+        assert!((separating - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_penetration_is_resolved_by_mass() {
+        let mut light = Body::new(Vec2::ZERO, Vec2::ZERO, 10.0);
+        let mut heavy = Body::new(Vec2::new(20.0, 0.0), Vec2::ZERO, 20.0);
+
+        let impact = resolve_collision(&mut light, &mut heavy, 1.0, 2000.0).unwrap();
+
+        // Resting contact: pushed apart without a bounce
+        assert_eq!(impact.speed, 0.0);
+        let gap = light.position.distance_to(heavy.position);
+        assert!((gap - 30.0).abs() < 1e-3);
+        // Heavy body (4x mass) moves a quarter as far
+        assert!((light.position.x + 8.0).abs() < 1e-3);
+        assert!((heavy.position.x - 22.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_separating_bodies_keep_velocity() {
+        let mut a = Body::new(Vec2::ZERO, Vec2::new(-50.0, 0.0), 10.0);
+        let mut b = Body::new(Vec2::new(15.0, 0.0), Vec2::new(50.0, 0.0), 10.0);
+
+        resolve_collision(&mut a, &mut b, 1.0, 2000.0).unwrap();
+
+        assert_eq!(a.velocity, Vec2::new(-50.0, 0.0));
+        assert_eq!(b.velocity, Vec2::new(50.0, 0.0));
+    }
+
+    #[test]
+    fn test_bounce_across_wrap_seam() {
+        let mut a = Body::new(Vec2::new(1995.0, 0.0), Vec2::new(100.0, 0.0), 10.0);
+        let mut b = Body::new(Vec2::new(-1995.0, 0.0), Vec2::ZERO, 10.0);
+
+        let impact = resolve_collision(&mut a, &mut b, 1.0, 2000.0).unwrap();
+
+        assert_eq!(impact.normal, Vec2::new(1.0, 0.0));
+        assert_eq!(a.velocity, Vec2::ZERO);
+        assert_eq!(b.velocity, Vec2::new(100.0, 0.0));
+    }
+
+    /// Square outline with half-width 10
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(-10.0, -10.0),
+            Vec2::new(10.0, -10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(-10.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn test_circle_bounces_off_polygon_edge() {
+        let mut circle = Body::new(Vec2::new(14.0, 0.0), Vec2::new(-50.0, 0.0), 5.0);
+        let mut polygon = Body::new(Vec2::ZERO, Vec2::ZERO, 100.0); // Effectively immovable
+
+        let impact = resolve_polygon_collision(&mut circle, &mut polygon, &square(), 1.0, 2000.0).unwrap();
+
+        assert_eq!(impact.normal, Vec2::new(-1.0, 0.0));
+        assert!((impact.speed - 50.0).abs() < 1e-3);
+        assert!(circle.velocity.x > 45.0, "Should bounce back, got {:?}", circle.velocity);
+        assert!(circle.position.x >= 14.99 - CONTACT_SLOP, "Should be pushed clear of the edge");
+    }
+
+    #[test]
+    fn test_circle_misses_polygon_corner() {
+        // Inside the polygon's bounding circle but clear of the square
+        let mut circle = Body::new(Vec2::new(14.5, 8.0), Vec2::new(-50.0, 0.0), 4.0);
+        let mut polygon = Body::new(Vec2::ZERO, Vec2::ZERO, 15.0);
+
+        assert_eq!(resolve_polygon_collision(&mut circle, &mut polygon, &square(), 1.0, 2000.0), None);
+    }
+
+    #[test]
+    fn test_polygon_bounce_across_wrap_seam() {
+        let mut circle = Body::new(Vec2::new(-1990.0, 0.0), Vec2::new(50.0, 0.0), 5.0);
+        let mut polygon = Body::new(Vec2::new(1996.0, 0.0), Vec2::ZERO, 100.0);
+
+        let impact = resolve_polygon_collision(&mut circle, &mut polygon, &square(), 1.0, 2000.0);
+
+        assert!(impact.is_some(), "14 px apart the short way round");
+    }
+}